@@ -0,0 +1,173 @@
+//! Runs a pipeline with three transformers over records with large blobs
+//!
+//! Every transformer copies its input record and changes one field, as most
+//! transformers do. With shared storage these copies don't duplicate the
+//! blobs. The `copied` case forces a copy of the blob in every transformer,
+//! which shows the cost the shared storage saves.
+//!
+//! Run with `cargo bench --bench pipeline`
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use model::{
+    BoxedError, Initializable,
+    export::Exporter,
+    import::{Importer, RecordHandler},
+    pipeline::{Pipeline, PluginResolver},
+    record::Record,
+    transform::Transformer,
+    value::{Value, shared::SharedBytes},
+    xml::{config::Configuration, process::Process},
+};
+
+const RECORDS: usize = 16;
+const BLOB_SIZE: usize = 4 * 1024 * 1024;
+
+static PROCESS_XML: &str = r#"
+<process id="bench">
+    <importer plugin="bench" />
+    <transformers>
+        <transformer plugin="bench" name="first" />
+        <transformer plugin="bench" name="second" />
+        <transformer plugin="bench" name="third" />
+    </transformers>
+    <exporters>
+        <exporter plugin="bench" />
+    </exporters>
+</process>
+"#;
+
+/// Produces [RECORDS] records, that all reference the same blob
+struct BlobImporter {
+    blob: SharedBytes,
+}
+
+impl Initializable for BlobImporter {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Importer for BlobImporter {
+    fn read(&mut self, handler: &mut dyn RecordHandler) -> Result<(), BoxedError> {
+        for i in 0..RECORDS {
+            let mut record = Record::new();
+            record.set("id", Value::USize(i));
+            record.set("name", Value::from(format!("document {}", i)));
+            record.set("content", Value::Blob(self.blob.clone()));
+            if !handler.handle_record(&mut record)?.is_continue() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Copies the record and sets the field `stage`. With `copy_blob`, the blob
+/// is changed as well, so that it cannot be shared anymore
+struct Stage {
+    name: String,
+    copy_blob: bool,
+}
+
+impl Initializable for Stage {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Transformer for Stage {
+    fn process(&self, record: &Record) -> Result<Record, BoxedError> {
+        let mut result = Record::copy(record);
+        result.set("stage", Value::from(self.name.as_str()));
+        if self.copy_blob
+            && let Some(Value::Blob(blob)) = result.get_mut("content")
+        {
+            blob.make_mut()[0] ^= 1;
+        }
+        Ok(result)
+    }
+}
+
+/// Keeps the records like an exporter, that writes at the end
+#[derive(Default)]
+struct CollectingExporter {
+    records: Vec<Record>,
+}
+
+impl Initializable for CollectingExporter {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Exporter for CollectingExporter {
+    fn write(&mut self, record: &Record) -> Result<(), BoxedError> {
+        self.records.push(Record::copy(record));
+        Ok(())
+    }
+}
+
+struct BenchResolver {
+    blob: SharedBytes,
+    copy_blob: bool,
+}
+
+impl PluginResolver for BenchResolver {
+    fn create_importer(
+        &self,
+        _plugin: &str,
+        _name: Option<&str>,
+    ) -> Result<Box<dyn Importer>, BoxedError> {
+        Ok(Box::new(BlobImporter {
+            blob: self.blob.clone(),
+        }))
+    }
+
+    fn create_transformer(
+        &self,
+        _plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Transformer>, BoxedError> {
+        Ok(Box::new(Stage {
+            name: name.unwrap_or_default().to_string(),
+            copy_blob: self.copy_blob,
+        }))
+    }
+
+    fn create_exporter(
+        &self,
+        _plugin: &str,
+        _name: Option<&str>,
+    ) -> Result<Box<dyn Exporter>, BoxedError> {
+        Ok(Box::<CollectingExporter>::default())
+    }
+}
+
+fn pipeline(c: &mut Criterion) {
+    let process: Process = serde_xml_rs::from_str(PROCESS_XML).unwrap();
+    let blob = SharedBytes::from(vec![0u8; BLOB_SIZE]);
+
+    let mut group = c.benchmark_group("pipeline");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes((RECORDS * BLOB_SIZE) as u64));
+    for (name, copy_blob) in [("shared", false), ("copied", true)] {
+        let resolver = BenchResolver {
+            blob: blob.clone(),
+            copy_blob,
+        };
+        group.bench_with_input(
+            BenchmarkId::from_parameter(name),
+            &resolver,
+            |b, resolver| {
+                b.iter(|| {
+                    let mut pipeline = Pipeline::new(&process, resolver).unwrap();
+                    let report = pipeline.run().unwrap();
+                    assert_eq!(RECORDS, report.imported);
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
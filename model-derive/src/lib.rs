@@ -0,0 +1,158 @@
+//! Derive macros for `model::mapping::IntoRecord` and
+//! `model::mapping::FromRecord`
+//!
+//! Use them through the `derive` feature of the `model` crate, see the
+//! documentation of `model::mapping`.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, parse_macro_input};
+
+/// A named field of the struct with its `#[record(...)]` attributes
+struct RecordField {
+    ident: Ident,
+    name: String,
+    skip: bool,
+}
+
+/// Derives `model::mapping::IntoRecord` and `model::mapping::IntoValue`
+#[proc_macro_derive(IntoRecord, attributes(record))]
+pub fn derive_into_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_into_record(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `model::mapping::FromRecord` and `model::mapping::FromValue`
+#[proc_macro_derive(FromRecord, attributes(record))]
+pub fn derive_from_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_record(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_into_record(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = record_fields(input)?;
+    let pushes = fields.iter().filter(|field| !field.skip).map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+        quote! {
+            if let ::std::option::Option::Some(value) =
+                ::model::mapping::IntoValue::into_field_value(self.#ident)
+            {
+                record
+                    .fields_as_mut()
+                    .push(::model::field::Field::new_value(#name, value));
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::model::mapping::IntoRecord for #ident #type_generics #where_clause {
+            fn into_record(self) -> ::model::record::Record {
+                let mut record = ::model::record::Record::new();
+                #(#pushes)*
+                record
+            }
+        }
+
+        impl #impl_generics ::model::mapping::IntoValue for #ident #type_generics #where_clause {
+            fn into_value(self) -> ::model::value::Value {
+                ::model::value::Value::Record(::model::mapping::IntoRecord::into_record(self))
+            }
+        }
+    })
+}
+
+fn expand_from_record(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let fields = record_fields(input)?;
+    let initializers = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let name = &field.name;
+        if field.skip {
+            quote! { #ident: ::std::default::Default::default() }
+        } else {
+            quote! {
+                #ident: ::model::mapping::FromValue::from_field_value(record.get(#name))
+                    .map_err(|error| error.within_field(#name))?
+            }
+        }
+    });
+    let type_name = ident.to_string();
+
+    Ok(quote! {
+        impl #impl_generics ::model::mapping::FromRecord for #ident #type_generics #where_clause {
+            fn from_record(
+                record: &::model::record::Record,
+            ) -> ::std::result::Result<Self, ::model::mapping::MappingError> {
+                ::std::result::Result::Ok(Self {
+                    #(#initializers,)*
+                })
+            }
+        }
+
+        impl #impl_generics ::model::mapping::FromValue for #ident #type_generics #where_clause {
+            fn from_value(
+                value: &::model::value::Value,
+            ) -> ::std::result::Result<Self, ::model::mapping::MappingError> {
+                match value {
+                    ::model::value::Value::Record(record) => {
+                        ::model::mapping::FromRecord::from_record(record)
+                    }
+                    _ => ::std::result::Result::Err(::model::mapping::MappingError::new(
+                        ::std::format!("Cannot convert {} to {}", value.type_name(), #type_name),
+                    )),
+                }
+            }
+        }
+    })
+}
+
+/// Returns the named fields of a struct with their attributes
+fn record_fields(input: &DeriveInput) -> syn::Result<Vec<RecordField>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs can be mapped to a Record",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "only structs with named fields can be mapped to a Record",
+        ));
+    };
+
+    let mut result = Vec::new();
+    for field in &fields.named {
+        let Some(ident) = field.ident.clone() else {
+            continue;
+        };
+        let mut record_field = RecordField {
+            name: ident.to_string().trim_start_matches("r#").to_string(),
+            ident,
+            skip: false,
+        };
+        for attribute in field.attrs.iter().filter(|a| a.path().is_ident("record")) {
+            attribute.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    record_field.name = meta.value()?.parse::<LitStr>()?.value();
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    record_field.skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+        result.push(record_field);
+    }
+    Ok(result)
+}
@@ -0,0 +1,149 @@
+use chrono::NaiveDate;
+use model::{
+    mapping::{FromRecord, IntoRecord},
+    record::Record,
+    value::Value,
+};
+use serde_json::json;
+
+#[derive(Debug, Clone, PartialEq, IntoRecord, FromRecord)]
+struct Address {
+    city: String,
+    zip: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoRecord, FromRecord)]
+struct Person {
+    #[record(rename = "person_id")]
+    id: u32,
+    name: String,
+    birthday: Option<NaiveDate>,
+    address: Address,
+    previous: Vec<Address>,
+    scores: Vec<Option<i16>>,
+    r#type: char,
+    #[record(skip)]
+    cache: Vec<u8>,
+}
+
+fn person() -> Person {
+    Person {
+        id: 42,
+        name: "John".to_string(),
+        birthday: NaiveDate::from_ymd_opt(1990, 1, 31),
+        address: Address {
+            city: "Vienna".to_string(),
+            zip: Some("1010".to_string()),
+        },
+        previous: vec![Address {
+            city: "Graz".to_string(),
+            zip: None,
+        }],
+        scores: vec![Some(1), None],
+        r#type: 'p',
+        cache: vec![1, 2, 3],
+    }
+}
+
+#[test]
+fn test_into_record() {
+    let record = person().into_record();
+    let names: Vec<&str> = record.iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        vec![
+            "person_id",
+            "name",
+            "birthday",
+            "address",
+            "previous",
+            "scores",
+            "type"
+        ],
+        names
+    );
+    assert_eq!(Some(&Value::U32(42)), record.get("person_id"));
+    assert_eq!(
+        Some(&Value::Date(NaiveDate::from_ymd_opt(1990, 1, 31).unwrap())),
+        record.get("birthday")
+    );
+    assert_eq!(
+        Some(&Value::from("Vienna")),
+        record.get_path(&"address.city".parse().unwrap())
+    );
+    assert_eq!(None, record.get_path(&"previous[0].zip".parse().unwrap()));
+    assert_eq!(
+        Some(&Value::Collection(vec![Value::I16(1), Value::None])),
+        record.get("scores")
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    let mut expected = person();
+    expected.cache = vec![];
+    assert_eq!(
+        expected,
+        Person::from_record(&person().into_record()).unwrap()
+    );
+
+    let mut without_birthday = person();
+    without_birthday.birthday = None;
+    without_birthday.cache = vec![];
+    assert_eq!(
+        without_birthday,
+        Person::from_record(&without_birthday.clone().into_record()).unwrap()
+    );
+}
+
+#[test]
+fn test_from_json() {
+    let record = Record::from(json!({
+        "person_id": 7,
+        "name": "Jane",
+        "birthday": null,
+        "address": {"city": "Linz"},
+        "previous": [],
+        "scores": [3, null],
+        "type": "x"
+    }));
+    let person = Person::from_record(&record).unwrap();
+    assert_eq!(7, person.id);
+    assert_eq!(None, person.birthday);
+    assert_eq!(None, person.address.zip);
+    assert_eq!(vec![Some(3), None], person.scores);
+}
+
+#[test]
+fn test_errors() {
+    let mut record = person().into_record();
+    record.remove("name");
+    let error = Person::from_record(&record).unwrap_err();
+    assert_eq!("name", error.path());
+    assert_eq!("Field 'name': missing", error.to_string());
+
+    let mut record = person().into_record();
+    record
+        .set_path(&"previous[0].city".parse().unwrap(), Value::I32(1))
+        .unwrap();
+    let error = Person::from_record(&record).unwrap_err();
+    assert_eq!(
+        "Field 'previous[0].city': Cannot convert I32 to String",
+        error.to_string()
+    );
+
+    let mut record = person().into_record();
+    record.set("address", Value::Bool(true));
+    let error = Person::from_record(&record).unwrap_err();
+    assert_eq!(
+        "Field 'address': Cannot convert Bool to Address",
+        error.to_string()
+    );
+
+    let mut record = person().into_record();
+    record.set("person_id", Value::I64(-1));
+    let error = Person::from_record(&record).unwrap_err();
+    assert_eq!(
+        "Field 'person_id': Cannot convert I64 to u32: value out of range",
+        error.to_string()
+    );
+}
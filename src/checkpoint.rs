@@ -0,0 +1,227 @@
+//! Checkpoints for resumable imports
+//!
+//! An importer, that can resume, puts a [Checkpoint] into the
+//! [crate::record::meta::RecordMeta] of every record. The checkpoint is an
+//! opaque token, e.g. an offset or the last key, that only the importer
+//! understands. [crate::import::Importer::read_from] reads the records after
+//! a checkpoint.
+//!
+//! A [CheckpointStore] keeps the last checkpoint of each process. The
+//! [crate::pipeline::Pipeline] saves it, after all exporters acknowledged the
+//! records before with [crate::export::Signal::Checkpoint], resumes from it
+//! after a crash and clears it after a complete run. [FileCheckpointStore]
+//! stores the checkpoints as files, [MemoryCheckpointStore] keeps them in
+//! memory.
+//!
+//! # Example
+//! ```
+//! use model::checkpoint::{Checkpoint, CheckpointStore, FileCheckpointStore};
+//!
+//! let directory = std::env::temp_dir().join("checkpoint-doc-example");
+//! let mut store = FileCheckpointStore::new(&directory);
+//! store.save("orders", &Checkpoint::new("offset=4711")).unwrap();
+//!
+//! let store = FileCheckpointStore::new(&directory);
+//! assert_eq!(Some(Checkpoint::new("offset=4711")), store.load("orders").unwrap());
+//! assert_eq!(None, store.load("customers").unwrap());
+//! # std::fs::remove_dir_all(&directory).unwrap();
+//! ```
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::BoxedError;
+
+/// An opaque position in an import source
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Checkpoint(String);
+
+impl Checkpoint {
+    /// Creates a checkpoint from the `token` of an importer
+    pub fn new(token: &str) -> Self {
+        Self(token.to_string())
+    }
+
+    /// Returns the token of the importer
+    pub fn token(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Checkpoint {
+    fn from(token: String) -> Self {
+        Self(token)
+    }
+}
+
+impl Display for Checkpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The error of the default [crate::import::Importer::read_from] of
+/// importers, that cannot resume
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CheckpointsNotSupported;
+
+impl Display for CheckpointsNotSupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The importer does not support checkpoints")
+    }
+}
+
+impl std::error::Error for CheckpointsNotSupported {}
+
+/// Keeps the last [Checkpoint] of each process
+pub trait CheckpointStore {
+    /// Returns the checkpoint of the process `process_id`, if there is one
+    fn load(&self, process_id: &str) -> Result<Option<Checkpoint>, BoxedError>;
+
+    /// Saves the checkpoint of the process `process_id`
+    fn save(&mut self, process_id: &str, checkpoint: &Checkpoint) -> Result<(), BoxedError>;
+
+    /// Removes the checkpoint of the process `process_id`, e.g. after a
+    /// complete run
+    fn clear(&mut self, process_id: &str) -> Result<(), BoxedError>;
+}
+
+/// Allows to pass a store to a [crate::pipeline::Pipeline] and still use it
+/// afterwards
+impl<S: CheckpointStore + ?Sized> CheckpointStore for &mut S {
+    fn load(&self, process_id: &str) -> Result<Option<Checkpoint>, BoxedError> {
+        (**self).load(process_id)
+    }
+
+    fn save(&mut self, process_id: &str, checkpoint: &Checkpoint) -> Result<(), BoxedError> {
+        (**self).save(process_id, checkpoint)
+    }
+
+    fn clear(&mut self, process_id: &str) -> Result<(), BoxedError> {
+        (**self).clear(process_id)
+    }
+}
+
+/// A [CheckpointStore], that keeps the checkpoints in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoints: HashMap<String, Checkpoint>,
+}
+
+impl MemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl CheckpointStore for MemoryCheckpointStore {
+    fn load(&self, process_id: &str) -> Result<Option<Checkpoint>, BoxedError> {
+        Ok(self.checkpoints.get(process_id).cloned())
+    }
+
+    fn save(&mut self, process_id: &str, checkpoint: &Checkpoint) -> Result<(), BoxedError> {
+        self.checkpoints
+            .insert(process_id.to_string(), checkpoint.clone());
+        Ok(())
+    }
+
+    fn clear(&mut self, process_id: &str) -> Result<(), BoxedError> {
+        self.checkpoints.remove(process_id);
+        Ok(())
+    }
+}
+
+/// The content of a checkpoint file
+#[derive(Serialize, Deserialize)]
+struct CheckpointFile {
+    process_id: String,
+    checkpoint: Checkpoint,
+}
+
+/// A [CheckpointStore], that writes one JSON file per process into a
+/// directory
+///
+/// A checkpoint is written to a temporary file first and then renamed, so a
+/// crash while saving leaves the previous checkpoint intact.
+///
+/// # Members
+/// * `directory` - The directory of the checkpoint files. It is created with
+///   the first checkpoint
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    directory: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the checkpoint file of the process `process_id`. Bytes, that
+    /// are not an ASCII letter, digit, `-`, `_` or `.`, are percent-encoded,
+    /// so different ids never share a file
+    pub fn path(&self, process_id: &str) -> PathBuf {
+        let mut name = String::new();
+        for byte in process_id.bytes() {
+            match byte {
+                b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' => {
+                    name.push(byte as char)
+                }
+                _ => name.push_str(&format!("%{:02X}", byte)),
+            }
+        }
+        self.directory.join(format!("{}.checkpoint.json", name))
+    }
+}
+
+impl CheckpointStore for FileCheckpointStore {
+    fn load(&self, process_id: &str) -> Result<Option<Checkpoint>, BoxedError> {
+        let path = self.path(process_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let file: CheckpointFile = serde_json::from_str(&fs::read_to_string(&path)?)
+            .map_err(|e| format!("Invalid checkpoint file '{}': {}", path.display(), e))?;
+        if file.process_id != process_id {
+            return Err(format!(
+                "The checkpoint file '{}' belongs to the process '{}'",
+                path.display(),
+                file.process_id
+            )
+            .into());
+        }
+        Ok(Some(file.checkpoint))
+    }
+
+    fn save(&mut self, process_id: &str, checkpoint: &Checkpoint) -> Result<(), BoxedError> {
+        fs::create_dir_all(&self.directory)?;
+        let path = self.path(process_id);
+        let temporary = path.with_extension("json.tmp");
+        let file = CheckpointFile {
+            process_id: process_id.to_string(),
+            checkpoint: checkpoint.clone(),
+        };
+        fs::write(&temporary, serde_json::to_string(&file)?)?;
+        fs::rename(&temporary, &path)?;
+        Ok(())
+    }
+
+    fn clear(&mut self, process_id: &str) -> Result<(), BoxedError> {
+        let path = self.path(process_id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,121 @@
+use std::{fs, path::PathBuf};
+
+use super::{
+    Checkpoint, CheckpointStore, CheckpointsNotSupported, FileCheckpointStore,
+    MemoryCheckpointStore,
+};
+
+fn directory(name: &str) -> PathBuf {
+    let directory =
+        std::env::temp_dir().join(format!("model-checkpoint-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+#[test]
+fn test_checkpoint() {
+    let checkpoint = Checkpoint::new("offset=8");
+    assert_eq!("offset=8", checkpoint.token());
+    assert_eq!("offset=8", checkpoint.to_string());
+    assert_eq!(checkpoint, Checkpoint::from("offset=8".to_string()));
+    assert_eq!(
+        "\"offset=8\"",
+        serde_json::to_string(&checkpoint).unwrap(),
+        "The token is serialized as a plain string"
+    );
+    assert_eq!(
+        "The importer does not support checkpoints",
+        CheckpointsNotSupported.to_string()
+    );
+}
+
+#[test]
+fn test_memory_store() {
+    let mut store = MemoryCheckpointStore::new();
+    assert_eq!(None, store.load("a").unwrap());
+    store.save("a", &Checkpoint::new("1")).unwrap();
+    store.save("a", &Checkpoint::new("2")).unwrap();
+    store.save("b", &Checkpoint::new("3")).unwrap();
+    assert_eq!(Some(Checkpoint::new("2")), store.load("a").unwrap());
+
+    store.clear("a").unwrap();
+    assert_eq!(None, store.load("a").unwrap());
+    assert_eq!(Some(Checkpoint::new("3")), store.load("b").unwrap());
+}
+
+#[test]
+fn test_file_store() {
+    let directory = directory("file");
+    let mut store = FileCheckpointStore::new(&directory);
+    assert_eq!(None, store.load("orders").unwrap());
+    store.clear("orders").unwrap();
+
+    store.save("orders", &Checkpoint::new("1")).unwrap();
+    store.save("orders", &Checkpoint::new("2")).unwrap();
+    let content = fs::read_to_string(store.path("orders")).unwrap();
+    assert_eq!(
+        serde_json::json!({"process_id": "orders", "checkpoint": "2"}),
+        serde_json::from_str::<serde_json::Value>(&content).unwrap()
+    );
+    assert_eq!(1, fs::read_dir(&directory).unwrap().count());
+
+    // Another instance sees the checkpoint
+    let other = FileCheckpointStore::new(&directory);
+    assert_eq!(Some(Checkpoint::new("2")), other.load("orders").unwrap());
+
+    store.clear("orders").unwrap();
+    assert_eq!(None, store.load("orders").unwrap());
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_file_store_similar_ids() {
+    let directory = directory("similar");
+    let mut store = FileCheckpointStore::new(&directory);
+    assert_ne!(store.path("orders/eu"), store.path("orders_eu"));
+    store.save("orders/eu", &Checkpoint::new("1")).unwrap();
+    store.save("orders_eu", &Checkpoint::new("2")).unwrap();
+    assert_eq!(Some(Checkpoint::new("1")), store.load("orders/eu").unwrap());
+    assert_eq!(Some(Checkpoint::new("2")), store.load("orders_eu").unwrap());
+
+    store.clear("orders/eu").unwrap();
+    assert_eq!(None, store.load("orders/eu").unwrap());
+    assert_eq!(Some(Checkpoint::new("2")), store.load("orders_eu").unwrap());
+
+    // A file of another process, e.g. on a case-insensitive file system
+    fs::copy(store.path("orders_eu"), store.path("customers")).unwrap();
+    assert!(
+        store
+            .load("customers")
+            .unwrap_err()
+            .to_string()
+            .ends_with("belongs to the process 'orders_eu'")
+    );
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn test_file_store_paths() {
+    let directory = directory("paths");
+    let mut store = FileCheckpointStore::new(&directory);
+    assert_eq!(
+        directory.join("%2F..%2Fetc%2Fpasswd.checkpoint.json"),
+        store.path("/../etc/passwd")
+    );
+    assert_eq!(
+        directory.join("%C3%9F%25.checkpoint.json"),
+        store.path("ß%")
+    );
+    store.save("a/b", &Checkpoint::new("x")).unwrap();
+    assert_eq!(Some(Checkpoint::new("x")), store.load("a/b").unwrap());
+
+    fs::write(store.path("broken"), "no json").unwrap();
+    assert!(
+        store
+            .load("broken")
+            .unwrap_err()
+            .to_string()
+            .starts_with("Invalid checkpoint file")
+    );
+    fs::remove_dir_all(&directory).unwrap();
+}
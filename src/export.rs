@@ -1,10 +1,16 @@
 //! RITE exporter trait
-use super::{BoxedError, Initializable, record::Record};
+use super::{
+    BoxedError, Initializable,
+    record::{Record, batch::RecordBatch},
+};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Signal {
     Start = 0,
     End = 1,
+    /// Persist everything written so far. After the signal returned, the
+    /// records before can be skipped on a resume
+    Checkpoint = 2,
 }
 
 impl Default for Signal {
@@ -18,6 +24,23 @@ pub trait Exporter: Initializable {
     ///
     fn write(&mut self, record: &Record) -> Result<(), BoxedError>;
 
+    /// Takes a [RecordBatch] and writes all its records
+    /// With default implementation, that calls `write` for every record.
+    /// Exporters, that write columnar data, can write the columns directly
+    ///
+    /// # Note
+    /// The records of a batch differ from the records pushed into it: every
+    /// record has all columns of the batch, a missing field is written as
+    /// [crate::value::Value::None], only the first field of duplicate names is
+    /// kept and the field metadata is lost. Call [Exporter::write] for every
+    /// record, if that matters
+    fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), BoxedError> {
+        for record in batch.records() {
+            self.write(&record)?;
+        }
+        Ok(())
+    }
+
     /// Event signaling function
     ///
     /// Exporters can utilize this, to collect records and process them at the
@@ -29,9 +52,12 @@ pub trait Exporter: Initializable {
 
 #[cfg(test)]
 mod test {
+    use serde_json::json;
+
     use crate::{
         Initializable,
         export::{Exporter, Signal},
+        record::{Record, batch::RecordBatch},
     };
 
     #[test]
@@ -61,4 +87,32 @@ mod test {
         let result = exporter.event(Signal::Start);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_write_batch() {
+        struct CountingExporter(usize);
+        impl Initializable for CountingExporter {
+            fn init(
+                &mut self,
+                _config: Option<crate::xml::config::Configuration>,
+            ) -> Result<(), crate::BoxedError> {
+                Ok(())
+            }
+        }
+        impl Exporter for CountingExporter {
+            fn write(&mut self, record: &Record) -> Result<(), crate::BoxedError> {
+                assert!(record.contains("id"));
+                self.0 += 1;
+                Ok(())
+            }
+        }
+
+        let records = vec![
+            Record::from(json!({"id": 1})),
+            Record::from(json!({"id": 2})),
+        ];
+        let mut exporter = CountingExporter(0);
+        assert!(exporter.write_batch(&RecordBatch::from(records)).is_ok());
+        assert_eq!(2, exporter.0);
+    }
 }
@@ -1,20 +1,31 @@
 //! Module for the Field
 //!
+use serde::{Deserialize, Serialize};
+
 use crate::value::Value;
 
+pub mod metadata;
+
+use metadata::Metadata;
+
 /// A struct to represent a field in a record
 ///
 /// # Members
 /// * `name` - Name of the field
 /// * `value` - The value of the field
+/// * `metadata` - The [Metadata] of the field
 ///
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Field {
     /// Name of the field
     name: String,
 
     /// Value of the field
     value: Value,
+
+    /// Metadata of the field
+    #[serde(default, skip_serializing_if = "Metadata::is_empty")]
+    metadata: Metadata,
 }
 
 impl Field {
@@ -32,6 +43,7 @@ impl Field {
         Field {
             name: name.to_string(),
             value: Value::None,
+            metadata: Metadata::new(),
         }
     }
 
@@ -52,6 +64,7 @@ impl Field {
         Field {
             name: name.to_string(),
             value,
+            metadata: Metadata::new(),
         }
     }
 
@@ -66,6 +79,9 @@ impl Field {
         &self.name
     }
 
+    /// Returns a clone of the value. Strings, blobs and records share their
+    /// data with the clone
+    ///
     /// # Example
     /// ```
     /// let field = model::field::Field::new_value("name",
@@ -96,6 +112,39 @@ impl Field {
     pub fn value_as_ref(&self) -> &Value {
         &self.value
     }
+
+    /// Returns the value as a mutable reference
+    ///
+    /// # Example
+    /// ```
+    /// let mut field = model::field::Field::new_value("count", model::value::Value::U8(1));
+    /// *field.value_as_mut() = model::value::Value::U8(2);
+    /// assert_eq!(model::value::Value::U8(2), field.value());
+    /// ```
+    pub fn value_as_mut(&mut self) -> &mut Value {
+        &mut self.value
+    }
+
+    /// Consumes the [Field] and returns its value without cloning it
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+
+    /// Sets the [Metadata] of the field
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the [Metadata] of the field
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns the [Metadata] of the field as a mutable reference
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
 }
 
 /// Implements the [Default] trait by returning a new Field with name "default"
@@ -105,6 +154,14 @@ impl Default for Field {
     }
 }
 
+/// The metadata is not part of the equality, like the
+/// [crate::record::meta::RecordMeta] of a record
+impl PartialEq for Field {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.value == other.value
+    }
+}
+
 /// Adds a field, if value is [Some]
 /// # Arguments
 /// * `fields`: A vector of fields where the new field should be added
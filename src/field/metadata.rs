@@ -0,0 +1,162 @@
+//! Metadata of a [Field](super::Field)
+//!
+//! Besides its value, a field can carry [Metadata]: the source column, a
+//! declared SQL type, a unit or format hint, a key marker or the lineage of
+//! the field. The well-known entries are typed with the [MetaKey]s in
+//! [keys], other entries are plain [Value]s.
+//!
+//! Metadata is shared between clones until one of them is changed, so
+//! copying records with metadata stays cheap. Transformers, that change the
+//! value of a field in place (e.g. with [crate::record::Record::set]), keep
+//! its metadata. Two fields with the same name and value are equal, even if
+//! their metadata differs.
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     field::{Field, metadata::keys},
+//!     value::Value,
+//! };
+//!
+//! let mut field = Field::new_value("id", Value::I32(1));
+//! field.metadata_mut().set(&keys::KEY, true);
+//! field.metadata_mut().set(&keys::SOURCE_COLUMN, "ID".to_string());
+//!
+//! assert_eq!(Some(true), field.metadata().get(&keys::KEY));
+//! assert_eq!(Some("ID".to_string()), field.metadata().get(&keys::SOURCE_COLUMN));
+//! assert_eq!(None, field.metadata().get(&keys::UNIT));
+//! ```
+use std::{collections::BTreeMap, marker::PhantomData, sync::Arc};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    mapping::{FromValue, IntoValue},
+    value::Value,
+};
+
+/// A typed key for a [Metadata] entry
+#[derive(Debug)]
+pub struct MetaKey<T> {
+    name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> MetaKey<T> {
+    /// Creates a key with the given `name`
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the name of the key
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+/// The well-known [Metadata] keys
+pub mod keys {
+    use super::MetaKey;
+
+    /// The name of the column or element in the import source
+    pub const SOURCE_COLUMN: MetaKey<String> = MetaKey::new("source_column");
+    /// The declared SQL type, e.g. `VARCHAR(40)`
+    pub const SQL_TYPE: MetaKey<String> = MetaKey::new("sql_type");
+    /// The unit of a numeric value, e.g. `kg`
+    pub const UNIT: MetaKey<String> = MetaKey::new("unit");
+    /// A format hint, e.g. a [chrono::format::strftime] format
+    pub const FORMAT: MetaKey<String> = MetaKey::new("format");
+    /// True, if the field is part of the unique key of the record, see
+    /// [crate::xml::common::Table::mark_unique_fields]
+    pub const KEY: MetaKey<bool> = MetaKey::new("key");
+    /// The name of the transformer, that changed the field last
+    pub const LAST_MODIFIED_BY: MetaKey<String> = MetaKey::new("last_modified_by");
+}
+
+/// The metadata of a field, a map of names to [Value]s
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    entries: Option<Arc<BTreeMap<String, Value>>>,
+}
+
+impl Metadata {
+    /// Creates empty metadata without allocating
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true, if there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.entries
+            .as_ref()
+            .is_none_or(|entries| entries.is_empty())
+    }
+
+    /// Returns the entry for a typed key. An entry, that cannot be converted
+    /// into `T`, is returned as [None]
+    pub fn get<T: FromValue>(&self, key: &MetaKey<T>) -> Option<T> {
+        self.get_value(key.name())
+            .and_then(|value| T::from_value(value).ok())
+    }
+
+    /// Sets the entry for a typed key
+    pub fn set<T: IntoValue>(&mut self, key: &MetaKey<T>, value: T) {
+        self.set_value(key.name(), value.into_value());
+    }
+
+    /// Returns the entry with the given `name`
+    pub fn get_value(&self, name: &str) -> Option<&Value> {
+        self.entries.as_ref()?.get(name)
+    }
+
+    /// Sets the entry with the given `name`
+    pub fn set_value(&mut self, name: &str, value: Value) {
+        Arc::make_mut(self.entries.get_or_insert_default()).insert(name.to_string(), value);
+    }
+
+    /// Removes the entry with the given `name` and returns its value
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        let entries = self.entries.as_mut()?;
+        if !entries.contains_key(name) {
+            return None;
+        }
+        Arc::make_mut(entries).remove(name)
+    }
+
+    /// Returns an iterator over the names and values ordered by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.entries
+            .iter()
+            .flat_map(|entries| entries.iter())
+            .map(|(name, value)| (name.as_str(), value))
+    }
+}
+
+impl PartialEq for Metadata {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+/// Metadata is serialized as a map in the natural representation of
+/// [crate::serialization]
+impl Serialize for Metadata {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Metadata {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entries = BTreeMap::<String, Value>::deserialize(deserializer)?;
+        Ok(Self {
+            entries: (!entries.is_empty()).then(|| Arc::new(entries)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,130 @@
+use crate::{
+    field::{Field, add_field},
+    record::Record,
+    serialization::Tagged,
+    value::Value,
+};
+
+use super::{MetaKey, Metadata, keys};
+
+#[test]
+fn test_typed_keys() {
+    let mut metadata = Metadata::new();
+    assert!(metadata.is_empty());
+    metadata.set(&keys::SQL_TYPE, "VARCHAR(40)".to_string());
+    metadata.set(&keys::KEY, false);
+    assert!(!metadata.is_empty());
+    assert_eq!(
+        Some("VARCHAR(40)".to_string()),
+        metadata.get(&keys::SQL_TYPE)
+    );
+    assert_eq!(Some(false), metadata.get(&keys::KEY));
+
+    // A custom key with another type
+    const PRECISION: MetaKey<u8> = MetaKey::new("precision");
+    metadata.set(&PRECISION, 2);
+    assert_eq!(Some(2), metadata.get(&PRECISION));
+    assert_eq!("precision", PRECISION.name());
+
+    // An entry of the wrong type
+    metadata.set_value("key", "yes".into());
+    assert_eq!(None, metadata.get(&keys::KEY));
+    assert_eq!(Some(&Value::from("yes")), metadata.get_value("key"));
+}
+
+#[test]
+fn test_entries() {
+    let mut metadata = Metadata::new();
+    assert_eq!(None, metadata.remove("unit"));
+    metadata.set_value("unit", "kg".into());
+    metadata.set_value("format", "%.2f".into());
+    let names: Vec<&str> = metadata.iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!["format", "unit"], names);
+
+    assert_eq!(Some(Value::from("kg")), metadata.remove("unit"));
+    assert_eq!(None, metadata.remove("unit"));
+    metadata.remove("format");
+    assert!(metadata.is_empty());
+    assert_eq!(Metadata::new(), metadata);
+}
+
+#[test]
+fn test_clone_on_write() {
+    let mut field = Field::new_value("weight", Value::F64(1.5));
+    field.metadata_mut().set(&keys::UNIT, "kg".to_string());
+
+    let mut copy = field.clone();
+    assert_eq!(field, copy);
+    copy.metadata_mut().set(&keys::UNIT, "g".to_string());
+    assert_eq!(Some("kg".to_string()), field.metadata().get(&keys::UNIT));
+    assert_eq!(Some("g".to_string()), copy.metadata().get(&keys::UNIT));
+    assert_ne!(field.metadata(), copy.metadata());
+}
+
+#[test]
+fn test_equality() {
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::I32(1));
+    record.fields_as_mut()[0]
+        .metadata_mut()
+        .set(&keys::SOURCE_COLUMN, "A".to_string());
+
+    // The tagged representation does not contain the metadata
+    let json = serde_json::to_string(&Tagged(&record)).unwrap();
+    let restored: Tagged<Record> = serde_json::from_str(&json).unwrap();
+    assert!(restored.0.fields()[0].metadata().is_empty());
+    assert_eq!(record, restored.0);
+    assert_eq!(record.fields()[0], restored.0.fields()[0]);
+    assert_ne!(
+        Field::new_value("a", Value::I32(1)),
+        Field::new_value("a", Value::I32(2))
+    );
+    assert_ne!(
+        Field::new_value("a", Value::I32(1)),
+        Field::new_value("b", Value::I32(1))
+    );
+}
+
+#[test]
+fn test_record_keeps_metadata() {
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::I32(1));
+    record.fields_as_mut()[0]
+        .metadata_mut()
+        .set(&keys::SOURCE_COLUMN, "A".to_string());
+
+    record.set("a", Value::I32(2));
+    *record.get_mut("a").unwrap() = Value::I32(3);
+    assert!(record.rename("a", "b"));
+
+    let field = record.field_by_name("b").unwrap();
+    assert_eq!(Value::I32(3), field.value());
+    assert_eq!(
+        Some("A".to_string()),
+        field.metadata().get(&keys::SOURCE_COLUMN)
+    );
+    let copy = Record::copy(&record);
+    assert_eq!(field, &copy.fields()[0]);
+    assert_eq!(field.metadata(), copy.fields()[0].metadata());
+}
+
+#[test]
+fn test_serialization() {
+    let field = Field::new_value("a", Value::U8(1));
+    assert_eq!(
+        r#"{"name":"a","value":1}"#,
+        serde_json::to_string(&field).unwrap()
+    );
+
+    let field = field.with_metadata({
+        let mut metadata = Metadata::new();
+        metadata.set(&keys::UNIT, "m".to_string());
+        metadata
+    });
+    let json = serde_json::to_string(&field).unwrap();
+    assert_eq!(r#"{"name":"a","value":1,"metadata":{"unit":"m"}}"#, json);
+    let restored: Field = serde_json::from_str(&json).unwrap();
+    assert_eq!(Some("m".to_string()), restored.metadata().get(&keys::UNIT));
+    let restored: Field = serde_json::from_str(r#"{"name":"a","value":1}"#).unwrap();
+    assert!(restored.metadata().is_empty());
+}
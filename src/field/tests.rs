@@ -30,7 +30,7 @@ fn test_new_f64() {
 
 #[test]
 fn test_new_string() {
-    let field = Field::new_value("name", Value::String("Alice".to_string()));
+    let field = Field::new_value("name", Value::String("Alice".into()));
     assert_eq!(field.name, "name");
     assert!(matches!(field.value, Value::String(ref s) if s == "Alice"));
 }
@@ -38,7 +38,7 @@ fn test_new_string() {
 #[test]
 fn test_new_blob() {
     let blob = vec![0x00, 0x01, 0x02, 0x03];
-    let field = Field::new_value("data", Value::Blob(blob.clone()));
+    let field = Field::new_value("data", Value::Blob(blob.clone().into()));
     assert_eq!(field.name, "data");
     assert!(matches!(field.value, Value::Blob(ref b) if b == &blob));
 }
@@ -74,7 +74,7 @@ fn test_name_getter() {
 
 #[test]
 fn test_value_getter_string() {
-    let field = Field::new_value("email", Value::String("alice@example.com".to_string()));
+    let field = Field::new_value("email", Value::String("alice@example.com".into()));
     match field.value() {
         Value::String(s) => assert_eq!(s, "alice@example.com"),
         _ => panic!("Expected String value"),
@@ -121,7 +121,7 @@ fn test_add_str() {
 
     assert_eq!(fields.len(), 1);
     assert_eq!(fields[0].name, "name");
-    assert_eq!(fields[0].value, Value::String("value".to_string()));
+    assert_eq!(fields[0].value, Value::String("value".into()));
 }
 
 #[test]
@@ -131,7 +131,7 @@ fn test_add_optional_str_some() {
 
     assert_eq!(fields.len(), 1);
     assert_eq!(fields[0].name, "name");
-    assert_eq!(fields[0].value, Value::String("value".to_string()));
+    assert_eq!(fields[0].value, Value::String("value".into()));
 }
 
 #[test]
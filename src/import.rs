@@ -1,30 +1,148 @@
-use super::{BoxedError, Initializable, record::Record};
+use super::{
+    BoxedError, Initializable,
+    checkpoint::{Checkpoint, CheckpointsNotSupported},
+    record::{Record, batch::RecordBatch},
+    schema::Schema,
+};
+
+/// Tells an [Importer], how to go on after a record was handled
+#[must_use]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Flow {
+    /// Read the next record
+    #[default]
+    Continue,
+    /// Stop reading, e.g. because a limit is reached or the user cancelled.
+    /// The importer returns from `read`
+    Stop,
+    /// Stop reading for now, e.g. because the consumer is busy. The importer
+    /// returns from `read` and keeps its position, so that the next `read`
+    /// resumes after the last handled record
+    Pause,
+}
+
+impl Flow {
+    /// Returns true for [Flow::Continue]
+    pub fn is_continue(&self) -> bool {
+        *self == Flow::Continue
+    }
+}
+
+/// Handlers without flow control just continue
+impl From<()> for Flow {
+    fn from(_: ()) -> Self {
+        Flow::Continue
+    }
+}
 
 pub trait RecordHandler {
-    fn handle_record(&mut self, record: &mut Record) -> Result<(), BoxedError>;
+    /// Handles a record and returns, whether the importer should read on
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError>;
+}
+
+/// Allows to pass a handler to [combinators] and still use it afterwards
+impl<H: RecordHandler + ?Sized> RecordHandler for &mut H {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        (**self).handle_record(record)
+    }
+}
+
+impl<H: RecordHandler + ?Sized> RecordHandler for Box<H> {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        (**self).handle_record(record)
+    }
+}
+
+/// Receives the [RecordBatch]es of [Importer::read_batch]
+pub trait BatchHandler {
+    /// Handles a batch and returns, whether the importer should read on
+    fn handle_batch(&mut self, batch: &mut RecordBatch) -> Result<Flow, BoxedError>;
 }
 
 pub trait Importer: Initializable {
     /// Reads all from the import source and calls the `callback` for each record
+    ///
+    /// Importers should fill in the [crate::record::meta::RecordMeta] of the
+    /// records, so that exporters and error reports know their source
+    ///
+    /// Importers must return, when the handler returns [Flow::Stop] or
+    /// [Flow::Pause]. After a pause, the next `read` should resume after the
+    /// last handled record, if the importer can do that
+    ///
+    /// Importers, that can resume, set the
+    /// [crate::record::meta::RecordMeta::checkpoint] of each record to the
+    /// position after it
     fn read(&mut self, handler: &mut dyn RecordHandler) -> Result<(), Box<dyn std::error::Error>>;
 
+    /// Reads the records after `checkpoint`, that an earlier `read` put into
+    /// the meta data of a record, and calls the `handler` for each record
+    /// With default implementation, that fails with [CheckpointsNotSupported],
+    /// since most importers cannot resume
+    fn read_from(
+        &mut self,
+        #[allow(unused_variables)] checkpoint: &Checkpoint,
+        #[allow(unused_variables)] handler: &mut dyn RecordHandler,
+    ) -> Result<(), BoxedError> {
+        Err(Box::new(CheckpointsNotSupported))
+    }
+
+    /// Reads all from the import source and calls the `handler` with batches
+    /// of up to `batch_size` records
+    /// With default implementation, that collects the records of `read`.
+    /// Importers, that read columnar data, can build the batches directly
+    fn read_batch(
+        &mut self,
+        batch_size: usize,
+        handler: &mut dyn BatchHandler,
+    ) -> Result<(), BoxedError> {
+        let mut batching = handlers::BatchingRecordHandler::new(batch_size, handler);
+        self.read(&mut batching)?;
+        batching.flush().map(|_| ())
+    }
+
+    /// Returns the next record of the import source, or [None] after the
+    /// last one. [iter::Records] turns an importer into an [Iterator] with it
+    /// With default implementation, that fails with [iter::NextNotSupported],
+    /// since most importers only support `read`
+    fn next(&mut self) -> Result<Option<Record>, BoxedError> {
+        Err(Box::new(iter::NextNotSupported))
+    }
+
     /// Resets the importer, so that `next` and `read` start from the beginning again
     /// With default implementation, since most importers will not support it
     fn reset(&mut self) -> Result<(), BoxedError> {
         Ok(())
     }
+
+    /// Returns the [Schema] of the records, that `read` will produce. It is
+    /// available after `init` and before `read`
+    /// With default implementation, since most importers do not know it in advance
+    fn schema(&self) -> Option<Schema> {
+        None
+    }
 }
 
+pub mod combinators;
 /// Common record handlers
 pub mod handlers;
+pub mod iter;
 
 #[cfg(test)]
 mod tests {
 
     use crate::xml::config::Configuration;
-    use crate::{BoxedError, Initializable, field::Field, record::Record, value::Value};
-
-    use super::{Importer, RecordHandler, handlers::CollectingRecordHandler};
+    use crate::{
+        BoxedError, Initializable,
+        checkpoint::{Checkpoint, CheckpointsNotSupported},
+        field::Field,
+        record::{Record, batch::RecordBatch},
+        value::Value,
+    };
+
+    use super::{
+        BatchHandler, Flow, Importer, RecordHandler,
+        handlers::{CollectingRecordHandler, LimitingRecordHandler},
+    };
 
     // Demo importer that simulates reading with delays
     pub struct DemoImporter;
@@ -37,7 +155,9 @@ mod tests {
                     .fields_as_mut()
                     .push(Field::new_value("index", Value::U16(i)));
 
-                handler.handle_record(&mut record)?;
+                if !handler.handle_record(&mut record)?.is_continue() {
+                    break;
+                }
             }
 
             Ok(())
@@ -65,6 +185,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_batch() -> Result<(), BoxedError> {
+        struct Sizes(Vec<usize>, Flow);
+        impl BatchHandler for Sizes {
+            fn handle_batch(&mut self, batch: &mut RecordBatch) -> Result<Flow, BoxedError> {
+                self.0.push(batch.len());
+                Ok(self.1)
+            }
+        }
+
+        let mut importer = DemoImporter;
+        let mut sizes = Sizes(vec![], Flow::Continue);
+        importer.read_batch(2, &mut sizes)?;
+        assert_eq!(vec![2, 2, 1], sizes.0);
+
+        let mut sizes = Sizes(vec![], Flow::Stop);
+        importer.read_batch(2, &mut sizes)?;
+        assert_eq!(vec![2], sizes.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_flow() -> Result<(), BoxedError> {
+        assert_eq!(Flow::Continue, Flow::default());
+        assert_eq!(Flow::Continue, Flow::from(()));
+        assert!(!Flow::Pause.is_continue());
+
+        let mut importer = DemoImporter;
+        let mut records = Vec::<Record>::new();
+        let mut collecting = CollectingRecordHandler::new(&mut records);
+        let mut handler = LimitingRecordHandler::new(3, &mut collecting);
+        importer.read(&mut handler)?;
+        assert_eq!(3, handler.count());
+        assert_eq!(3, records.len());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_from() {
+        let mut importer = DemoImporter;
+        let mut records = Vec::<Record>::new();
+        let error = importer
+            .read_from(
+                &Checkpoint::new("1"),
+                &mut CollectingRecordHandler::new(&mut records),
+            )
+            .unwrap_err();
+        assert!(error.is::<CheckpointsNotSupported>());
+        assert!(records.is_empty());
+    }
+
     #[test]
     fn test_reset() -> Result<(), BoxedError> {
         let mut importer = DemoImporter;
@@ -72,4 +243,10 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_schema() {
+        let importer = DemoImporter;
+        assert!(importer.schema().is_none());
+    }
 }
@@ -0,0 +1,421 @@
+//! Combinators to build [RecordHandler]s from existing pieces
+//!
+//! Each combinator wraps one or more downstream handlers and is a
+//! [RecordHandler] itself, so they nest into processing graphs:
+//! * [filter] passes only the records, that match a predicate
+//! * [map] passes a new record created from each record
+//! * [inspect] looks at each record before passing it
+//! * [tee] passes each record to two handlers
+//! * [batch] collects the records into [Vec]s of a given size
+//! * [chain_transformer] passes the output of a [Transformer]
+//!
+//! Handlers can be passed by value or as `&mut`, so a handler can still be
+//! used after the import.
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     import::{
+//!         RecordHandler,
+//!         combinators::{filter, inspect, map, tee},
+//!         handlers::CollectingRecordHandler,
+//!     },
+//!     record::Record,
+//!     value::Value,
+//! };
+//!
+//! let mut all = Vec::new();
+//! let mut even = Vec::new();
+//! let mut seen = 0;
+//! let mut handler = inspect(
+//!     |_| seen += 1,
+//!     tee(
+//!         CollectingRecordHandler::new(&mut all),
+//!         filter(
+//!             |r: &Record| r.get("id").and_then(|v| v.as_i64().ok()).unwrap_or(1) % 2 == 0,
+//!             map(
+//!                 |r: &Record| {
+//!                     let mut r = r.clone();
+//!                     r.set("even", Value::Bool(true));
+//!                     r
+//!                 },
+//!                 CollectingRecordHandler::new(&mut even),
+//!             ),
+//!         ),
+//!     ),
+//! );
+//! for id in 0..4 {
+//!     let mut record = Record::new();
+//!     record.set("id", Value::I64(id));
+//!     assert!(handler.handle_record(&mut record).unwrap().is_continue());
+//! }
+//! assert_eq!(4, seen);
+//! assert_eq!(4, all.len());
+//! assert_eq!(2, even.len());
+//! assert_eq!(Some(&Value::Bool(true)), even[1].get("even"));
+//! ```
+use crate::{BoxedError, record::Record, transform::Transformer};
+
+use super::{Flow, RecordHandler};
+
+/// Passes only the records, for which `predicate` returns true, to
+/// `handler`. Other records are skipped
+pub fn filter<P, H>(predicate: P, handler: H) -> Filter<P, H>
+where
+    P: FnMut(&Record) -> bool,
+    H: RecordHandler,
+{
+    Filter { predicate, handler }
+}
+
+/// Passes the record, that `f` creates from each record, to `handler`
+pub fn map<F, H>(f: F, handler: H) -> Map<F, H>
+where
+    F: FnMut(&Record) -> Record,
+    H: RecordHandler,
+{
+    Map { f, handler }
+}
+
+/// Calls `f` with each record before passing it to `handler`
+pub fn inspect<F, H>(f: F, handler: H) -> Inspect<F, H>
+where
+    F: FnMut(&Record),
+    H: RecordHandler,
+{
+    Inspect { f, handler }
+}
+
+/// Passes each record to `first` and `second`
+///
+/// The first handler gets a copy of the record, so that changes of one
+/// handler are not seen by the other. A handler, that returned
+/// [Flow::Stop], does not get any more records. The tee stops, when both
+/// handlers stopped, and pauses, when one of them paused.
+pub fn tee<A, B>(first: A, second: B) -> Tee<A, B>
+where
+    A: RecordHandler,
+    B: RecordHandler,
+{
+    Tee {
+        first,
+        second,
+        flows: [Flow::Continue; 2],
+    }
+}
+
+/// Collects the records into [Vec]s of up to `size` records, at least one,
+/// and passes every full [Vec] to `handler`. [Batch::flush] passes the rest
+pub fn batch<F>(size: usize, handler: F) -> Batch<F>
+where
+    F: FnMut(Vec<Record>) -> Result<Flow, BoxedError>,
+{
+    let size = size.max(1);
+    Batch {
+        size,
+        records: Vec::with_capacity(size),
+        handler,
+    }
+}
+
+/// Passes the output of `transformer` for each record to `handler`. Like in
+/// the [crate::pipeline], an output without
+/// [crate::record::meta::RecordMeta] keeps the meta data of its input
+pub fn chain_transformer<H>(transformer: &dyn Transformer, handler: H) -> ChainTransformer<'_, H>
+where
+    H: RecordHandler,
+{
+    ChainTransformer {
+        transformer,
+        handler,
+    }
+}
+
+/// The handler of [filter]
+pub struct Filter<P, H> {
+    predicate: P,
+    handler: H,
+}
+
+impl<P, H> RecordHandler for Filter<P, H>
+where
+    P: FnMut(&Record) -> bool,
+    H: RecordHandler,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        if (self.predicate)(record) {
+            self.handler.handle_record(record)
+        } else {
+            Ok(Flow::Continue)
+        }
+    }
+}
+
+/// The handler of [map]
+pub struct Map<F, H> {
+    f: F,
+    handler: H,
+}
+
+impl<F, H> RecordHandler for Map<F, H>
+where
+    F: FnMut(&Record) -> Record,
+    H: RecordHandler,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        self.handler.handle_record(&mut (self.f)(record))
+    }
+}
+
+/// The handler of [inspect]
+pub struct Inspect<F, H> {
+    f: F,
+    handler: H,
+}
+
+impl<F, H> RecordHandler for Inspect<F, H>
+where
+    F: FnMut(&Record),
+    H: RecordHandler,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        (self.f)(record);
+        self.handler.handle_record(record)
+    }
+}
+
+/// The handler of [tee]
+///
+/// # Members
+/// * `first` - The first handler
+/// * `second` - The second handler
+/// * `flows` - The last [Flow] of each handler
+pub struct Tee<A, B> {
+    first: A,
+    second: B,
+    flows: [Flow; 2],
+}
+
+impl<A, B> RecordHandler for Tee<A, B>
+where
+    A: RecordHandler,
+    B: RecordHandler,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        if self.flows[0] != Flow::Stop {
+            self.flows[0] = self.first.handle_record(&mut Record::copy(record))?;
+        }
+        if self.flows[1] != Flow::Stop {
+            self.flows[1] = self.second.handle_record(record)?;
+        }
+        Ok(match self.flows {
+            [Flow::Stop, Flow::Stop] => Flow::Stop,
+            [Flow::Pause, _] | [_, Flow::Pause] => Flow::Pause,
+            _ => Flow::Continue,
+        })
+    }
+}
+
+/// The handler of [batch]
+pub struct Batch<F> {
+    size: usize,
+    records: Vec<Record>,
+    handler: F,
+}
+
+impl<F> Batch<F>
+where
+    F: FnMut(Vec<Record>) -> Result<Flow, BoxedError>,
+{
+    /// Passes the collected records, if there are any, and returns the
+    /// [Flow] of the handler
+    pub fn flush(&mut self) -> Result<Flow, BoxedError> {
+        if self.records.is_empty() {
+            return Ok(Flow::Continue);
+        }
+        let records = std::mem::replace(&mut self.records, Vec::with_capacity(self.size));
+        (self.handler)(records)
+    }
+}
+
+impl<F> RecordHandler for Batch<F>
+where
+    F: FnMut(Vec<Record>) -> Result<Flow, BoxedError>,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        self.records.push(Record::copy(record));
+        if self.records.len() >= self.size {
+            return self.flush();
+        }
+        Ok(Flow::Continue)
+    }
+}
+
+/// The handler of [chain_transformer]
+pub struct ChainTransformer<'a, H> {
+    transformer: &'a dyn Transformer,
+    handler: H,
+}
+
+impl<H> RecordHandler for ChainTransformer<'_, H>
+where
+    H: RecordHandler,
+{
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        let mut output = self.transformer.process(record)?;
+        if output.meta().is_empty() && !record.meta().is_empty() {
+            *output.meta_mut() = record.meta().clone();
+        }
+        self.handler.handle_record(&mut output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BoxedError, Initializable,
+        import::{
+            Flow, Importer, RecordHandler,
+            handlers::{CollectingRecordHandler, LimitingRecordHandler},
+            iter::IteratorImporter,
+        },
+        record::{Record, meta::RecordMeta},
+        transform::Transformer,
+        value::Value,
+        xml::config::Configuration,
+    };
+
+    use super::{batch, chain_transformer, filter, inspect, map, tee};
+
+    fn records(count: i64) -> IteratorImporter<impl Iterator<Item = Result<Record, BoxedError>>> {
+        IteratorImporter::new((0..count).map(|i| {
+            let mut record = Record::new().with_meta(RecordMeta::new().with_sequence(i as u64));
+            record.set("id", Value::I64(i));
+            Ok(record)
+        }))
+    }
+
+    fn ids(records: &[Record]) -> Vec<i64> {
+        records
+            .iter()
+            .map(|r| r.get("id").unwrap().as_i64().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_map_inspect() {
+        let mut collected = Vec::new();
+        let mut inspected = 0;
+        let mut handler = filter(
+            |r: &Record| r.get("id") != Some(&Value::I64(1)),
+            inspect(
+                |_| inspected += 1,
+                map(
+                    |r: &Record| {
+                        let mut result = Record::new();
+                        result.set(
+                            "id",
+                            Value::I64(r.get("id").unwrap().as_i64().unwrap() * 10),
+                        );
+                        result
+                    },
+                    CollectingRecordHandler::new(&mut collected),
+                ),
+            ),
+        );
+        records(3).read(&mut handler).unwrap();
+        assert_eq!(2, inspected);
+        assert_eq!(vec![0, 20], ids(&collected));
+    }
+
+    #[test]
+    fn test_tee() {
+        let mut limited = Vec::new();
+        let mut all = Vec::new();
+        let mut limit_handler = CollectingRecordHandler::new(&mut limited);
+        let mut handler = tee(
+            LimitingRecordHandler::new(2, &mut limit_handler),
+            map(
+                |r: &Record| {
+                    let mut r = r.clone();
+                    r.set("changed", Value::Bool(true));
+                    r
+                },
+                CollectingRecordHandler::new(&mut all),
+            ),
+        );
+        records(4).read(&mut handler).unwrap();
+        // The limited branch stops, the other one gets all records
+        assert_eq!(vec![0, 1], ids(&limited));
+        assert_eq!(vec![0, 1, 2, 3], ids(&all));
+        assert!(!limited[0].contains("changed"));
+
+        // The tee stops, when both branches stopped
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        let mut first_handler = CollectingRecordHandler::new(&mut first);
+        let mut second_handler = CollectingRecordHandler::new(&mut second);
+        let mut handler = tee(
+            LimitingRecordHandler::new(1, &mut first_handler),
+            LimitingRecordHandler::new(2, &mut second_handler),
+        );
+        let mut record = Record::new();
+        assert_eq!(Flow::Continue, handler.handle_record(&mut record).unwrap());
+        assert_eq!(Flow::Stop, handler.handle_record(&mut record).unwrap());
+        assert_eq!((1, 2), (first.len(), second.len()));
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut sizes = Vec::new();
+        let mut handler = batch(2, |records| {
+            sizes.push(ids(&records));
+            Ok(Flow::Continue)
+        });
+        records(5).read(&mut handler).unwrap();
+        assert_eq!(Flow::Continue, handler.flush().unwrap());
+        assert_eq!(Flow::Continue, handler.flush().unwrap());
+        assert_eq!(vec![vec![0, 1], vec![2, 3], vec![4]], sizes);
+
+        let mut calls = 0;
+        let mut handler = batch(2, |_| {
+            calls += 1;
+            Ok(Flow::Stop)
+        });
+        records(5).read(&mut handler).unwrap();
+        assert_eq!(1, calls);
+    }
+
+    struct Negate;
+
+    impl Initializable for Negate {
+        fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+            Ok(())
+        }
+    }
+
+    impl Transformer for Negate {
+        fn process(&self, record: &Record) -> Result<Record, BoxedError> {
+            let id = record.get("id").ok_or("no id")?.as_i64()?;
+            let mut result = Record::new();
+            result.set("id", Value::I64(-id));
+            Ok(result)
+        }
+    }
+
+    #[test]
+    fn test_chain_transformer() {
+        let mut collected = Vec::new();
+        let mut handler = chain_transformer(
+            &Negate,
+            chain_transformer(&Negate, CollectingRecordHandler::new(&mut collected)),
+        );
+        records(3).read(&mut handler).unwrap();
+        assert_eq!(vec![0, 1, 2], ids(&collected));
+        assert_eq!(Some(2), collected[2].meta().sequence());
+
+        let mut handler = chain_transformer(&Negate, CollectingRecordHandler::new(&mut collected));
+        let error = handler.handle_record(&mut Record::new()).unwrap_err();
+        assert_eq!("no id", error.to_string());
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{BoxedError, record::Record};
+use crate::{
+    BoxedError,
+    record::{Record, batch::RecordBatch},
+};
 
-use super::RecordHandler;
+use super::{BatchHandler, Flow, RecordHandler};
 
 pub struct CollectingRecordHandler<'a> {
     records: &'a mut Vec<Record>,
@@ -13,9 +16,85 @@ impl<'a> CollectingRecordHandler<'a> {
 }
 
 impl<'a> RecordHandler for CollectingRecordHandler<'a> {
-    fn handle_record(&mut self, record: &mut Record) -> Result<(), BoxedError> {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
         self.records.push(Record::copy(record));
-        Ok(())
+        Ok(Flow::Continue)
+    }
+}
+
+/// Passes at most `limit` records to another [RecordHandler] and stops the
+/// importer after the last one
+pub struct LimitingRecordHandler<'a> {
+    limit: usize,
+    count: usize,
+    handler: &'a mut dyn RecordHandler,
+}
+
+impl<'a> LimitingRecordHandler<'a> {
+    pub fn new(limit: usize, handler: &'a mut dyn RecordHandler) -> Self {
+        Self {
+            limit,
+            count: 0,
+            handler,
+        }
+    }
+
+    /// Returns the number of records passed on
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl RecordHandler for LimitingRecordHandler<'_> {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        if self.count >= self.limit {
+            return Ok(Flow::Stop);
+        }
+        self.count += 1;
+        match self.handler.handle_record(record)? {
+            Flow::Continue if self.count >= self.limit => Ok(Flow::Stop),
+            flow => Ok(flow),
+        }
+    }
+}
+
+/// Collects the records into [RecordBatch]es and hands every full batch to a
+/// [BatchHandler]. [BatchingRecordHandler::flush] hands over the rest
+pub struct BatchingRecordHandler<'a> {
+    batch_size: usize,
+    batch: RecordBatch,
+    handler: &'a mut dyn BatchHandler,
+}
+
+impl<'a> BatchingRecordHandler<'a> {
+    /// Creates a handler for batches of up to `batch_size` records, at least
+    /// one
+    pub fn new(batch_size: usize, handler: &'a mut dyn BatchHandler) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            batch: RecordBatch::new(),
+            handler,
+        }
+    }
+
+    /// Hands the collected records to the [BatchHandler], if there are any,
+    /// and returns its [Flow]
+    pub fn flush(&mut self) -> Result<Flow, BoxedError> {
+        if self.batch.is_empty() {
+            return Ok(Flow::Continue);
+        }
+        let mut batch = std::mem::take(&mut self.batch);
+        self.handler.handle_batch(&mut batch)
+    }
+}
+
+impl RecordHandler for BatchingRecordHandler<'_> {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        self.batch.push(record);
+        if self.batch.len() >= self.batch_size {
+            return self.flush();
+        }
+        Ok(Flow::Continue)
     }
 }
 
@@ -23,9 +102,11 @@ pub struct ClosureRecordHandler<F> {
     callback: F,
 }
 
-impl<F> ClosureRecordHandler<F>
+/// The callback returns either `()` to continue or a [Flow]
+impl<F, R> ClosureRecordHandler<F>
 where
-    F: FnMut(&mut Record),
+    F: FnMut(&mut Record) -> R,
+    R: Into<Flow>,
 {
     pub fn new(callback: F) -> Self {
         if cfg!(test) {
@@ -35,13 +116,13 @@ where
     }
 }
 
-impl<F> RecordHandler for ClosureRecordHandler<F>
+impl<F, R> RecordHandler for ClosureRecordHandler<F>
 where
-    F: FnMut(&mut Record),
+    F: FnMut(&mut Record) -> R,
+    R: Into<Flow>,
 {
-    fn handle_record(&mut self, record: &mut Record) -> Result<(), BoxedError> {
-        (self.callback)(record);
-        Ok(())
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        Ok((self.callback)(record).into())
     }
 }
 
@@ -49,8 +130,12 @@ where
 mod test {
     use crate::{
         field::add_field,
-        import::{RecordHandler, handlers::ClosureRecordHandler},
+        import::{
+            Flow, RecordHandler,
+            handlers::{ClosureRecordHandler, CollectingRecordHandler, LimitingRecordHandler},
+        },
         record::Record,
+        value::Value,
     };
 
     #[test]
@@ -66,4 +151,41 @@ mod test {
         let result = handler.handle_record(&mut r);
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_closure_handler_flow() {
+        let mut count = 0;
+        let mut handler = ClosureRecordHandler::new(|_| {
+            count += 1;
+            if count < 2 {
+                Flow::Continue
+            } else {
+                Flow::Pause
+            }
+        });
+        let mut r = Record::new();
+        assert_eq!(Flow::Continue, handler.handle_record(&mut r).unwrap());
+        assert_eq!(Flow::Pause, handler.handle_record(&mut r).unwrap());
+    }
+
+    #[test]
+    fn test_limiting_handler() {
+        let mut records = Vec::new();
+        let mut collecting = CollectingRecordHandler::new(&mut records);
+        let mut handler = LimitingRecordHandler::new(2, &mut collecting);
+        let mut r = Record::new();
+        r.set("a", Value::I32(1));
+        assert_eq!(Flow::Continue, handler.handle_record(&mut r).unwrap());
+        assert_eq!(Flow::Stop, handler.handle_record(&mut r).unwrap());
+        assert_eq!(Flow::Stop, handler.handle_record(&mut r).unwrap());
+        assert_eq!(2, handler.count());
+        assert_eq!(2, records.len());
+
+        let mut none = Vec::new();
+        let mut collecting = CollectingRecordHandler::new(&mut none);
+        let mut handler = LimitingRecordHandler::new(0, &mut collecting);
+        assert_eq!(Flow::Stop, handler.handle_record(&mut r).unwrap());
+        assert_eq!(0, handler.count());
+        assert!(none.is_empty());
+    }
 }
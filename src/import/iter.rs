@@ -0,0 +1,305 @@
+//! Pull-based access to importers
+//!
+//! [Records] turns any [Importer] into an [Iterator] over its records. It
+//! pulls them with [Importer::next], so a consumer can stop early, interleave
+//! several importers or join two sources without threads. Importers, that
+//! only implement [Importer::read], are read completely on the first call and
+//! their records are handed out from a buffer, so this fallback does not
+//! stream.
+//!
+//! [IteratorImporter] goes the other way and turns an [Iterator] of records
+//! into an [Importer].
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     BoxedError,
+//!     import::iter::{IteratorImporter, Records},
+//!     record::Record,
+//!     value::Value,
+//! };
+//!
+//! let record = |i: i32| -> Result<Record, BoxedError> {
+//!     let mut record = Record::new();
+//!     record.set("id", Value::I32(i));
+//!     Ok(record)
+//! };
+//! let mut left = IteratorImporter::new((1..=3).map(record));
+//! let mut right = IteratorImporter::new((10..=30).map(record));
+//!
+//! // Interleave both importers and stop after four records
+//! let ids: Vec<Value> = Records::new(&mut left)
+//!     .zip(Records::new(&mut right))
+//!     .flat_map(|(l, r)| [l, r])
+//!     .take(4)
+//!     .map(|record| record.unwrap().get("id").cloned().unwrap())
+//!     .collect();
+//! assert_eq!(
+//!     vec![Value::I32(1), Value::I32(10), Value::I32(2), Value::I32(11)],
+//!     ids
+//! );
+//! ```
+use std::{collections::VecDeque, fmt::Display};
+
+use crate::{BoxedError, Initializable, record::Record, xml::config::Configuration};
+
+use super::{Importer, RecordHandler, handlers::CollectingRecordHandler};
+
+/// The error of the default [Importer::next] of importers, that only support
+/// [Importer::read]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NextNotSupported;
+
+impl Display for NextNotSupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The importer does not support next, use read instead")
+    }
+}
+
+impl std::error::Error for NextNotSupported {}
+
+/// An [Iterator] over the records of an [Importer]
+///
+/// The iterator ends after the first error.
+///
+/// # Note
+/// If the importer does not support [Importer::next], the first call reads
+/// the whole import with [Importer::read] and keeps all records in memory.
+/// An error of [Importer::read] is returned after the records read before it
+///
+/// # Members
+/// * `importer` - The importer to pull the records from
+/// * `buffer` - The records of [Importer::read], if the importer does not
+///   support [Importer::next]
+/// * `error` - The error of [Importer::read], returned after the buffer
+/// * `done` - True, after the last record or an error
+pub struct Records<'a> {
+    importer: &'a mut dyn Importer,
+    buffer: Option<VecDeque<Record>>,
+    error: Option<BoxedError>,
+    done: bool,
+}
+
+impl<'a> Records<'a> {
+    /// Creates an iterator over the records of `importer`
+    pub fn new(importer: &'a mut dyn Importer) -> Self {
+        Self {
+            importer,
+            buffer: None,
+            error: None,
+            done: false,
+        }
+    }
+
+    fn pull(&mut self) -> Result<Option<Record>, BoxedError> {
+        if self.buffer.is_none() {
+            match self.importer.next() {
+                Err(e) if e.is::<NextNotSupported>() => {
+                    let mut records = Vec::new();
+                    self.error = self
+                        .importer
+                        .read(&mut CollectingRecordHandler::new(&mut records))
+                        .err();
+                    self.buffer = Some(VecDeque::from(records));
+                }
+                result => return result,
+            }
+        }
+        match self.buffer.as_mut().and_then(VecDeque::pop_front) {
+            Some(record) => Ok(Some(record)),
+            None => self.error.take().map_or(Ok(None), Err),
+        }
+    }
+}
+
+impl Iterator for Records<'_> {
+    type Item = Result<Record, BoxedError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let result = self.pull().transpose();
+        self.done = !matches!(result, Some(Ok(_)));
+        result
+    }
+}
+
+/// An [Importer], that takes its records from an [Iterator]
+pub struct IteratorImporter<I> {
+    iter: I,
+}
+
+impl<I> IteratorImporter<I>
+where
+    I: Iterator<Item = Result<Record, BoxedError>>,
+{
+    /// Creates an importer for the records of `iter`
+    pub fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+/// There is nothing to configure
+impl<I> Initializable for IteratorImporter<I> {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl<I> Importer for IteratorImporter<I>
+where
+    I: Iterator<Item = Result<Record, BoxedError>>,
+{
+    fn read(&mut self, handler: &mut dyn RecordHandler) -> Result<(), BoxedError> {
+        for record in self.iter.by_ref() {
+            if !handler.handle_record(&mut record?)?.is_continue() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn next(&mut self) -> Result<Option<Record>, BoxedError> {
+        self.iter.next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BoxedError, Initializable,
+        field::add_field,
+        import::{Importer, RecordHandler, handlers::CollectingRecordHandler},
+        record::Record,
+        value::Value,
+        xml::config::Configuration,
+    };
+
+    use super::{IteratorImporter, NextNotSupported, Records};
+
+    /// Supports only `read`
+    struct PushImporter {
+        count: usize,
+        fail_at: Option<usize>,
+    }
+
+    impl Initializable for PushImporter {
+        fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+            Ok(())
+        }
+    }
+
+    impl Importer for PushImporter {
+        fn read(&mut self, handler: &mut dyn RecordHandler) -> Result<(), BoxedError> {
+            for i in 0..self.count {
+                if self.fail_at == Some(i) {
+                    return Err(format!("failed at {}", i).into());
+                }
+                let mut record = Record::new();
+                add_field(record.fields_as_mut(), "index", Value::USize(i));
+                if !handler.handle_record(&mut record)?.is_continue() {
+                    break;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn record(index: usize) -> Result<Record, BoxedError> {
+        let mut record = Record::new();
+        record.set("index", Value::USize(index));
+        Ok(record)
+    }
+
+    fn indices(records: impl Iterator<Item = Result<Record, BoxedError>>) -> Vec<usize> {
+        records
+            .map(|r| r.unwrap().get("index").unwrap().as_u64().unwrap() as usize)
+            .collect()
+    }
+
+    #[test]
+    fn test_next_not_supported() {
+        let mut importer = PushImporter {
+            count: 3,
+            fail_at: None,
+        };
+        let error = importer.next().unwrap_err();
+        assert!(error.is::<NextNotSupported>());
+        assert_eq!(
+            "The importer does not support next, use read instead",
+            error.to_string()
+        );
+
+        // Records falls back to read
+        assert_eq!(vec![0, 1, 2], indices(Records::new(&mut importer)));
+    }
+
+    #[test]
+    fn test_records_stop_early() {
+        let mut importer = IteratorImporter::new((0..100).map(record));
+        assert_eq!(vec![0, 1], indices(Records::new(&mut importer).take(2)));
+
+        // The importer continues after the consumed records
+        let next = importer.next().unwrap().unwrap();
+        assert_eq!(Some(&Value::USize(2)), next.get("index"));
+    }
+
+    #[test]
+    fn test_records_errors() {
+        let mut importer = PushImporter {
+            count: 3,
+            fail_at: Some(1),
+        };
+        let mut records = Records::new(&mut importer);
+        // The records read before the error come first
+        assert_eq!(
+            Some(&Value::USize(0)),
+            records.next().unwrap().unwrap().get("index")
+        );
+        assert_eq!(
+            "failed at 1",
+            records.next().unwrap().unwrap_err().to_string()
+        );
+        assert!(records.next().is_none());
+
+        let mut importer = PushImporter {
+            count: 3,
+            fail_at: Some(0),
+        };
+        let mut records = Records::new(&mut importer);
+        assert_eq!(
+            "failed at 0",
+            records.next().unwrap().unwrap_err().to_string()
+        );
+        assert!(records.next().is_none());
+
+        let mut importer =
+            IteratorImporter::new(vec![record(0), Err("broken".into()), record(2)].into_iter());
+        let mut records = Records::new(&mut importer);
+        assert!(records.next().unwrap().is_ok());
+        assert_eq!("broken", records.next().unwrap().unwrap_err().to_string());
+        assert!(records.next().is_none());
+    }
+
+    #[test]
+    fn test_iterator_importer() {
+        let mut importer = IteratorImporter::new((0..3).map(record));
+        assert!(importer.init(None).is_ok());
+        assert_eq!(
+            Some(Value::USize(0)),
+            importer
+                .next()
+                .unwrap()
+                .and_then(|r| r.get("index").cloned())
+        );
+
+        // read continues with the rest
+        let mut collected = Vec::new();
+        importer
+            .read(&mut CollectingRecordHandler::new(&mut collected))
+            .unwrap();
+        assert_eq!(2, collected.len());
+        assert!(importer.next().unwrap().is_none());
+    }
+}
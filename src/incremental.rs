@@ -0,0 +1,336 @@
+//! Incremental imports with a high-water mark
+//!
+//! An importer with an [Incremental] description, e.g.
+//! `<incremental field="updated_at"/>`, only reads the records changed since
+//! the last run. The [crate::pipeline::Pipeline] keeps the maximum value of
+//! the field, the high-water mark, per process in a [CheckpointStore]:
+//! 1. Before the importer is initialized, the mark of the last run is added
+//!    to its [Configuration] under [Incremental::key]. The importer reads the
+//!    records with a larger value, e.g. with a `WHERE updated_at > ?`
+//! 2. While the records are imported, a [HighWaterMark] tracks the maximum
+//!    value of the field
+//! 3. After all exporters received [crate::export::Signal::End], the mark
+//!    advances to that maximum. A failed run keeps the old mark
+//!
+//! A pipeline [crate::pipeline::Pipeline::with_checkpoints] also saves the
+//! mark of the records before each checkpoint under [running_store_id]. A
+//! resumed run starts from that mark, so the records exported before the
+//! crash count for the new mark, too.
+//!
+//! The mark is stored with its exact [Value] variant, so it is compared with
+//! the values of the next run by [Value::compare]. The importer gets its
+//! [Value] display string.
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     checkpoint::MemoryCheckpointStore,
+//!     incremental::{self, HighWaterMark},
+//!     record::Record,
+//!     value::Value,
+//! };
+//!
+//! let mut mark = HighWaterMark::new("updated_at", Some(Value::I64(5)));
+//! for updated_at in [7, 9, 8] {
+//!     let mut record = Record::new();
+//!     record.set("updated_at", Value::I64(updated_at));
+//!     mark.observe(&record);
+//! }
+//! assert_eq!(Some(&Value::I64(9)), mark.value());
+//!
+//! let mut store = MemoryCheckpointStore::new();
+//! incremental::save(&mut store, "orders", &Value::I64(9)).unwrap();
+//! assert_eq!(Some(Value::I64(9)), incremental::load(&store, "orders").unwrap());
+//! ```
+use std::cmp::Ordering;
+
+use crate::{
+    BoxedError,
+    checkpoint::{Checkpoint, CheckpointStore},
+    record::Record,
+    value::Value,
+    xml::{config::Configuration, import::Incremental},
+};
+
+/// Tracks the maximum value of a field
+///
+/// [Value::None] and missing fields are ignored.
+///
+/// # Members
+/// * `field` - The name of the field
+/// * `value` - The maximum value so far
+/// * `advanced` - True, if a record had a larger value than the initial mark
+#[derive(Debug, Clone)]
+pub struct HighWaterMark {
+    field: String,
+    value: Option<Value>,
+    advanced: bool,
+}
+
+impl HighWaterMark {
+    /// Creates a tracker for `field`, that starts at the mark `value` of the
+    /// last run
+    pub fn new(field: &str, value: Option<Value>) -> Self {
+        Self {
+            field: field.to_string(),
+            value,
+            advanced: false,
+        }
+    }
+
+    /// Returns the name of the tracked field
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the maximum value so far
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+
+    /// Returns true, if a record had a larger value than the initial mark
+    pub fn is_advanced(&self) -> bool {
+        self.advanced
+    }
+
+    /// Takes the value of the tracked field of `record` into account
+    pub fn observe(&mut self, record: &Record) {
+        if let Some(value) = record.get(&self.field) {
+            self.observe_value(value);
+        }
+    }
+
+    /// Takes `value` into account, e.g. the mark of an interrupted run
+    pub fn observe_value(&mut self, value: &Value) {
+        if value == &Value::None {
+            return;
+        }
+        let larger = match &self.value {
+            Some(current) => value.compare(current) == Ordering::Greater,
+            None => true,
+        };
+        if larger {
+            self.value = Some(value.clone());
+            self.advanced = true;
+        }
+    }
+}
+
+/// Separates the process id from the suffix of the ids of the marks. XML
+/// does not allow it, so no process id contains it and the ids of the marks
+/// never collide with the checkpoint of another process
+const SEPARATOR: char = '\0';
+
+/// Returns the id, under which the mark of the process `process_id` is
+/// stored. It differs from the id of every process checkpoint, so both can
+/// share a [CheckpointStore]
+pub fn store_id(process_id: &str) -> String {
+    format!("{}{}incremental", process_id, SEPARATOR)
+}
+
+/// Returns the id, under which a pipeline with checkpoints keeps the mark of
+/// the records before the last checkpoint of the process `process_id`
+pub fn running_store_id(process_id: &str) -> String {
+    format!("{}{}incremental.running", process_id, SEPARATOR)
+}
+
+/// Returns the high-water mark of the process `process_id`, if there is one
+pub fn load(store: &dyn CheckpointStore, process_id: &str) -> Result<Option<Value>, BoxedError> {
+    load_value(store, &store_id(process_id))
+}
+
+/// Saves `value` as the high-water mark of the process `process_id`
+pub fn save(
+    store: &mut dyn CheckpointStore,
+    process_id: &str,
+    value: &Value,
+) -> Result<(), BoxedError> {
+    save_value(store, &store_id(process_id), value)
+}
+
+/// Returns the mark of the records before the last checkpoint of the process
+/// `process_id`, if there is one
+pub fn load_running(
+    store: &dyn CheckpointStore,
+    process_id: &str,
+) -> Result<Option<Value>, BoxedError> {
+    load_value(store, &running_store_id(process_id))
+}
+
+/// Saves `value` as the mark of the records before the last checkpoint of the
+/// process `process_id`
+pub fn save_running(
+    store: &mut dyn CheckpointStore,
+    process_id: &str,
+    value: &Value,
+) -> Result<(), BoxedError> {
+    save_value(store, &running_store_id(process_id), value)
+}
+
+fn load_value(store: &dyn CheckpointStore, id: &str) -> Result<Option<Value>, BoxedError> {
+    let Some(checkpoint) = store.load(id)? else {
+        return Ok(None);
+    };
+    let json = serde_json::from_str(checkpoint.token())
+        .map_err(|e| format!("Invalid high-water mark '{}': {}", checkpoint, e))?;
+    Ok(Some(Value::from_tagged_json(&json)?))
+}
+
+fn save_value(store: &mut dyn CheckpointStore, id: &str, value: &Value) -> Result<(), BoxedError> {
+    let checkpoint = Checkpoint::from(value.to_tagged_json().to_string());
+    store.save(id, &checkpoint)
+}
+
+/// Returns `configuration` with the high-water mark `value` under the key of
+/// `incremental`. Without a mark, e.g. on the first run, the configuration is
+/// returned unchanged
+pub fn configure(
+    configuration: Option<Configuration>,
+    incremental: &Incremental,
+    value: Option<&Value>,
+) -> Option<Configuration> {
+    let Some(value) = value else {
+        return configuration;
+    };
+    let mut configuration = configuration.unwrap_or_else(Configuration::new);
+    if configuration.config.is_none() {
+        configuration.config = Some(Vec::new());
+    }
+    configuration.insert_str(incremental.key(), &value.to_string());
+    Some(configuration)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use crate::{
+        checkpoint::{Checkpoint, CheckpointStore, FileCheckpointStore, MemoryCheckpointStore},
+        record::Record,
+        value::Value,
+        xml::{config::Configuration, import::Incremental},
+    };
+
+    use super::{
+        HighWaterMark, configure, load, load_running, running_store_id, save, save_running,
+        store_id,
+    };
+
+    fn record(value: Value) -> Record {
+        let mut record = Record::new();
+        record.set("updated_at", value);
+        record
+    }
+
+    #[test]
+    fn test_high_water_mark() {
+        let mut mark = HighWaterMark::new("updated_at", None);
+        assert_eq!("updated_at", mark.field());
+        mark.observe(&record(Value::None));
+        mark.observe(&Record::new());
+        assert_eq!(None, mark.value());
+        assert!(!mark.is_advanced());
+
+        mark.observe(&record(Value::U8(3)));
+        mark.observe(&record(Value::I64(10)));
+        mark.observe(&record(Value::I32(7)));
+        assert_eq!(Some(&Value::I64(10)), mark.value());
+        assert!(mark.is_advanced());
+        mark.observe_value(&Value::I64(12));
+        assert_eq!(Some(&Value::I64(12)), mark.value());
+
+        // Values up to the initial mark do not advance it
+        let mut mark = HighWaterMark::new("updated_at", Some(Value::I64(10)));
+        mark.observe(&record(Value::U8(10)));
+        assert_eq!(Some(&Value::I64(10)), mark.value());
+        assert!(!mark.is_advanced());
+    }
+
+    #[test]
+    fn test_store() {
+        let mut store = MemoryCheckpointStore::new();
+        assert_eq!(None, load(&store, "orders").unwrap());
+
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        save(&mut store, "orders", &Value::DateTime(date)).unwrap();
+        assert_eq!(Some(Value::DateTime(date)), load(&store, "orders").unwrap());
+        // The checkpoint of the process is not touched
+        assert_eq!(None, store.load("orders").unwrap());
+        assert_eq!("orders\0incremental", store_id("orders"));
+
+        // The running mark is kept apart from the mark of the last run
+        assert_eq!(None, load_running(&store, "orders").unwrap());
+        save_running(&mut store, "orders", &Value::I64(3)).unwrap();
+        assert_eq!(Some(Value::I64(3)), load_running(&store, "orders").unwrap());
+        assert_eq!(Some(Value::DateTime(date)), load(&store, "orders").unwrap());
+        assert_eq!("orders\0incremental.running", running_store_id("orders"));
+
+        store
+            .save(&store_id("broken"), &"no json".to_string().into())
+            .unwrap();
+        assert!(
+            load(&store, "broken")
+                .unwrap_err()
+                .to_string()
+                .starts_with("Invalid high-water mark 'no json'")
+        );
+    }
+
+    #[test]
+    fn test_store_ids() {
+        let directory =
+            std::env::temp_dir().join(format!("model-incremental-{}", std::process::id()));
+        let mut memory = MemoryCheckpointStore::new();
+        let mut file = FileCheckpointStore::new(&directory);
+        let stores: [&mut dyn CheckpointStore; 2] = [&mut memory, &mut file];
+        for store in stores {
+            // Processes, whose ids end like the ids of the marks
+            store
+                .save("orders.incremental", &Checkpoint::new("7"))
+                .unwrap();
+            store
+                .save("orders.incremental.running", &Checkpoint::new("8"))
+                .unwrap();
+            save(store, "orders", &Value::I64(1)).unwrap();
+            save_running(store, "orders", &Value::I64(2)).unwrap();
+
+            assert_eq!(Some(Value::I64(1)), load(store, "orders").unwrap());
+            assert_eq!(Some(Value::I64(2)), load_running(store, "orders").unwrap());
+            assert_eq!(
+                Some(Checkpoint::new("7")),
+                store.load("orders.incremental").unwrap()
+            );
+            assert_eq!(
+                Some(Checkpoint::new("8")),
+                store.load("orders.incremental.running").unwrap()
+            );
+        }
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_configure() {
+        let incremental = Incremental {
+            field: "updated_at".to_string(),
+            key: None,
+        };
+        assert!(configure(None, &incremental, None).is_none());
+
+        let configuration = configure(None, &incremental, Some(&Value::I64(5))).unwrap();
+        assert_eq!(Some("5".to_string()), configuration.get("high_water_mark"));
+
+        let mut configuration = Configuration::new();
+        configuration.insert_str("table", "orders");
+        let incremental = Incremental {
+            field: "updated_at".to_string(),
+            key: Some("since".to_string()),
+        };
+        let configuration =
+            configure(Some(configuration), &incremental, Some(&Value::from("x"))).unwrap();
+        assert_eq!(2, configuration.len());
+        assert_eq!(Some("x".to_string()), configuration.get("since"));
+    }
+}
@@ -7,6 +7,12 @@ pub mod import;
 pub mod transform;
 pub mod export;
 pub mod plugin;
+pub mod serialization;
+pub mod pipeline;
+pub mod schema;
+pub mod mapping;
+pub mod checkpoint;
+pub mod incremental;
 
 // Type aliases for async trait methods
 pub type BoxedError = Box<dyn std::error::Error>;
@@ -0,0 +1,282 @@
+//! Mapping of Rust structs to and from a [Record]
+//!
+//! [IntoRecord] and [FromRecord] convert a struct into a record and back.
+//! With the feature `derive`, both can be derived. The fields are converted
+//! with [IntoValue] and [FromValue], which are implemented for all types with
+//! a `From<T> for Value` conversion, for [Value] and [Record] themselves and
+//! for structs deriving the traits:
+//! * An [Option] is a field, that is absent or [Value::None]. A [None] field
+//!   is not added to the record, like [crate::field::add_optional_field] does
+//! * A nested struct is a [Value::Record]
+//! * A [Vec] is a [Value::Collection], also a `Vec<u8>`
+//!
+//! The derive macros accept the field attributes `#[record(rename = "name")]`
+//! and `#[record(skip)]`. Skipped fields are not added to the record and are
+//! set to [Default::default] when reading it.
+//!
+//! # Example
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use model::mapping::{FromRecord, IntoRecord};
+//! use model::value::Value;
+//!
+//! #[derive(Debug, PartialEq, IntoRecord, FromRecord)]
+//! struct Address {
+//!     city: String,
+//! }
+//!
+//! #[derive(Debug, PartialEq, IntoRecord, FromRecord)]
+//! struct Person {
+//!     #[record(rename = "person_id")]
+//!     id: u32,
+//!     nickname: Option<String>,
+//!     address: Address,
+//!     tags: Vec<String>,
+//!     #[record(skip)]
+//!     cache: Vec<u8>,
+//! }
+//!
+//! let person = Person {
+//!     id: 42,
+//!     nickname: None,
+//!     address: Address { city: "Vienna".to_string() },
+//!     tags: vec!["a".to_string()],
+//!     cache: vec![1],
+//! };
+//! let record = person.into_record();
+//! assert_eq!(Some(&Value::U32(42)), record.get("person_id"));
+//! assert!(!record.contains("nickname"));
+//!
+//! let person = Person::from_record(&record).unwrap();
+//! assert_eq!("Vienna", person.address.city);
+//! assert!(person.cache.is_empty());
+//! # }
+//! ```
+use std::fmt::Display;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::{
+    record::Record,
+    value::{Value, try_from::ConversionError},
+};
+
+#[cfg(feature = "derive")]
+pub use model_derive::{FromRecord, IntoRecord};
+
+/// The error returned, when a [Record] or [Value] cannot be mapped to a Rust
+/// type
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappingError {
+    path: String,
+    message: String,
+}
+
+impl MappingError {
+    /// Creates an error with a message
+    pub fn new(message: impl Display) -> Self {
+        Self {
+            path: String::new(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates the error for a missing field
+    pub fn missing() -> Self {
+        Self::new("missing")
+    }
+
+    /// Returns the error with the field `name` prepended to the path
+    pub fn within_field(mut self, name: &str) -> Self {
+        self.path = match self.path.chars().next() {
+            None => name.to_string(),
+            Some('[') => format!("{}{}", name, self.path),
+            Some(_) => format!("{}.{}", name, self.path),
+        };
+        self
+    }
+
+    /// Returns the error with the collection `index` prepended to the path
+    pub fn within_index(mut self, index: usize) -> Self {
+        self.path = match self.path.chars().next() {
+            None | Some('[') => format!("[{}]{}", index, self.path),
+            Some(_) => format!("[{}].{}", index, self.path),
+        };
+        self
+    }
+
+    /// Returns the path of the field, that could not be mapped, e.g.
+    /// `address.city` or `tags[2]`
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Display for MappingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "Field '{}': {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for MappingError {}
+
+impl From<ConversionError> for MappingError {
+    fn from(error: ConversionError) -> Self {
+        MappingError::new(error)
+    }
+}
+
+/// Converts a Rust value into a [Value]
+pub trait IntoValue {
+    /// Converts into a [Value]
+    fn into_value(self) -> Value;
+
+    /// Converts into the value of a field or [None], if the field is absent
+    fn into_field_value(self) -> Option<Value>
+    where
+        Self: Sized,
+    {
+        Some(self.into_value())
+    }
+}
+
+/// Converts a [Value] into a Rust value
+pub trait FromValue: Sized {
+    /// Converts from a [Value]
+    fn from_value(value: &Value) -> Result<Self, MappingError>;
+
+    /// Converts from the value of a field, that is [None] if the field is
+    /// absent
+    fn from_field_value(value: Option<&Value>) -> Result<Self, MappingError> {
+        match value {
+            Some(value) => Self::from_value(value),
+            None => Err(MappingError::missing()),
+        }
+    }
+}
+
+/// Converts a Rust struct into a [Record]
+pub trait IntoRecord {
+    /// Converts into a [Record]
+    fn into_record(self) -> Record;
+}
+
+/// Converts a [Record] into a Rust struct
+pub trait FromRecord: Sized {
+    /// Converts from a [Record]
+    fn from_record(record: &Record) -> Result<Self, MappingError>;
+}
+
+/// Implements [IntoValue] and [FromValue] with the `From` and `TryFrom`
+/// conversions of [Value]
+macro_rules! value_mapping {
+    ($($target:ty),*) => {$(
+        impl IntoValue for $target {
+            fn into_value(self) -> Value {
+                Value::from(self)
+            }
+        }
+
+        impl FromValue for $target {
+            fn from_value(value: &Value) -> Result<Self, MappingError> {
+                Ok(<$target>::try_from(value)?)
+            }
+        }
+    )*};
+}
+
+value_mapping!(
+    bool,
+    char,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    Decimal,
+    String,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    DateTime<FixedOffset>,
+    TimeDelta,
+    Uuid,
+    Record
+);
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Result<Self, MappingError> {
+        Ok(value.clone())
+    }
+}
+
+impl<T: IntoValue> IntoValue for Option<T> {
+    /// Converts [None] into [Value::None]
+    fn into_value(self) -> Value {
+        self.map_or(Value::None, IntoValue::into_value)
+    }
+
+    fn into_field_value(self) -> Option<Value> {
+        self.map(IntoValue::into_value)
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self, MappingError> {
+        match value {
+            Value::None => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+
+    fn from_field_value(value: Option<&Value>) -> Result<Self, MappingError> {
+        value.map_or(Ok(None), Self::from_value)
+    }
+}
+
+impl<T: IntoValue> IntoValue for Vec<T> {
+    fn into_value(self) -> Value {
+        Value::Collection(self.into_iter().map(IntoValue::into_value).collect())
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(value: &Value) -> Result<Self, MappingError> {
+        match value {
+            Value::Collection(items) => items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| T::from_value(item).map_err(|e| e.within_index(index)))
+                .collect(),
+            _ => Err(MappingError::new(format!(
+                "Cannot convert {} to Vec",
+                value.type_name()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,77 @@
+use rust_decimal::Decimal;
+
+use crate::{record::Record, value::Value};
+
+use super::{FromValue, IntoValue, MappingError};
+
+#[test]
+fn test_values() {
+    assert_eq!(Value::U8(1), 1u8.into_value());
+    assert_eq!(Value::from("a"), "a".to_string().into_value());
+    assert_eq!(
+        Value::Decimal(Decimal::new(15, 1)),
+        Decimal::new(15, 1).into_value()
+    );
+    assert_eq!(Ok(5i64), i64::from_value(&Value::U8(5)));
+    assert_eq!(Ok(Value::Bool(true)), Value::from_value(&Value::Bool(true)));
+    assert_eq!(
+        "Cannot convert String to bool",
+        bool::from_value(&Value::from("x")).unwrap_err().to_string()
+    );
+    assert_eq!(
+        Ok(Record::new()),
+        Record::from_value(&Value::Record(Record::new()))
+    );
+}
+
+#[test]
+fn test_option() {
+    assert_eq!(Value::None, None::<i32>.into_value());
+    assert_eq!(None, None::<i32>.into_field_value());
+    assert_eq!(Some(Value::I32(1)), Some(1).into_field_value());
+    assert_eq!(Some(Value::I32(1)), 1.into_field_value());
+
+    assert_eq!(Ok(None), Option::<i32>::from_field_value(None));
+    assert_eq!(
+        Ok(None),
+        Option::<i32>::from_field_value(Some(&Value::None))
+    );
+    assert_eq!(
+        Ok(Some(1)),
+        Option::<i32>::from_field_value(Some(&Value::U8(1)))
+    );
+    assert_eq!(Err(MappingError::missing()), i32::from_field_value(None));
+    assert!(i32::from_value(&Value::None).is_err());
+}
+
+#[test]
+fn test_vec() {
+    assert_eq!(
+        Value::Collection(vec![Value::U8(1), Value::U8(2)]),
+        vec![1u8, 2].into_value()
+    );
+    assert_eq!(
+        Ok(vec![Some(1u16), None]),
+        Vec::<Option<u16>>::from_value(&Value::Collection(vec![Value::U8(1), Value::None]))
+    );
+
+    let error =
+        Vec::<u8>::from_value(&Value::Collection(vec![Value::U8(1), Value::I32(-1)])).unwrap_err();
+    assert_eq!("[1]", error.path());
+    assert!(Vec::<u8>::from_value(&Value::Blob(vec![].into())).is_err());
+}
+
+#[test]
+fn test_error_path() {
+    let error = MappingError::new("wrong")
+        .within_field("city")
+        .within_index(2)
+        .within_field("addresses")
+        .within_field("person");
+    assert_eq!("person.addresses[2].city", error.path());
+    assert_eq!("Field 'person.addresses[2].city': wrong", error.to_string());
+
+    let error = MappingError::new("wrong").within_index(0).within_index(1);
+    assert_eq!("[1][0]", error.path());
+    assert_eq!("wrong", MappingError::new("wrong").to_string());
+}
@@ -0,0 +1,618 @@
+//! Module for running a RITE process end to end
+//!
+//! A [Pipeline] is built from a [Process] description and a [PluginResolver],
+//! which creates the importer, transformer and exporter components. Running
+//! the pipeline follows the semantics documented at [Process]:
+//! 1. All components are initialized with their [Configuration] when the
+//!    pipeline is built
+//! 2. Every exporter receives [Signal::Start]
+//! 3. The importer reads all records. Every record is fed through all
+//!    transformers in the order of the description, and the result is
+//!    written to every exporter. A transformer, that returns a record without
+//!    [crate::record::meta::RecordMeta], keeps the meta data of its input
+//! 4. Every exporter receives [Signal::End]
+//!
+//! A pipeline [Pipeline::with_checkpoints] can resume after a crash. Every
+//! `interval` records, it sends [Signal::Checkpoint] to all exporters and
+//! then saves the [Checkpoint] of the last imported record to the
+//! [CheckpointStore]. The next run reads the records after the saved
+//! checkpoint with [Importer::read_from]. After a complete run the checkpoint
+//! is cleared. An incremental pipeline saves its high-water mark with every
+//! checkpoint, so a resumed run does not lose the mark of the records
+//! exported before the crash.
+//!
+//! A pipeline [Pipeline::new_incremental] imports only the records changed
+//! since the last run, see [crate::incremental].
+//!
+//! The first error of any component stops the run and is returned. Errors of
+//! transformers and exporters point to the source of the record, if the
+//! importer filled in its meta data.
+//!
+//! # Example
+//! ```no_run
+//! use std::collections::HashMap;
+//! use model::pipeline::{LibraryResolver, Pipeline};
+//! use model::xml::file::create_rite;
+//!
+//! let rite = create_rite("example.xml", &HashMap::new()).unwrap();
+//! let resolver = LibraryResolver::new(&rite.plugins).unwrap();
+//! for mut pipeline in Pipeline::from_rite(&rite, &resolver).unwrap() {
+//!     let report = pipeline.run().unwrap();
+//!     println!("{:?}", report);
+//! }
+//! ```
+use std::{collections::HashMap, marker::PhantomData};
+
+use crate::{
+    BoxedError, Initializable,
+    checkpoint::{Checkpoint, CheckpointStore},
+    export::{Exporter, Signal},
+    import::{Flow, Importer, RecordHandler},
+    incremental::{self, HighWaterMark},
+    plugin::Plugin,
+    record::Record,
+    transform::Transformer,
+    value::Value,
+    xml::{self, Rite, config::Configuration, process::Process},
+};
+
+/// Creates the components of a [Pipeline] from the plugin ids of a [Process]
+pub trait PluginResolver {
+    /// Creates the importer `name` from the plugin with the id `plugin`
+    fn create_importer(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Importer>, BoxedError>;
+
+    /// Creates the transformer `name` from the plugin with the id `plugin`
+    fn create_transformer(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Transformer>, BoxedError>;
+
+    /// Creates the exporter `name` from the plugin with the id `plugin`
+    fn create_exporter(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Exporter>, BoxedError>;
+}
+
+/// A [PluginResolver] that loads the dynamic libraries of the plugins section
+/// of a [Rite]
+pub struct LibraryResolver {
+    plugins: HashMap<String, Plugin>,
+}
+
+impl LibraryResolver {
+    /// Loads all plugins from the `plugins` section
+    ///
+    /// # Arguments
+    /// * `plugins` - The [xml::plugin::Plugins] of a [Rite]
+    pub fn new(plugins: &xml::plugin::Plugins) -> Result<Self, BoxedError> {
+        let mut loaded = HashMap::new();
+        for plugin in &plugins.plugins {
+            let library = Plugin::new(plugin.path.as_deref(), &plugin.name)
+                .map_err(|e| format!("Cannot load plugin '{}': {}", plugin.id, e))?;
+            loaded.insert(plugin.id.clone(), library);
+        }
+        Ok(Self { plugins: loaded })
+    }
+
+    fn plugin(&self, id: &str) -> Result<&Plugin, BoxedError> {
+        self.plugins
+            .get(id)
+            .ok_or_else(|| format!("Unknown plugin '{}'", id).into())
+    }
+}
+
+impl PluginResolver for LibraryResolver {
+    fn create_importer(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Importer>, BoxedError> {
+        self.plugin(plugin)?.create_importer(name)
+    }
+
+    fn create_transformer(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Transformer>, BoxedError> {
+        self.plugin(plugin)?.create_transformer(name)
+    }
+
+    fn create_exporter(
+        &self,
+        plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Exporter>, BoxedError> {
+        self.plugin(plugin)?.create_exporter(name)
+    }
+}
+
+/// The number of records a single stage of a [Pipeline] has processed
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageReport {
+    /// The stage label in the form `plugin` or `plugin:name`
+    pub stage: String,
+    /// Number of records processed by this stage
+    pub records: usize,
+}
+
+/// The result of a [Pipeline::run]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunReport {
+    /// The id of the process that was run
+    pub process_id: String,
+    /// Number of records read by the importer
+    pub imported: usize,
+    /// One entry per transformer, in the order of the process description
+    pub transformers: Vec<StageReport>,
+    /// One entry per exporter, in the order of the process description
+    pub exporters: Vec<StageReport>,
+    /// The checkpoint the run resumed from, if there was one
+    pub resumed_from: Option<Checkpoint>,
+    /// The high-water mark after the run, if the import is incremental
+    pub high_water_mark: Option<Value>,
+}
+
+/// A component of the pipeline together with its label
+struct Stage<T: ?Sized> {
+    label: String,
+    component: Box<T>,
+}
+
+/// Where and how often a [Pipeline] saves its checkpoints
+struct Checkpoints<'a> {
+    store: Box<dyn CheckpointStore + 'a>,
+    interval: usize,
+}
+
+/// What a [Pipeline::new_incremental] needs to initialize its importer again
+/// with an advanced high-water mark
+struct IncrementalImport {
+    description: xml::import::Incremental,
+    /// The configuration of the importer without the mark
+    configuration: Option<Configuration>,
+    /// True, if the mark advanced since the importer was initialized
+    outdated: bool,
+}
+
+/// A runnable import -> transform -> export pipeline
+///
+/// The lifetime `'a` binds the pipeline to its [PluginResolver], so that the
+/// components cannot outlive the libraries they were created from
+pub struct Pipeline<'a> {
+    process_id: String,
+    importer: Stage<dyn Importer>,
+    transformers: Vec<Stage<dyn Transformer>>,
+    exporters: Vec<Stage<dyn Exporter>>,
+    checkpoints: Option<Checkpoints<'a>>,
+    high_water_mark: Option<HighWaterMark>,
+    marks: Option<Box<dyn CheckpointStore + 'a>>,
+    incremental: Option<IncrementalImport>,
+    _resolver: PhantomData<&'a dyn PluginResolver>,
+}
+
+impl<'a> Pipeline<'a> {
+    /// Creates and initializes all components of `process`
+    ///
+    /// # Arguments
+    /// * `process` - The [Process] description
+    /// * `resolver` - The [PluginResolver] that creates the components
+    ///
+    /// An [xml::import::Incremental] importer tracks its high-water mark,
+    /// but every run imports all records, since the mark is not stored. Use
+    /// [Pipeline::new_incremental] for that
+    pub fn new(process: &Process, resolver: &'a dyn PluginResolver) -> Result<Self, BoxedError> {
+        Self::build(process, resolver, None)
+    }
+
+    /// Creates and initializes all components of `process`, that imports
+    /// only the records changed since the last run
+    ///
+    /// The importer gets the high-water mark of the last run in its
+    /// configuration. After a complete run the mark advances to the maximum
+    /// value of the incremental field. Before the next [Pipeline::run], the
+    /// importer is initialized again with the advanced mark
+    ///
+    /// # Arguments
+    /// * `process` - The [Process] description with an
+    ///   [xml::import::Incremental] importer
+    /// * `resolver` - The [PluginResolver] that creates the components
+    /// * `store` - The [CheckpointStore], that keeps the high-water mark under
+    ///   [incremental::store_id]
+    pub fn new_incremental(
+        process: &Process,
+        resolver: &'a dyn PluginResolver,
+        store: Box<dyn CheckpointStore + 'a>,
+    ) -> Result<Self, BoxedError> {
+        if process.importer.incremental.is_none() {
+            return Err(format!(
+                "The importer of process '{}' has no incremental element",
+                process.id
+            )
+            .into());
+        }
+        Self::build(process, resolver, Some(store))
+    }
+
+    fn build(
+        process: &Process,
+        resolver: &'a dyn PluginResolver,
+        marks: Option<Box<dyn CheckpointStore + 'a>>,
+    ) -> Result<Self, BoxedError> {
+        let description = &process.importer;
+        let mut configuration = description.configuration.clone();
+        let mut high_water_mark = None;
+        let mut incremental_import = None;
+        if let Some(description) = &description.incremental {
+            if marks.is_some() {
+                incremental_import = Some(IncrementalImport {
+                    description: description.clone(),
+                    configuration: configuration.clone(),
+                    outdated: false,
+                });
+            }
+            let value = match &marks {
+                Some(store) => incremental::load(store.as_ref(), &process.id)
+                    .map_err(|e| format!("Cannot load high-water mark: {}", e))?,
+                None => None,
+            };
+            configuration = incremental::configure(configuration, description, value.as_ref());
+            high_water_mark = Some(HighWaterMark::new(&description.field, value));
+        }
+
+        let mut importer = Stage {
+            label: label(&description.plugin, &description.name),
+            component: resolver
+                .create_importer(&description.plugin, description.name.as_deref())?,
+        };
+        initialize(&importer.label, importer.component.as_mut(), &configuration)?;
+
+        let mut transformers = Vec::new();
+        if let Some(descriptions) = process
+            .transformers
+            .as_ref()
+            .and_then(|t| t.transformers.as_ref())
+        {
+            for description in descriptions {
+                let mut stage = Stage {
+                    label: label(&description.plugin, &description.name),
+                    component: resolver
+                        .create_transformer(&description.plugin, description.name.as_deref())?,
+                };
+                initialize(
+                    &stage.label,
+                    stage.component.as_mut(),
+                    &description.configuration,
+                )?;
+                transformers.push(stage);
+            }
+        }
+
+        let mut exporters = Vec::new();
+        for description in &process.exporters.exporters {
+            let mut stage = Stage {
+                label: label(&description.plugin, &description.name),
+                component: resolver
+                    .create_exporter(&description.plugin, description.name.as_deref())?,
+            };
+            initialize(
+                &stage.label,
+                stage.component.as_mut(),
+                &description.configuration,
+            )?;
+            exporters.push(stage);
+        }
+
+        Ok(Self {
+            process_id: process.id.clone(),
+            importer,
+            transformers,
+            exporters,
+            checkpoints: None,
+            high_water_mark,
+            marks,
+            incremental: incremental_import,
+            _resolver: PhantomData,
+        })
+    }
+
+    /// Creates a [Pipeline] for every process of `rite`
+    ///
+    /// # Arguments
+    /// * `rite` - The [Rite] configuration
+    /// * `resolver` - The [PluginResolver] that creates the components
+    pub fn from_rite(
+        rite: &Rite,
+        resolver: &'a dyn PluginResolver,
+    ) -> Result<Vec<Self>, BoxedError> {
+        rite.processes
+            .processes
+            .iter()
+            .map(|process| Pipeline::new(process, resolver))
+            .collect()
+    }
+
+    /// Saves a checkpoint to `store` every `interval` records and resumes
+    /// from it
+    ///
+    /// # Arguments
+    /// * `store` - The [CheckpointStore], that keeps the checkpoint under the
+    ///   id of the process
+    /// * `interval` - The number of records between two checkpoints
+    pub fn with_checkpoints(
+        mut self,
+        store: Box<dyn CheckpointStore + 'a>,
+        interval: usize,
+    ) -> Self {
+        self.checkpoints = Some(Checkpoints {
+            store,
+            interval: interval.max(1),
+        });
+        self
+    }
+
+    /// Returns the id of the process this pipeline was built from
+    pub fn process_id(&self) -> &str {
+        &self.process_id
+    }
+
+    /// Runs the pipeline and returns a [RunReport] with the record counts of
+    /// every stage
+    pub fn run(&mut self) -> Result<RunReport, BoxedError> {
+        if let Some(import) = &mut self.incremental
+            && import.outdated
+        {
+            let value = self.high_water_mark.as_ref().and_then(HighWaterMark::value);
+            let configuration =
+                incremental::configure(import.configuration.clone(), &import.description, value);
+            initialize(
+                &self.importer.label,
+                self.importer.component.as_mut(),
+                &configuration,
+            )?;
+            import.outdated = false;
+        }
+
+        let resumed_from = match &self.checkpoints {
+            Some(checkpoints) => checkpoints
+                .store
+                .load(&self.process_id)
+                .map_err(|e| format!("Cannot load checkpoint: {}", e))?,
+            None => None,
+        };
+        let mut high_water_mark = self.high_water_mark.clone();
+        if let (Some(checkpoints), Some(mark), Some(_)) =
+            (&self.checkpoints, &mut high_water_mark, &resumed_from)
+        {
+            let running = incremental::load_running(checkpoints.store.as_ref(), &self.process_id)
+                .map_err(|e| format!("Cannot load high-water mark: {}", e))?;
+            if let Some(value) = running {
+                mark.observe_value(&value);
+            }
+        }
+
+        for exporter in self.exporters.iter_mut() {
+            exporter
+                .component
+                .event(Signal::Start)
+                .map_err(|e| stage_error("Exporter", &exporter.label, e))?;
+        }
+
+        let mut handler = PipelineHandler {
+            imported: 0,
+            transformed: vec![0; self.transformers.len()],
+            exported: vec![0; self.exporters.len()],
+            transformers: &self.transformers,
+            exporters: &mut self.exporters,
+            process_id: &self.process_id,
+            checkpoints: self.checkpoints.as_mut(),
+            pending: None,
+            uncommitted: 0,
+            high_water_mark,
+        };
+        match &resumed_from {
+            Some(checkpoint) => self.importer.component.read_from(checkpoint, &mut handler),
+            None => self.importer.component.read(&mut handler),
+        }
+        .map_err(|e| stage_error("Importer", &self.importer.label, e))?;
+        let PipelineHandler {
+            imported,
+            transformed,
+            exported,
+            high_water_mark,
+            ..
+        } = handler;
+
+        let report = RunReport {
+            process_id: self.process_id.clone(),
+            imported,
+            transformers: stage_reports(&self.transformers, transformed),
+            exporters: stage_reports(&self.exporters, exported),
+            resumed_from,
+            high_water_mark: high_water_mark
+                .as_ref()
+                .and_then(|mark| mark.value().cloned()),
+        };
+
+        for exporter in self.exporters.iter_mut() {
+            exporter
+                .component
+                .event(Signal::End)
+                .map_err(|e| stage_error("Exporter", &exporter.label, e))?;
+        }
+        if let Some(checkpoints) = &mut self.checkpoints {
+            checkpoints
+                .store
+                .clear(&self.process_id)
+                .map_err(|e| format!("Cannot clear checkpoint: {}", e))?;
+            if self.high_water_mark.is_some() {
+                checkpoints
+                    .store
+                    .clear(&incremental::running_store_id(&self.process_id))
+                    .map_err(|e| format!("Cannot clear high-water mark: {}", e))?;
+            }
+        }
+        if let Some(mark) = high_water_mark
+            && mark.is_advanced()
+        {
+            if let (Some(store), Some(value)) = (&mut self.marks, mark.value()) {
+                incremental::save(store.as_mut(), &self.process_id, value)
+                    .map_err(|e| format!("Cannot save high-water mark: {}", e))?;
+            }
+            self.high_water_mark = Some(HighWaterMark::new(mark.field(), mark.value().cloned()));
+            if let Some(import) = &mut self.incremental {
+                import.outdated = true;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// The [RecordHandler] that feeds the imported records through the
+/// transformers to the exporters
+struct PipelineHandler<'p, 'a> {
+    transformers: &'p [Stage<dyn Transformer>],
+    exporters: &'p mut [Stage<dyn Exporter>],
+    imported: usize,
+    transformed: Vec<usize>,
+    exported: Vec<usize>,
+    process_id: &'p str,
+    checkpoints: Option<&'p mut Checkpoints<'a>>,
+    /// The checkpoint of the last imported record, that is not saved yet
+    pending: Option<Checkpoint>,
+    /// The number of records since the last saved checkpoint
+    uncommitted: usize,
+    high_water_mark: Option<HighWaterMark>,
+}
+
+impl PipelineHandler<'_, '_> {
+    /// Lets all exporters persist the records written so far and then saves
+    /// the high-water mark of these records and the pending checkpoint
+    fn commit(&mut self) -> Result<(), BoxedError> {
+        self.uncommitted = 0;
+        let (Some(checkpoints), Some(checkpoint)) = (&mut self.checkpoints, self.pending.take())
+        else {
+            return Ok(());
+        };
+        for exporter in self.exporters.iter_mut() {
+            exporter
+                .component
+                .event(Signal::Checkpoint)
+                .map_err(|e| stage_error("Exporter", &exporter.label, e))?;
+        }
+        if let Some(value) = self.high_water_mark.as_ref().and_then(HighWaterMark::value) {
+            incremental::save_running(checkpoints.store.as_mut(), self.process_id, value)
+                .map_err(|e| format!("Cannot save high-water mark: {}", e))?;
+        }
+        checkpoints
+            .store
+            .save(self.process_id, &checkpoint)
+            .map_err(|e| format!("Cannot save checkpoint {}: {}", checkpoint, e))?;
+        Ok(())
+    }
+}
+
+impl RecordHandler for PipelineHandler<'_, '_> {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        self.imported += 1;
+        if let Some(mark) = &mut self.high_water_mark {
+            mark.observe(record);
+        }
+
+        let mut current: Option<Record> = None;
+        for (index, transformer) in self.transformers.iter().enumerate() {
+            let input = current.as_ref().unwrap_or(record);
+            let mut output = transformer
+                .component
+                .process(input)
+                .map_err(|e| record_error("Transformer", &transformer.label, input, e))?;
+            if output.meta().is_empty() && !input.meta().is_empty() {
+                *output.meta_mut() = input.meta().clone();
+            }
+            self.transformed[index] += 1;
+            current = Some(output);
+        }
+
+        let output = current.as_ref().unwrap_or(record);
+        for (index, exporter) in self.exporters.iter_mut().enumerate() {
+            exporter
+                .component
+                .write(output)
+                .map_err(|e| record_error("Exporter", &exporter.label, output, e))?;
+            self.exported[index] += 1;
+        }
+
+        if let Some(checkpoints) = &self.checkpoints {
+            if let Some(checkpoint) = record.meta().checkpoint() {
+                self.pending = Some(checkpoint.clone());
+            }
+            self.uncommitted += 1;
+            if self.uncommitted >= checkpoints.interval {
+                self.commit()?;
+            }
+        }
+
+        Ok(Flow::Continue)
+    }
+}
+
+/// Returns the label of a component in the form `plugin` or `plugin:name`
+fn label(plugin: &str, name: &Option<String>) -> String {
+    match name {
+        Some(name) => format!("{}:{}", plugin, name),
+        None => plugin.to_string(),
+    }
+}
+
+/// Initializes a component with a copy of its configuration
+fn initialize<T: Initializable + ?Sized>(
+    label: &str,
+    component: &mut T,
+    configuration: &Option<Configuration>,
+) -> Result<(), BoxedError> {
+    component
+        .init(configuration.clone())
+        .map_err(|e| format!("Cannot initialize '{}': {}", label, e).into())
+}
+
+fn stage_error(kind: &str, label: &str, error: BoxedError) -> BoxedError {
+    format!("{} '{}' failed: {}", kind, label, error).into()
+}
+
+/// Returns the error of a stage, that failed on `record`. The error points to
+/// the source of the record, if its [crate::record::meta::RecordMeta] is known
+fn record_error(kind: &str, label: &str, record: &Record, error: BoxedError) -> BoxedError {
+    if record.meta().is_empty() {
+        return stage_error(kind, label, error);
+    }
+    format!(
+        "{} '{}' failed at {}: {}",
+        kind,
+        label,
+        record.meta(),
+        error
+    )
+    .into()
+}
+
+fn stage_reports<T: ?Sized>(stages: &[Stage<T>], counts: Vec<usize>) -> Vec<StageReport> {
+    stages
+        .iter()
+        .zip(counts)
+        .map(|(stage, records)| StageReport {
+            stage: stage.label.clone(),
+            records,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,828 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    BoxedError, Initializable,
+    checkpoint::{Checkpoint, CheckpointStore, CheckpointsNotSupported, MemoryCheckpointStore},
+    export::{Exporter, Signal},
+    field::add_field,
+    import::{Importer, RecordHandler},
+    incremental,
+    record::{Record, meta::RecordMeta},
+    transform::Transformer,
+    value::Value,
+    xml::{config::Configuration, process::Process},
+};
+
+use super::{Pipeline, PipelineHandler, PluginResolver, RunReport, StageReport};
+
+/// Everything the test components observed
+#[derive(Default)]
+struct Log {
+    events: Vec<String>,
+    written: Vec<Record>,
+}
+
+/// Imports `count` records. With a `source` every record gets a checkpoint
+/// with the index of the next record. With `fail_at` the importer fails
+/// before that index, like a crash. With a `high_water_mark` only the records
+/// with a larger index are imported. With `descending` every record gets a
+/// field `updated`, that decreases from `count` to 1
+struct TestImporter {
+    count: usize,
+    source: Option<String>,
+    fail_at: Option<usize>,
+    high_water_mark: Option<usize>,
+    descending: bool,
+}
+
+impl Initializable for TestImporter {
+    fn init(&mut self, config: Option<Configuration>) -> Result<(), BoxedError> {
+        if let Some(config) = config {
+            self.count = config.get_result("count")?.parse()?;
+            self.source = config.get("source");
+            if let Some(fail_at) = config.get("fail_at") {
+                self.fail_at = Some(fail_at.parse()?);
+            }
+            if let Some(mark) = config.get("high_water_mark") {
+                self.high_water_mark = Some(mark.parse()?);
+            }
+            self.descending = config.get("descending").is_some();
+        }
+        Ok(())
+    }
+}
+
+impl TestImporter {
+    fn read_range(
+        &mut self,
+        start: usize,
+        handler: &mut dyn RecordHandler,
+    ) -> Result<(), BoxedError> {
+        for i in start..self.count {
+            if self.fail_at == Some(i) {
+                return Err(format!("crashed at {}", i).into());
+            }
+            if self.high_water_mark.is_some_and(|mark| i <= mark) {
+                continue;
+            }
+            let mut record = Record::new();
+            if let Some(source) = &self.source {
+                record = record.with_meta(
+                    RecordMeta::new()
+                        .with_source(source)
+                        .with_sequence(i as u64)
+                        .with_offset(i as u64 + 2)
+                        .with_checkpoint(Checkpoint::new(&(i + 1).to_string())),
+                );
+            }
+            add_field(record.fields_as_mut(), "index", Value::USize(i));
+            if self.descending {
+                add_field(
+                    record.fields_as_mut(),
+                    "updated",
+                    Value::USize(self.count - i),
+                );
+            }
+            if !handler.handle_record(&mut record)?.is_continue() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Importer for TestImporter {
+    fn read(&mut self, handler: &mut dyn RecordHandler) -> Result<(), BoxedError> {
+        self.read_range(0, handler)
+    }
+
+    fn read_from(
+        &mut self,
+        checkpoint: &Checkpoint,
+        handler: &mut dyn RecordHandler,
+    ) -> Result<(), BoxedError> {
+        if self.source.is_none() {
+            return Err(Box::new(CheckpointsNotSupported));
+        }
+        self.read_range(checkpoint.token().parse()?, handler)
+    }
+}
+
+struct Suffix {
+    suffix: String,
+}
+
+impl Initializable for Suffix {
+    fn init(&mut self, config: Option<Configuration>) -> Result<(), BoxedError> {
+        if let Some(config) = config {
+            self.suffix = config.get_result("suffix")?;
+        }
+        Ok(())
+    }
+}
+
+impl Transformer for Suffix {
+    fn process(&self, record: &Record) -> Result<Record, BoxedError> {
+        let mut result = Record::copy(record);
+        let text = match result.field_by_name("text") {
+            Some(field) => format!("{}{}", field.value(), self.suffix),
+            None => self.suffix.clone(),
+        };
+        result.fields_as_mut().retain(|f| f.name() != "text");
+        add_field(result.fields_as_mut(), "text", Value::String(text.into()));
+        Ok(result)
+    }
+}
+
+struct Failing;
+
+impl Initializable for Failing {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Transformer for Failing {
+    fn process(&self, _record: &Record) -> Result<Record, BoxedError> {
+        Err("always fails".into())
+    }
+}
+
+/// Returns a new record with the fields of the input, but without its meta
+/// data
+struct Fresh;
+
+impl Initializable for Fresh {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Transformer for Fresh {
+    fn process(&self, record: &Record) -> Result<Record, BoxedError> {
+        let mut result = Record::new();
+        *result.fields_as_mut() = record.fields().clone();
+        Ok(result)
+    }
+}
+
+struct TestExporter {
+    name: String,
+    log: Rc<RefCell<Log>>,
+}
+
+impl Initializable for TestExporter {
+    fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+        Ok(())
+    }
+}
+
+impl Exporter for TestExporter {
+    fn write(&mut self, record: &Record) -> Result<(), BoxedError> {
+        let mut log = self.log.borrow_mut();
+        log.events.push(format!("{}:write", self.name));
+        log.written.push(Record::copy(record));
+        Ok(())
+    }
+
+    fn event(&mut self, signal: Signal) -> Result<(), BoxedError> {
+        self.log
+            .borrow_mut()
+            .events
+            .push(format!("{}:{:?}", self.name, signal));
+        Ok(())
+    }
+}
+
+struct TestResolver {
+    log: Rc<RefCell<Log>>,
+}
+
+impl PluginResolver for TestResolver {
+    fn create_importer(
+        &self,
+        plugin: &str,
+        _name: Option<&str>,
+    ) -> Result<Box<dyn Importer>, BoxedError> {
+        match plugin {
+            "test" => Ok(Box::new(TestImporter {
+                count: 0,
+                source: None,
+                fail_at: None,
+                high_water_mark: None,
+                descending: false,
+            })),
+            _ => Err(format!("Unknown plugin '{}'", plugin).into()),
+        }
+    }
+
+    fn create_transformer(
+        &self,
+        _plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Transformer>, BoxedError> {
+        match name {
+            Some("failing") => Ok(Box::new(Failing)),
+            Some("fresh") => Ok(Box::new(Fresh)),
+            _ => Ok(Box::new(Suffix {
+                suffix: String::new(),
+            })),
+        }
+    }
+
+    fn create_exporter(
+        &self,
+        _plugin: &str,
+        name: Option<&str>,
+    ) -> Result<Box<dyn Exporter>, BoxedError> {
+        Ok(Box::new(TestExporter {
+            name: name.unwrap_or_default().to_string(),
+            log: self.log.clone(),
+        }))
+    }
+}
+
+fn process(xml: &str) -> Process {
+    serde_xml_rs::from_str(xml).unwrap()
+}
+
+static PROCESS_XML: &str = r#"
+<process id="test-process">
+    <importer plugin="test">
+        <configuration>
+            <config key="count" value="3" />
+        </configuration>
+    </importer>
+    <transformers>
+        <transformer plugin="test" name="suffix">
+            <configuration>
+                <config key="suffix" value="a" />
+            </configuration>
+        </transformer>
+        <transformer plugin="test" name="suffix">
+            <configuration>
+                <config key="suffix" value="b" />
+            </configuration>
+        </transformer>
+    </transformers>
+    <exporters>
+        <exporter plugin="test" name="first" />
+        <exporter plugin="test" name="second" />
+    </exporters>
+</process>"#;
+
+#[test]
+fn test_run() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut pipeline = Pipeline::new(&process(PROCESS_XML), &resolver)?;
+    assert_eq!("test-process", pipeline.process_id());
+
+    let report = pipeline.run()?;
+    assert_eq!(
+        RunReport {
+            process_id: "test-process".to_string(),
+            imported: 3,
+            transformers: vec![
+                StageReport {
+                    stage: "test:suffix".to_string(),
+                    records: 3
+                },
+                StageReport {
+                    stage: "test:suffix".to_string(),
+                    records: 3
+                },
+            ],
+            exporters: vec![
+                StageReport {
+                    stage: "test:first".to_string(),
+                    records: 3
+                },
+                StageReport {
+                    stage: "test:second".to_string(),
+                    records: 3
+                },
+            ],
+            resumed_from: None,
+            high_water_mark: None,
+        },
+        report
+    );
+
+    let log = resolver.log.borrow();
+    assert_eq!(
+        vec![
+            "first:Start",
+            "second:Start",
+            "first:write",
+            "second:write",
+            "first:write",
+            "second:write",
+            "first:write",
+            "second:write",
+            "first:End",
+            "second:End",
+        ],
+        log.events
+    );
+
+    // Every exporter got the records transformed by both transformers
+    assert_eq!(6, log.written.len());
+    for record in &log.written {
+        assert_eq!(
+            Value::String("ab".into()),
+            record.field_by_name("text").unwrap().value()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_run_without_transformers() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="plain">
+            <importer plugin="test">
+                <configuration>
+                    <config key="count" value="2" />
+                </configuration>
+            </importer>
+            <exporters>
+                <exporter plugin="test" name="only" />
+            </exporters>
+        </process>"#;
+    let report = Pipeline::new(&process(xml), &resolver)?.run()?;
+    assert_eq!(2, report.imported);
+    assert!(report.transformers.is_empty());
+    assert_eq!(2, report.exporters[0].records);
+
+    let log = resolver.log.borrow();
+    assert_eq!(
+        Value::USize(1),
+        log.written[1].field_by_name("index").unwrap().value()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_unknown_plugin() {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="unknown">
+            <importer plugin="unknown" />
+            <exporters>
+                <exporter plugin="test" />
+            </exporters>
+        </process>"#;
+    let result = Pipeline::new(&process(xml), &resolver);
+    assert_eq!(
+        "Unknown plugin 'unknown'",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn test_init_error() {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="init">
+            <importer plugin="test">
+                <configuration />
+            </importer>
+            <exporters>
+                <exporter plugin="test" />
+            </exporters>
+        </process>"#;
+    let result = Pipeline::new(&process(xml), &resolver);
+    assert_eq!(
+        "Cannot initialize 'test': Configuration key 'count' missing",
+        result.err().unwrap().to_string()
+    );
+}
+
+#[test]
+fn test_transformer_error() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="failing">
+            <importer plugin="test">
+                <configuration>
+                    <config key="count" value="1" />
+                </configuration>
+            </importer>
+            <transformers>
+                <transformer plugin="test" name="failing" />
+            </transformers>
+            <exporters>
+                <exporter plugin="test" name="only" />
+            </exporters>
+        </process>"#;
+    let mut pipeline = Pipeline::new(&process(xml), &resolver)?;
+    let result = pipeline.run();
+    assert_eq!(
+        "Importer 'test' failed: Transformer 'test:failing' failed: always fails",
+        result.err().unwrap().to_string()
+    );
+
+    // The run stopped before the end signal
+    let log = resolver.log.borrow();
+    assert_eq!(vec!["only:Start"], log.events);
+    assert!(log.written.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_record_meta() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="meta">
+            <importer plugin="test">
+                <configuration>
+                    <config key="count" value="2" />
+                    <config key="source" value="input.csv" />
+                </configuration>
+            </importer>
+            <transformers>
+                <transformer plugin="test" name="fresh" />
+            </transformers>
+            <exporters>
+                <exporter plugin="test" name="only" />
+            </exporters>
+        </process>"#;
+    Pipeline::new(&process(xml), &resolver)?.run()?;
+
+    // The transformer returned records without meta data, so it is kept
+    let log = resolver.log.borrow();
+    let meta: Vec<String> = log.written.iter().map(|r| r.meta().to_string()).collect();
+    assert_eq!(
+        vec![
+            "source 'input.csv', offset 2, sequence 0",
+            "source 'input.csv', offset 3, sequence 1"
+        ],
+        meta
+    );
+    Ok(())
+}
+
+#[test]
+fn test_record_meta_error() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = r#"
+        <process id="failing">
+            <importer plugin="test">
+                <configuration>
+                    <config key="count" value="1" />
+                    <config key="source" value="input.csv" />
+                </configuration>
+            </importer>
+            <transformers>
+                <transformer plugin="test" name="failing" />
+            </transformers>
+            <exporters>
+                <exporter plugin="test" name="only" />
+            </exporters>
+        </process>"#;
+    let result = Pipeline::new(&process(xml), &resolver)?.run();
+    assert_eq!(
+        "Importer 'test' failed: Transformer 'test:failing' failed at source 'input.csv', \
+         offset 2, sequence 0: always fails",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_resume() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = |fail_at: &str| {
+        format!(
+            r#"
+            <process id="resumable">
+                <importer plugin="test">
+                    <configuration>
+                        <config key="count" value="5" />
+                        <config key="source" value="input.csv" />
+                        {}
+                    </configuration>
+                </importer>
+                <exporters>
+                    <exporter plugin="test" name="only" />
+                </exporters>
+            </process>"#,
+            fail_at
+        )
+    };
+    let mut store = MemoryCheckpointStore::new();
+
+    // The first run crashes after the checkpoint of the first two records
+    let result = Pipeline::new(
+        &process(&xml(r#"<config key="fail_at" value="3" />"#)),
+        &resolver,
+    )?
+    .with_checkpoints(Box::new(&mut store), 2)
+    .run();
+    assert_eq!(
+        "Importer 'test' failed: crashed at 3",
+        result.err().unwrap().to_string()
+    );
+    assert_eq!(Some(Checkpoint::new("2")), store.load("resumable")?);
+    assert_eq!(
+        vec![
+            "only:Start",
+            "only:write",
+            "only:write",
+            "only:Checkpoint",
+            "only:write"
+        ],
+        resolver.log.borrow().events
+    );
+
+    // The second run starts after the saved checkpoint
+    resolver.log.borrow_mut().events.clear();
+    let report = Pipeline::new(&process(&xml("")), &resolver)?
+        .with_checkpoints(Box::new(&mut store), 2)
+        .run()?;
+    assert_eq!(Some(Checkpoint::new("2")), report.resumed_from);
+    assert_eq!(3, report.imported);
+    let log = resolver.log.borrow();
+    assert_eq!(
+        vec![
+            "only:Start",
+            "only:write",
+            "only:write",
+            "only:Checkpoint",
+            "only:write",
+            "only:End"
+        ],
+        log.events
+    );
+    let sequences: Vec<Option<u64>> = log.written[3..]
+        .iter()
+        .map(|r| r.meta().sequence())
+        .collect();
+    assert_eq!(vec![Some(2), Some(3), Some(4)], sequences);
+
+    // The complete run cleared the checkpoint
+    assert_eq!(None, store.load("resumable")?);
+    Ok(())
+}
+
+#[test]
+fn test_resume_not_supported() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut store = MemoryCheckpointStore::new();
+    store.save("test-process", &Checkpoint::new("1"))?;
+    let result = Pipeline::new(&process(PROCESS_XML), &resolver)?
+        .with_checkpoints(Box::new(&mut store), 10)
+        .run();
+    assert_eq!(
+        "Importer 'test' failed: The importer does not support checkpoints",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+fn incremental_process(count: usize, fail_at: Option<usize>) -> Process {
+    let fail_at = fail_at
+        .map(|i| format!(r#"<config key="fail_at" value="{}" />"#, i))
+        .unwrap_or_default();
+    process(&format!(
+        r#"
+        <process id="delta">
+            <importer plugin="test">
+                <configuration>
+                    <config key="count" value="{}" />
+                    {}
+                </configuration>
+                <incremental field="index" />
+            </importer>
+            <exporters>
+                <exporter plugin="test" name="only" />
+            </exporters>
+        </process>"#,
+        count, fail_at
+    ))
+}
+
+#[test]
+fn test_incremental() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut store = MemoryCheckpointStore::new();
+
+    // The first run imports everything
+    let report = Pipeline::new_incremental(
+        &incremental_process(3, None),
+        &resolver,
+        Box::new(&mut store),
+    )?
+    .run()?;
+    assert_eq!(3, report.imported);
+    assert_eq!(Some(Value::USize(2)), report.high_water_mark);
+    assert_eq!(Some(Value::USize(2)), incremental::load(&store, "delta")?);
+
+    // The next run only gets the new records
+    let report = Pipeline::new_incremental(
+        &incremental_process(5, None),
+        &resolver,
+        Box::new(&mut store),
+    )?
+    .run()?;
+    assert_eq!(2, report.imported);
+    assert_eq!(Some(Value::USize(4)), report.high_water_mark);
+
+    // Without new records the mark stays
+    let report = Pipeline::new_incremental(
+        &incremental_process(5, None),
+        &resolver,
+        Box::new(&mut store),
+    )?
+    .run()?;
+    assert_eq!(0, report.imported);
+    assert_eq!(Some(Value::USize(4)), incremental::load(&store, "delta")?);
+
+    let log = resolver.log.borrow();
+    let indices: Vec<Value> = log
+        .written
+        .iter()
+        .map(|r| r.get("index").cloned().unwrap())
+        .collect();
+    assert_eq!((0..5).map(Value::USize).collect::<Vec<_>>(), indices);
+    Ok(())
+}
+
+#[test]
+fn test_incremental_failed_run() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut store = MemoryCheckpointStore::new();
+    incremental::save(&mut store, "delta", &Value::USize(1))?;
+
+    // A failed run keeps the old mark
+    let result = Pipeline::new_incremental(
+        &incremental_process(5, Some(4)),
+        &resolver,
+        Box::new(&mut store),
+    )?
+    .run();
+    assert!(result.is_err());
+    assert_eq!(Some(Value::USize(1)), incremental::load(&store, "delta")?);
+
+    // Without a store every run imports everything
+    let report = Pipeline::new(&incremental_process(3, None), &resolver)?.run()?;
+    assert_eq!(3, report.imported);
+    assert_eq!(Some(Value::USize(2)), report.high_water_mark);
+
+    let result = Pipeline::new_incremental(&process(PROCESS_XML), &resolver, Box::new(&mut store));
+    assert_eq!(
+        "The importer of process 'test-process' has no incremental element",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_incremental_resume() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let xml = |fail_at: &str| {
+        format!(
+            r#"
+            <process id="delta">
+                <importer plugin="test">
+                    <configuration>
+                        <config key="count" value="5" />
+                        <config key="source" value="input.csv" />
+                        <config key="descending" value="true" />
+                        {}
+                    </configuration>
+                    <incremental field="updated" />
+                </importer>
+                <exporters>
+                    <exporter plugin="test" name="only" />
+                </exporters>
+            </process>"#,
+            fail_at
+        )
+    };
+    let mut marks = MemoryCheckpointStore::new();
+    let mut checkpoints = MemoryCheckpointStore::new();
+
+    // The first run exports the records with the largest values and crashes
+    let result = Pipeline::new_incremental(
+        &process(&xml(r#"<config key="fail_at" value="3" />"#)),
+        &resolver,
+        Box::new(&mut marks),
+    )?
+    .with_checkpoints(Box::new(&mut checkpoints), 2)
+    .run();
+    assert!(result.is_err());
+    assert_eq!(None, incremental::load(&marks, "delta")?);
+    assert_eq!(
+        Some(Value::USize(5)),
+        incremental::load_running(&checkpoints, "delta")?
+    );
+
+    // The resumed run only sees smaller values, but keeps the mark of the
+    // records exported before the crash
+    let report = Pipeline::new_incremental(&process(&xml("")), &resolver, Box::new(&mut marks))?
+        .with_checkpoints(Box::new(&mut checkpoints), 2)
+        .run()?;
+    assert_eq!(Some(Checkpoint::new("2")), report.resumed_from);
+    assert_eq!(3, report.imported);
+    assert_eq!(Some(Value::USize(5)), report.high_water_mark);
+    assert_eq!(Some(Value::USize(5)), incremental::load(&marks, "delta")?);
+    assert_eq!(None, incremental::load_running(&checkpoints, "delta")?);
+    assert_eq!(None, checkpoints.load("delta")?);
+    Ok(())
+}
+
+#[test]
+fn test_commit_without_checkpoints() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut pipeline = Pipeline::new(
+        &process(
+            r#"
+            <process id="plain">
+                <importer plugin="test" />
+                <exporters>
+                    <exporter plugin="test" name="only" />
+                </exporters>
+            </process>"#,
+        ),
+        &resolver,
+    )?
+    .with_checkpoints(Box::new(MemoryCheckpointStore::new()), 2);
+
+    // Records without a checkpoint do not let the counter grow
+    let mut handler = PipelineHandler {
+        imported: 0,
+        transformed: Vec::new(),
+        exported: vec![0],
+        transformers: &pipeline.transformers,
+        exporters: &mut pipeline.exporters,
+        process_id: &pipeline.process_id,
+        checkpoints: pipeline.checkpoints.as_mut(),
+        pending: None,
+        uncommitted: 0,
+        high_water_mark: None,
+    };
+    for _ in 0..5 {
+        assert!(handler.handle_record(&mut Record::new())?.is_continue());
+    }
+    assert_eq!(1, handler.uncommitted);
+    assert_eq!(5, handler.imported);
+    assert!(
+        !resolver
+            .log
+            .borrow()
+            .events
+            .contains(&"only:Checkpoint".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_incremental_run_twice() -> Result<(), BoxedError> {
+    let resolver = TestResolver {
+        log: Rc::new(RefCell::new(Log::default())),
+    };
+    let mut store = MemoryCheckpointStore::new();
+    let mut pipeline = Pipeline::new_incremental(
+        &incremental_process(3, None),
+        &resolver,
+        Box::new(&mut store),
+    )?;
+    assert_eq!(3, pipeline.run()?.imported);
+
+    // The second run starts from the advanced mark
+    let report = pipeline.run()?;
+    assert_eq!(0, report.imported);
+    assert_eq!(Some(Value::USize(2)), report.high_water_mark);
+    drop(pipeline);
+    assert_eq!(Some(Value::USize(2)), incremental::load(&store, "delta")?);
+    assert_eq!(3, resolver.log.borrow().written.len());
+    Ok(())
+}
@@ -1,17 +1,106 @@
 //! Module for the Record
 //!
-use crate::{field::Field, value::Value};
+//! Besides the [Vec] of [Field]s, a [Record] offers a map-like API (`get`,
+//! `get_mut`, `set`, `remove`, `rename`, `contains`, `insert_at` and `iter`).
+//! The order of the fields is always kept. Wide records build a name index on
+//! the first lookup, so that lookups don't scan all fields.
+//!
+//! Values in nested records and collections are addressed with a
+//! [path::Path], e.g. `items[2].price`. [Record::flatten] turns them into
+//! top-level fields for exporters with flat columns. [Record::diff],
+//! [Record::patch] and [Record::merge] compare and combine records.
+//!
+//! The [meta::RecordMeta] of a record tells where it came from.
+//!
+//! # Copy on write
+//! Cloning a record (or [Record::copy]) does not copy the fields. The clones
+//! share them until one of the clones is changed, then only this clone copies
+//! the fields. The values themselves share large strings and blobs (see
+//! [crate::value::shared]), so even this copy does not duplicate their data.
+//! Transformers, that copy the incoming record and change a few fields, stay
+//! cheap with large records.
+//!
+//! # Duplicate field names
+//! [Record::fields_as_mut] allows to add fields with the same name. A record
+//! with duplicate names is valid, but the map-like API only sees the first
+//! field with a name: `get`, `set` and `remove` act on it, and
+//! `field_by_name` returns it. The map-like API itself never creates
+//! duplicates: `insert_at` replaces an existing field and `rename` refuses to
+//! rename to the name of another field.
+//!
+//! # Example
+//! ```
+//! use model::{record::Record, value::Value};
+//!
+//! let mut record = Record::new();
+//! record.set("id", Value::I32(1));
+//! record.set("name", "John".into());
+//! assert_eq!(Some(Value::I32(1)), record.set("id", Value::I32(2)));
+//! assert!(record.rename("name", "first_name"));
+//! record.insert_at(0, "version", Value::U8(1));
+//!
+//! let names: Vec<&str> = record.iter().map(|(name, _)| name).collect();
+//! assert_eq!(vec!["version", "id", "first_name"], names);
+//! assert_eq!(Some(&Value::I32(2)), record.get("id"));
+//! ```
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{Arc, OnceLock},
+};
+
+use crate::{
+    field::Field,
+    value::{Value, json::options::JsonConversionOptions},
+};
 use serde_json::Value as JsonValue;
 
+pub mod batch;
+pub mod diff;
+pub mod flatten;
+pub mod merge;
+pub mod meta;
+pub mod path;
+
+use meta::RecordMeta;
+
+/// Records with at least this number of fields use a name index for lookups
+const INDEX_THRESHOLD: usize = 16;
+
+/// The meta data of records without any
+static EMPTY_META: RecordMeta = RecordMeta::new();
+
 /// A record struct, that is used to transfer data from the import data source
 /// to the export data sink
 ///
+/// # Note
+/// The lazily built name index is interior mutable, but it is not part of
+/// [PartialEq] or of the hash of [crate::value::ordering::Ordered]. It is
+/// safe to use records, [Value]s and [crate::value::ordering::Ordered] as
+/// keys of a [HashMap] or [std::collections::BTreeMap]. Clippy's
+/// `mutable_key_type` lint can not see that and warns about such maps. Add
+/// `ignore-interior-mutability = ["model::record::Record"]` to the
+/// `clippy.toml` of the using crate or allow the lint where the map is used.
+///
 /// # Members
-/// * `fields` - a [Vec] of [Field]
+/// * `data` - The fields, shared between clones until one of them is changed
+/// * `meta` - The [RecordMeta], only allocated if there is any
 ///
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub struct Record {
+    data: Arc<RecordData>,
+    meta: Option<Box<RecordMeta>>,
+}
+
+/// The shared part of a [Record]
+///
+/// # Members
+/// * `fields` - a [Vec] of [Field]
+/// * `index` - The lazily built position of the first field for each name
+#[derive(Clone, Default)]
+struct RecordData {
     fields: Vec<Field>,
+    index: OnceLock<HashMap<String, usize>>,
 }
 
 impl Record {
@@ -26,10 +115,30 @@ impl Record {
     /// }
     /// ```
     pub fn new() -> Self {
-        Self { fields: vec![] }
+        Self {
+            data: Arc::default(),
+            meta: None,
+        }
     }
 
-    /// Creates a new [Record] that is a copy of the given `other`
+    /// Returns the record with the given [RecordMeta]
+    pub fn with_meta(mut self, meta: RecordMeta) -> Self {
+        self.meta = (!meta.is_empty()).then(|| Box::new(meta));
+        self
+    }
+
+    /// Returns the [RecordMeta] of the record
+    pub fn meta(&self) -> &RecordMeta {
+        self.meta.as_deref().unwrap_or(&EMPTY_META)
+    }
+
+    /// Returns a mutable reference to the [RecordMeta] of the record
+    pub fn meta_mut(&mut self) -> &mut RecordMeta {
+        self.meta.get_or_insert_default()
+    }
+
+    /// Creates a new [Record] that is a copy of the given `other`. The fields
+    /// are shared until one of the records is changed
     /// # Arguments
     /// * `other` - A [Record] to be copied from
     /// # Example
@@ -47,37 +156,177 @@ impl Record {
     /// ```
     ///
     pub fn copy(other: &Record) -> Self {
-        Self {
-            fields: other.fields.clone(),
-        }
+        other.clone()
+    }
+
+    /// Returns true, if both records share their fields, i.e. one is an
+    /// unchanged copy of the other
+    pub fn ptr_eq(a: &Record, b: &Record) -> bool {
+        Arc::ptr_eq(&a.data, &b.data)
     }
 
     /// Returns a reference to the fields
     pub fn fields(&self) -> &Vec<Field> {
-        &self.fields
+        &self.data.fields
     }
 
-    /// Returns a mutable reference to the fields
+    /// Returns a mutable reference to the fields. Shared fields are copied
+    /// before
     pub fn fields_as_mut(&mut self) -> &mut Vec<Field> {
-        &mut self.fields
+        self.invalidate_index();
+        &mut self.data_mut().fields
     }
 
     /// Returns a field by name. If the field cannot be found, a [None] is returned
     pub fn field_by_name(&self, name: &str) -> Option<&Field> {
-        self.fields.iter().find(|field| field.name() == name)
+        self.position(name).map(|index| &self.data.fields[index])
+    }
+
+    /// Returns the position of the first field with the given `name`
+    pub fn position(&self, name: &str) -> Option<usize> {
+        let fields = &self.data.fields;
+        if fields.len() < INDEX_THRESHOLD {
+            return fields.iter().position(|field| field.name() == name);
+        }
+        self.data
+            .index
+            .get_or_init(|| {
+                let mut index = HashMap::with_capacity(fields.len());
+                for (position, field) in fields.iter().enumerate() {
+                    index.entry(field.name().to_string()).or_insert(position);
+                }
+                index
+            })
+            .get(name)
+            .copied()
+    }
+
+    /// Returns true, if the record has a field with the given `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.position(name).is_some()
+    }
+
+    /// Returns the value of the field with the given `name`
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.field_by_name(name).map(Field::value_as_ref)
+    }
+
+    /// Returns the value of the field with the given `name` as a mutable
+    /// reference. Shared fields are copied before
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Value> {
+        self.position(name)
+            .map(|index| self.data_mut().fields[index].value_as_mut())
+    }
+
+    /// Sets the value of the field with the given `name`. A new field is
+    /// appended, if there is none with this name
+    ///
+    /// Returns the previous value, if the field existed
+    pub fn set(&mut self, name: &str, value: Value) -> Option<Value> {
+        if let Some(previous) = self.get_mut(name) {
+            return Some(std::mem::replace(previous, value));
+        }
+        let data = self.data_mut();
+        if let Some(index) = data.index.get_mut() {
+            index.insert(name.to_string(), data.fields.len());
+        }
+        data.fields.push(Field::new_value(name, value));
+        None
+    }
+
+    /// Inserts a field at the given position. An existing field with the same
+    /// `name` is removed before
+    ///
+    /// Returns the value of the removed field
+    ///
+    /// # Panics
+    /// If `index` is greater than the number of fields after the removal
+    pub fn insert_at(&mut self, index: usize, name: &str, value: Value) -> Option<Value> {
+        let previous = self.remove(name);
+        self.invalidate_index();
+        self.data_mut()
+            .fields
+            .insert(index, Field::new_value(name, value));
+        previous
+    }
+
+    /// Removes the field with the given `name` and returns its value
+    pub fn remove(&mut self, name: &str) -> Option<Value> {
+        let index = self.position(name)?;
+        self.invalidate_index();
+        Some(self.data_mut().fields.remove(index).into_value())
+    }
+
+    /// Renames the field `from` to `to` at its position. The metadata of the
+    /// field is kept
+    ///
+    /// Returns false and leaves the record unchanged, if there is no field
+    /// `from` or there is already another field `to`
+    pub fn rename(&mut self, from: &str, to: &str) -> bool {
+        if from == to {
+            return self.contains(from);
+        }
+        let Some(index) = self.position(from) else {
+            return false;
+        };
+        if self.contains(to) {
+            return false;
+        }
+        let field = &mut self.data_mut().fields[index];
+        let value = std::mem::replace(field.value_as_mut(), Value::None);
+        let metadata = std::mem::take(field.metadata_mut());
+        *field = Field::new_value(to, value).with_metadata(metadata);
+        self.invalidate_index();
+        true
+    }
+
+    /// Returns an iterator over the names and values of the fields in their
+    /// order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+        self.data
+            .fields
+            .iter()
+            .map(|field| (field.name(), field.value_as_ref()))
+    }
+
+    /// Returns the fields for a change, copies them if they are shared
+    fn data_mut(&mut self) -> &mut RecordData {
+        Arc::make_mut(&mut self.data)
+    }
+
+    fn invalidate_index(&mut self) {
+        if self.data.index.get().is_some() {
+            self.data_mut().index.take();
+        }
+    }
+}
+
+impl Default for Record {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The name index and the meta data are not part of the equality
+impl PartialEq for Record {
+    fn eq(&self, other: &Self) -> bool {
+        self.data.fields == other.data.fields
+    }
+}
+
+/// Shows only the fields
+impl Debug for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Record")
+            .field("fields", &self.data.fields)
+            .finish()
     }
 }
 
 impl From<JsonValue> for Record {
+    /// Converts with the [JsonConversionOptions::heuristic] options
     fn from(value: JsonValue) -> Self {
-        let mut record = Record::new();
-        if let JsonValue::Object(map) = value {
-            let fields = record.fields_as_mut();
-            for (key, json_value) in map {
-                fields.push(Field::new_value(&key, Value::from(json_value)));
-            }
-        }
-        record
+        Record::from_json(value, &JsonConversionOptions::heuristic())
     }
 }
 
@@ -0,0 +1,440 @@
+//! A column-oriented batch of records
+//!
+//! A [RecordBatch] stores the values of many records column by column. Every
+//! [Column] keeps its values in a typed [Vec] (e.g. a `Vec<i64>` for
+//! [Value::I64]) and marks missing values and [Value::None] in a [Bitmap].
+//! Batch-aware components (see [crate::import::Importer::read_batch],
+//! [crate::transform::Transformer::process_batch] and
+//! [crate::export::Exporter::write_batch]) can work on whole columns instead
+//! of calling through the traits for every single record.
+//!
+//! The columns are ordered by the first appearance of their field name.
+//! A column, that holds values of different variants or nested values
+//! (collections, records and JSON documents), falls back to
+//! [ColumnData::Mixed].
+//!
+//! Converting back to records gives every record all columns, so a field,
+//! that is missing in a record, comes back as [Value::None]. Only the first
+//! field with a name is kept and the field [crate::field::metadata] is not
+//! stored. The [super::meta::RecordMeta] of each record is kept.
+//!
+//! # Example
+//! ```
+//! use model::record::{Record, batch::{ColumnData, RecordBatch}};
+//! use serde_json::json;
+//!
+//! let records = vec![
+//!     Record::from(json!({"id": 1, "name": "John"})),
+//!     Record::from(json!({"id": 2})),
+//! ];
+//! let batch = RecordBatch::from_records(&records);
+//!
+//! let ids = batch.column("id").unwrap();
+//! assert_eq!(&ColumnData::U8(vec![1, 2]), ids.data());
+//! assert_eq!(1, batch.column("name").unwrap().null_count());
+//!
+//! let back = batch.to_records();
+//! assert_eq!(records[0], back[0]);
+//! assert_eq!(Some(&model::value::Value::None), back[1].get("name"));
+//! ```
+use std::collections::HashMap;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::{
+    checkpoint::Checkpoint,
+    field::Field,
+    value::{
+        Value,
+        shared::{SharedBytes, SharedString},
+        value_type::ValueType,
+    },
+};
+
+use super::{Record, meta::RecordMeta};
+
+/// A growable sequence of bits, e.g. the validity of the values of a [Column]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bitmap {
+    bits: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    /// Creates an empty [Bitmap]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true, if there are no bits
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends a bit
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.bits.push(0);
+        }
+        if bit {
+            self.bits[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    /// Returns the bit at `index`, false if it is out of range
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Returns the number of set bits
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+}
+
+/// Defines [ColumnData] with one typed variant for every scalar [Value]
+/// variant
+macro_rules! column_data {
+    ($($variant:ident($ty:ty)),* $(,)?) => {
+        /// The values of a [Column]
+        ///
+        /// Null values are stored as the default of the type, the validity
+        /// [Bitmap] of the column tells them apart.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum ColumnData {
+            /// A column, that has only null values so far
+            Null,
+            $(
+                #[doc = concat!("The values of [Value::", stringify!($variant), "]")]
+                $variant(Vec<$ty>),
+            )*
+            /// Values of different variants or nested values
+            Mixed(Vec<Value>),
+        }
+
+        impl ColumnData {
+            /// Returns the type of the values, [None] for [ColumnData::Null]
+            /// and [ColumnData::Mixed]
+            pub fn value_type(&self) -> Option<ValueType> {
+                match self {
+                    $(ColumnData::$variant(_) => Some(ValueType::$variant),)*
+                    ColumnData::Null | ColumnData::Mixed(_) => None,
+                }
+            }
+
+            /// Creates the data for `nulls` null values followed by `value`
+            fn with_value(nulls: usize, value: Value) -> Self {
+                match value {
+                    $(Value::$variant(v) => {
+                        let mut values = vec![<$ty>::default(); nulls];
+                        values.push(v);
+                        ColumnData::$variant(values)
+                    })*
+                    value => {
+                        let mut values = vec![Value::None; nulls];
+                        values.push(value);
+                        ColumnData::Mixed(values)
+                    }
+                }
+            }
+
+            /// Appends a value. A value of another variant is returned
+            fn push(&mut self, value: Value) -> Result<(), Value> {
+                match (self, value) {
+                    $((ColumnData::$variant(values), Value::$variant(v)) => values.push(v),)*
+                    (ColumnData::Mixed(values), value) => values.push(value),
+                    (_, value) => return Err(value),
+                }
+                Ok(())
+            }
+
+            /// Appends a null value
+            fn push_null(&mut self) {
+                match self {
+                    ColumnData::Null => {}
+                    $(ColumnData::$variant(values) => values.push(<$ty>::default()),)*
+                    ColumnData::Mixed(values) => values.push(Value::None),
+                }
+            }
+
+            /// Returns the value at `row`, which must be in range
+            fn value(&self, row: usize) -> Value {
+                match self {
+                    ColumnData::Null => Value::None,
+                    $(ColumnData::$variant(values) => Value::$variant(values[row].clone()),)*
+                    ColumnData::Mixed(values) => values[row].clone(),
+                }
+            }
+        }
+    };
+}
+
+column_data!(
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    ISize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    USize(usize),
+    F32(f32),
+    F64(f64),
+    Decimal(Decimal),
+    String(SharedString),
+    Blob(SharedBytes),
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+    DateTimeTz(DateTime<FixedOffset>),
+    Time(NaiveTime),
+    Duration(TimeDelta),
+    Uuid(Uuid),
+);
+
+/// The values of one field name in a [RecordBatch]
+///
+/// # Members
+/// * `name` - The name of the field
+/// * `data` - The typed values
+/// * `validity` - A set bit for every value, that is not null
+#[derive(Debug, Clone, PartialEq)]
+pub struct Column {
+    name: String,
+    data: ColumnData,
+    validity: Bitmap,
+}
+
+impl Column {
+    /// Creates a column with `nulls` null values
+    fn new(name: &str, nulls: usize) -> Self {
+        let mut validity = Bitmap::new();
+        (0..nulls).for_each(|_| validity.push(false));
+        Self {
+            name: name.to_string(),
+            data: ColumnData::Null,
+            validity,
+        }
+    }
+
+    /// Returns the name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the typed values
+    pub fn data(&self) -> &ColumnData {
+        &self.data
+    }
+
+    /// Returns the validity [Bitmap], a set bit for every value, that is not
+    /// null
+    pub fn validity(&self) -> &Bitmap {
+        &self.validity
+    }
+
+    /// Returns the number of values
+    pub fn len(&self) -> usize {
+        self.validity.len()
+    }
+
+    /// Returns true, if there are no values
+    pub fn is_empty(&self) -> bool {
+        self.validity.is_empty()
+    }
+
+    /// Returns the number of null values
+    pub fn null_count(&self) -> usize {
+        self.len() - self.validity.count_ones()
+    }
+
+    /// Returns true, if the value at `row` is null or out of range
+    pub fn is_null(&self, row: usize) -> bool {
+        !self.validity.get(row)
+    }
+
+    /// Returns the value at `row`, [Value::None] for null values
+    pub fn get(&self, row: usize) -> Option<Value> {
+        match row < self.len() {
+            true if self.is_null(row) => Some(Value::None),
+            true => Some(self.data.value(row)),
+            false => None,
+        }
+    }
+
+    /// Returns an iterator over the values, [Value::None] for null values
+    pub fn iter(&self) -> impl Iterator<Item = Value> + '_ {
+        (0..self.len()).filter_map(|row| self.get(row))
+    }
+
+    /// Appends a value. A value of another variant than the previous ones
+    /// turns the data into [ColumnData::Mixed]
+    fn push(&mut self, value: Value) {
+        let row = self.len();
+        if value.is_none() {
+            self.validity.push(false);
+            self.data.push_null();
+            return;
+        }
+        if matches!(self.data, ColumnData::Null) {
+            self.data = ColumnData::with_value(row, value);
+        } else if let Err(value) = self.data.push(value) {
+            let mut values: Vec<Value> = self.iter().collect();
+            values.push(value);
+            self.data = ColumnData::Mixed(values);
+        }
+        self.validity.push(true);
+    }
+}
+
+/// Records stored column by column
+///
+/// # Members
+/// * `columns` - The columns in the order of the first appearance of their
+///   name
+/// * `positions` - The position of each column by name
+/// * `meta` - The [RecordMeta] of each record
+#[derive(Debug, Clone, Default)]
+pub struct RecordBatch {
+    columns: Vec<Column>,
+    positions: HashMap<String, usize>,
+    meta: Vec<RecordMeta>,
+}
+
+impl RecordBatch {
+    /// Creates an empty [RecordBatch]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [RecordBatch] with the values of `records`
+    pub fn from_records(records: &[Record]) -> Self {
+        let mut batch = Self::new();
+        records.iter().for_each(|record| batch.push(record));
+        batch
+    }
+
+    /// Returns the number of records
+    pub fn len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Returns true, if there are no records
+    pub fn is_empty(&self) -> bool {
+        self.meta.is_empty()
+    }
+
+    /// Returns the columns
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Returns the column with the given `name`
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.positions.get(name).map(|&index| &self.columns[index])
+    }
+
+    /// Returns the [RecordMeta] of the record at `row`
+    pub fn meta(&self, row: usize) -> Option<&RecordMeta> {
+        self.meta.get(row)
+    }
+
+    /// Returns the [Checkpoint] after the batch, that is the checkpoint of
+    /// its last record
+    pub fn checkpoint(&self) -> Option<&Checkpoint> {
+        self.meta.last().and_then(RecordMeta::checkpoint)
+    }
+
+    /// Appends the values of `record`. Columns, that the record has no field
+    /// for, get a null value
+    pub fn push(&mut self, record: &Record) {
+        let row = self.len();
+        for (name, value) in record.iter() {
+            let index = match self.positions.get(name) {
+                Some(&index) => index,
+                None => {
+                    self.positions.insert(name.to_string(), self.columns.len());
+                    self.columns.push(Column::new(name, row));
+                    self.columns.len() - 1
+                }
+            };
+            let column = &mut self.columns[index];
+            // Only the first field with a name is stored
+            if column.len() == row {
+                column.push(value.clone());
+            }
+        }
+        for column in self.columns.iter_mut().filter(|c| c.len() == row) {
+            column.push(Value::None);
+        }
+        self.meta.push(record.meta().clone());
+    }
+
+    /// Returns the record at `row` with a field for every column
+    pub fn record(&self, row: usize) -> Option<Record> {
+        let meta = self.meta.get(row)?;
+        let mut record = Record::new().with_meta(meta.clone());
+        let fields = record.fields_as_mut();
+        fields.reserve(self.columns.len());
+        for column in &self.columns {
+            fields.push(Field::new_value(column.name(), column.get(row)?));
+        }
+        Some(record)
+    }
+
+    /// Returns an iterator over the records
+    pub fn records(&self) -> impl Iterator<Item = Record> + '_ {
+        (0..self.len()).filter_map(|row| self.record(row))
+    }
+
+    /// Converts the batch back into records
+    pub fn to_records(&self) -> Vec<Record> {
+        self.records().collect()
+    }
+}
+
+/// The meta data is not part of the equality, like for [Record]
+impl PartialEq for RecordBatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.columns == other.columns
+    }
+}
+
+impl From<Vec<Record>> for RecordBatch {
+    fn from(records: Vec<Record>) -> Self {
+        Self::from_records(&records)
+    }
+}
+
+impl From<RecordBatch> for Vec<Record> {
+    fn from(batch: RecordBatch) -> Self {
+        batch.to_records()
+    }
+}
+
+impl FromIterator<Record> for RecordBatch {
+    fn from_iter<T: IntoIterator<Item = Record>>(iter: T) -> Self {
+        let mut batch = Self::new();
+        iter.into_iter().for_each(|record| batch.push(&record));
+        batch
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,154 @@
+use serde_json::json;
+
+use crate::{
+    checkpoint::Checkpoint,
+    record::{Record, meta::RecordMeta},
+    value::{Value, value_type::ValueType},
+};
+
+use super::{Bitmap, ColumnData, RecordBatch};
+
+fn records() -> Vec<Record> {
+    vec![
+        Record::from(json!({"id": 1, "name": "John", "active": true})),
+        Record::from(json!({"id": 2, "name": null, "active": false})),
+        Record::from(json!({"id": 3, "name": "Jane", "active": true})),
+    ]
+}
+
+#[test]
+fn test_bitmap() {
+    let mut bitmap = Bitmap::new();
+    assert!(bitmap.is_empty());
+    for i in 0..130 {
+        bitmap.push(i % 3 == 0);
+    }
+    assert_eq!(130, bitmap.len());
+    assert_eq!(44, bitmap.count_ones());
+    assert!(bitmap.get(0));
+    assert!(!bitmap.get(64));
+    assert!(bitmap.get(129));
+    assert!(!bitmap.get(130));
+}
+
+#[test]
+fn test_typed_columns() {
+    let batch = RecordBatch::from_records(&records());
+    assert_eq!(3, batch.len());
+    let names: Vec<&str> = batch.columns().iter().map(|c| c.name()).collect();
+    assert_eq!(vec!["active", "id", "name"], names);
+
+    let id = batch.column("id").unwrap();
+    assert_eq!(&ColumnData::U8(vec![1, 2, 3]), id.data());
+    assert_eq!(Some(ValueType::U8), id.data().value_type());
+    assert_eq!(0, id.null_count());
+
+    let name = batch.column("name").unwrap();
+    assert_eq!(Some(ValueType::String), name.data().value_type());
+    assert_eq!(1, name.null_count());
+    assert!(name.is_null(1));
+    assert_eq!(Some(Value::None), name.get(1));
+    assert_eq!(Some(Value::from("Jane")), name.get(2));
+    assert_eq!(None, name.get(3));
+
+    assert_eq!(
+        &ColumnData::Bool(vec![true, false, true]),
+        batch.column("active").unwrap().data()
+    );
+    assert!(batch.column("unknown").is_none());
+}
+
+#[test]
+fn test_round_trip() {
+    let records = records();
+    let batch = RecordBatch::from(records.clone());
+    assert_eq!(records, Vec::from(batch.clone()));
+    assert_eq!(batch, records.into_iter().collect());
+}
+
+#[test]
+fn test_missing_fields() {
+    let mut first = Record::new();
+    first.set("a", Value::I32(1));
+    let mut second = Record::new();
+    second.set("b", Value::I32(2));
+    let batch = RecordBatch::from_records(&[first, second]);
+
+    let a = batch.column("a").unwrap();
+    assert_eq!(
+        vec![Value::I32(1), Value::None],
+        a.iter().collect::<Vec<_>>()
+    );
+    // A column, that appears later, starts with null values
+    let b = batch.column("b").unwrap();
+    assert_eq!(&ColumnData::I32(vec![0, 2]), b.data());
+    assert_eq!(
+        vec![Value::None, Value::I32(2)],
+        b.iter().collect::<Vec<_>>()
+    );
+
+    // Every record gets all columns
+    let record = batch.record(0).unwrap();
+    assert_eq!(Some(&Value::None), record.get("b"));
+    assert!(batch.record(2).is_none());
+}
+
+#[test]
+fn test_null_and_mixed_columns() {
+    let values = [
+        Value::None,
+        Value::I32(1),
+        Value::from("x"),
+        Value::None,
+        Value::Collection(vec![Value::U8(1)]),
+    ];
+    let batch: RecordBatch = values
+        .iter()
+        .map(|value| {
+            let mut record = Record::new();
+            record.set("value", value.clone());
+            record
+        })
+        .collect();
+
+    let column = batch.column("value").unwrap();
+    assert_eq!(
+        &ColumnData::Mixed(values.to_vec()),
+        column.data(),
+        "Different variants fall back to mixed values"
+    );
+    assert_eq!(None, column.data().value_type());
+    assert_eq!(2, column.null_count());
+    assert_eq!(values.to_vec(), column.iter().collect::<Vec<_>>());
+
+    let mut record = Record::new();
+    record.set("empty", Value::None);
+    let batch = RecordBatch::from_records(&[record.clone(), record]);
+    assert_eq!(&ColumnData::Null, batch.column("empty").unwrap().data());
+    assert_eq!(2, batch.column("empty").unwrap().null_count());
+}
+
+#[test]
+fn test_duplicate_names_and_meta() {
+    let mut record = Record::new().with_meta(RecordMeta::new().with_source("a.csv"));
+    crate::field::add_field(record.fields_as_mut(), "a", Value::I32(1));
+    crate::field::add_field(record.fields_as_mut(), "a", Value::I32(2));
+    let batch = RecordBatch::from_records(&[record]);
+
+    assert_eq!(&ColumnData::I32(vec![1]), batch.column("a").unwrap().data());
+    assert_eq!(Some("a.csv"), batch.meta(0).unwrap().source());
+    let back = batch.record(0).unwrap();
+    assert_eq!(1, back.fields().len());
+    assert_eq!(Some("a.csv"), back.meta().source());
+}
+
+#[test]
+fn test_checkpoint() {
+    let mut batch = RecordBatch::new();
+    assert!(batch.checkpoint().is_none());
+    for token in ["1", "2"] {
+        let meta = RecordMeta::new().with_checkpoint(Checkpoint::new(token));
+        batch.push(&Record::new().with_meta(meta));
+    }
+    assert_eq!(Some(&Checkpoint::new("2")), batch.checkpoint());
+}
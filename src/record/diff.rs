@@ -0,0 +1,286 @@
+//! Differences between records and patches to apply them
+//!
+//! [Record::diff] compares two records and returns a [Patch] with the
+//! [Change]s, that turn the first record into the second one. It recurses
+//! into nested records and collections, so a change of `items[1].price` is
+//! reported as such and not as a change of the whole collection.
+//!
+//! A [Patch] can be applied to a record with [Record::patch] and serialized
+//! with serde. Each change is written with an `op` (`add`, `remove` or
+//! `replace`), the [Path] as string with escaped field names and the values
+//! in the self-describing representation of [crate::serialization::Tagged].
+//!
+//! # Example
+//! ```
+//! use model::{record::Record, value::Value};
+//! use serde_json::json;
+//!
+//! let old = Record::from(json!({"id": 1, "name": "John", "tags": ["a"]}));
+//! let new = Record::from(json!({"id": 1, "name": "Jane", "tags": ["a", "b"]}));
+//!
+//! let patch = old.diff(&new);
+//! assert_eq!(2, patch.changes().len());
+//! assert_eq!("replace name: John -> Jane", patch.changes()[0].to_string());
+//! assert_eq!("add tags[1]: b", patch.changes()[1].to_string());
+//!
+//! let json = serde_json::to_string(&patch).unwrap();
+//! let patch: model::record::diff::Patch = serde_json::from_str(&json).unwrap();
+//! let mut record = old.clone();
+//! record.patch(&patch).unwrap();
+//! assert_eq!(new, record);
+//! ```
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{serialization::Tagged, value::Value};
+
+use super::{
+    Record,
+    path::{Path, PathError, Segment},
+};
+
+/// A single change of a [Patch]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ChangeRepr", into = "ChangeRepr")]
+pub enum Change {
+    /// A field or collection item was added
+    Add { path: Path, value: Value },
+    /// A field or collection item was removed
+    Remove { path: Path, value: Value },
+    /// The value of a field or collection item was replaced
+    Replace { path: Path, old: Value, new: Value },
+}
+
+impl Change {
+    /// Returns the path of the changed value
+    pub fn path(&self) -> &Path {
+        match self {
+            Change::Add { path, .. }
+            | Change::Remove { path, .. }
+            | Change::Replace { path, .. } => path,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Add { path, value } => write!(f, "add {}: {}", path, value),
+            Change::Remove { path, value } => write!(f, "remove {}: {}", path, value),
+            Change::Replace { path, old, new } => write!(f, "replace {}: {} -> {}", path, old, new),
+        }
+    }
+}
+
+/// The serialized form of a [Change]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum ChangeRepr {
+    Add {
+        path: Path,
+        value: Tagged<Value>,
+    },
+    Remove {
+        path: Path,
+        value: Tagged<Value>,
+    },
+    Replace {
+        path: Path,
+        old: Tagged<Value>,
+        new: Tagged<Value>,
+    },
+}
+
+impl From<ChangeRepr> for Change {
+    fn from(repr: ChangeRepr) -> Self {
+        match repr {
+            ChangeRepr::Add { path, value } => Change::Add {
+                path,
+                value: value.0,
+            },
+            ChangeRepr::Remove { path, value } => Change::Remove {
+                path,
+                value: value.0,
+            },
+            ChangeRepr::Replace { path, old, new } => Change::Replace {
+                path,
+                old: old.0,
+                new: new.0,
+            },
+        }
+    }
+}
+
+impl From<Change> for ChangeRepr {
+    fn from(change: Change) -> Self {
+        match change {
+            Change::Add { path, value } => ChangeRepr::Add {
+                path,
+                value: Tagged(value),
+            },
+            Change::Remove { path, value } => ChangeRepr::Remove {
+                path,
+                value: Tagged(value),
+            },
+            Change::Replace { path, old, new } => ChangeRepr::Replace {
+                path,
+                old: Tagged(old),
+                new: Tagged(new),
+            },
+        }
+    }
+}
+
+/// An ordered list of [Change]s created by [Record::diff]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Patch {
+    changes: Vec<Change>,
+}
+
+impl Patch {
+    /// Creates a patch from a list of changes
+    pub fn new(changes: Vec<Change>) -> Self {
+        Self { changes }
+    }
+
+    /// Returns the changes in the order they are applied
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Returns true, if the patch has no changes
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Record {
+    /// Returns the [Patch], that turns this record into `other`
+    ///
+    /// Fields are compared by name, collection items by position. Values of
+    /// different variants are replaced, e.g. `U8(1)` by `I32(1)`. For
+    /// duplicate field names only the first field is compared
+    ///
+    /// # Arguments
+    /// * `other` - The new state of the record
+    pub fn diff(&self, other: &Record) -> Patch {
+        let mut changes = Vec::new();
+        diff_records(None, self, other, &mut changes);
+        Patch { changes }
+    }
+
+    /// Applies the changes of a [Patch] in their order
+    ///
+    /// An added field is appended, an added collection item is inserted at
+    /// its index.
+    ///
+    /// # Errors
+    /// If a removed or replaced value does not exist or the parent of an
+    /// added value does not exist. The changes before the error are kept
+    pub fn patch(&mut self, patch: &Patch) -> Result<(), PathError> {
+        for change in patch.changes() {
+            apply(self, change)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the path of the field `name` in the record at `parent`
+fn field_path(parent: Option<&Path>, name: &str) -> Path {
+    match parent {
+        Some(parent) => parent.child(Segment::Field(name.to_string())),
+        None => Path::new(name),
+    }
+}
+
+fn diff_records(path: Option<&Path>, old: &Record, new: &Record, changes: &mut Vec<Change>) {
+    for (name, old_value) in old.iter() {
+        let path = field_path(path, name);
+        match new.get(name) {
+            Some(new_value) => diff_values(&path, old_value, new_value, changes),
+            None => changes.push(Change::Remove {
+                path,
+                value: old_value.clone(),
+            }),
+        }
+    }
+    for (name, new_value) in new.iter() {
+        if !old.contains(name) {
+            changes.push(Change::Add {
+                path: field_path(path, name),
+                value: new_value.clone(),
+            });
+        }
+    }
+}
+
+fn diff_values(path: &Path, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Record(old), Value::Record(new)) => diff_records(Some(path), old, new, changes),
+        (Value::Collection(old), Value::Collection(new)) => {
+            for (index, (old, new)) in old.iter().zip(new).enumerate() {
+                diff_values(&path.child(Segment::Index(index)), old, new, changes);
+            }
+            for (index, value) in new.iter().enumerate().skip(old.len()) {
+                changes.push(Change::Add {
+                    path: path.child(Segment::Index(index)),
+                    value: value.clone(),
+                });
+            }
+            // From the end, so that the indexes stay valid
+            for (index, value) in old.iter().enumerate().skip(new.len()).rev() {
+                changes.push(Change::Remove {
+                    path: path.child(Segment::Index(index)),
+                    value: value.clone(),
+                });
+            }
+        }
+        (old, new) if old != new => changes.push(Change::Replace {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn apply(record: &mut Record, change: &Change) -> Result<(), PathError> {
+    let path = change.path();
+    let error = |message: &str| PathError::new(&path.to_string(), message);
+    match change {
+        Change::Add { value, .. } => match (path.parent(), path.last()) {
+            (None, _) => record.set_path(path, value.clone()),
+            (Some(parent), segment) => match (record.get_path_mut(&parent), segment) {
+                (Some(Value::Record(parent)), Segment::Field(name)) => {
+                    parent.set(name, value.clone());
+                    Ok(())
+                }
+                (Some(Value::Collection(items)), Segment::Index(index))
+                    if *index <= items.len() =>
+                {
+                    items.insert(*index, value.clone());
+                    Ok(())
+                }
+                _ => Err(error("cannot add the value")),
+            },
+        },
+        Change::Remove { .. } => {
+            if path.has_wildcard() || record.remove_path(path).is_empty() {
+                return Err(error("cannot remove a missing value"));
+            }
+            Ok(())
+        }
+        Change::Replace { new, .. } => match record.get_path_mut(path) {
+            Some(target) => {
+                *target = new.clone();
+                Ok(())
+            }
+            None => Err(error("cannot replace a missing value")),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,210 @@
+use serde_json::json;
+
+use crate::{record::Record, value::Value};
+
+use super::{Change, Patch};
+
+fn path(input: &str) -> crate::record::path::Path {
+    input.parse().unwrap()
+}
+
+fn old() -> Record {
+    Record::from(json!({
+        "id": 1,
+        "name": "John",
+        "address": {"city": "Vienna", "zip": "1010"},
+        "items": [{"sku": "a-1", "qty": 1}, {"sku": "b-2"}, {"sku": "c-3"}],
+        "obsolete": true
+    }))
+}
+
+fn new() -> Record {
+    Record::from(json!({
+        "id": 1,
+        "name": "Jane",
+        "address": {"city": "Graz", "country": "AT"},
+        "items": [{"sku": "a-1", "qty": 2}],
+        "email": "jane@example.com"
+    }))
+}
+
+#[test]
+fn test_diff() {
+    let patch = old().diff(&new());
+    assert_eq!(
+        vec![
+            Change::Replace {
+                path: path("address.city"),
+                old: "Vienna".into(),
+                new: "Graz".into()
+            },
+            Change::Remove {
+                path: path("address.zip"),
+                value: "1010".into()
+            },
+            Change::Add {
+                path: path("address.country"),
+                value: "AT".into()
+            },
+            Change::Replace {
+                path: path("items[0].qty"),
+                old: Value::U8(1),
+                new: Value::U8(2)
+            },
+            Change::Remove {
+                path: path("items[2]"),
+                value: Value::Record(Record::from(json!({"sku": "c-3"})))
+            },
+            Change::Remove {
+                path: path("items[1]"),
+                value: Value::Record(Record::from(json!({"sku": "b-2"})))
+            },
+            Change::Replace {
+                path: path("name"),
+                old: "John".into(),
+                new: "Jane".into()
+            },
+            Change::Remove {
+                path: path("obsolete"),
+                value: Value::Bool(true)
+            },
+            Change::Add {
+                path: path("email"),
+                value: "jane@example.com".into()
+            },
+        ],
+        patch.changes()
+    );
+    assert_eq!(&path("address.city"), patch.changes()[0].path());
+
+    assert!(old().diff(&old()).is_empty());
+    assert!(Record::new().diff(&Record::new()).is_empty());
+}
+
+#[test]
+fn test_diff_types() {
+    let mut old = Record::new();
+    old.set("a", Value::U8(1));
+    old.set("b", Value::Collection(vec![Value::U8(1)]));
+    let mut new = Record::new();
+    new.set("a", Value::I32(1));
+    new.set("b", "text".into());
+
+    let patch = old.diff(&new);
+    assert_eq!(
+        vec![
+            "replace a: 1 -> 1".to_string(),
+            format!(
+                "replace b: {} -> text",
+                Value::Collection(vec![Value::U8(1)])
+            )
+        ],
+        patch
+            .changes()
+            .iter()
+            .map(|change| change.to_string())
+            .collect::<Vec<String>>()
+    );
+}
+
+#[test]
+fn test_patch() {
+    let mut record = old();
+    record.patch(&old().diff(&new())).unwrap();
+    // The added fields are appended, so compare field by field
+    assert!(record.diff(&new()).is_empty());
+    assert!(new().diff(&record).is_empty());
+
+    let mut record = new();
+    record.patch(&new().diff(&old())).unwrap();
+    assert!(record.diff(&old()).is_empty());
+
+    // Items are inserted at their index
+    let old = Record::from(json!({"list": ["a", "c"]}));
+    let mut record = old.clone();
+    record
+        .patch(&Patch::new(vec![Change::Add {
+            path: path("list[1]"),
+            value: Value::Char('b'),
+        }]))
+        .unwrap();
+    assert_eq!(Record::from(json!({"list": ["a", "b", "c"]})), record);
+}
+
+#[test]
+fn test_patch_errors() {
+    let mut record = old();
+    for (change, message) in [
+        (
+            Change::Remove {
+                path: path("unknown"),
+                value: Value::None,
+            },
+            "Path 'unknown': cannot remove a missing value",
+        ),
+        (
+            Change::Replace {
+                path: path("items[5].sku"),
+                old: Value::None,
+                new: Value::None,
+            },
+            "Path 'items[5].sku': cannot replace a missing value",
+        ),
+        (
+            Change::Add {
+                path: path("items[5]"),
+                value: Value::None,
+            },
+            "Path 'items[5]': cannot add the value",
+        ),
+        (
+            Change::Add {
+                path: path("unknown.field"),
+                value: Value::None,
+            },
+            "Path 'unknown.field': cannot add the value",
+        ),
+    ] {
+        let error = record.patch(&Patch::new(vec![change])).unwrap_err();
+        assert_eq!(message, error.to_string());
+    }
+    assert_eq!(old(), record);
+}
+
+#[test]
+fn test_serialization() {
+    let patch = old().diff(&new());
+    let json = serde_json::to_value(&patch).unwrap();
+    assert_eq!(
+        json!({"op": "replace", "path": "address.city", "old": {"String": "Vienna"}, "new": {"String": "Graz"}}),
+        json[0]
+    );
+    assert_eq!(
+        json!({"op": "remove", "path": "items[2]", "value": {"Record": [{"name": "sku", "value": {"String": "c-3"}}]}}),
+        json[4]
+    );
+    assert_eq!(
+        json!({"op": "add", "path": "email", "value": {"String": "jane@example.com"}}),
+        json[8]
+    );
+
+    let restored: Patch = serde_json::from_value(json).unwrap();
+    assert_eq!(patch, restored);
+}
+
+#[test]
+fn test_serialization_escaped_names() {
+    let old = Record::from(json!({"a.b": 1, "c": {"*": [1]}}));
+    let new = Record::from(json!({"a.b": 2, "c": {"*": [1, 2]}}));
+    let patch = old.diff(&new);
+
+    let json = serde_json::to_value(&patch).unwrap();
+    assert_eq!(json!(r"a\.b"), json[0]["path"]);
+    assert_eq!(json!(r"c.\*[1]"), json[1]["path"]);
+
+    let restored: Patch = serde_json::from_value(json).unwrap();
+    assert_eq!(patch, restored);
+    let mut record = old.clone();
+    record.patch(&restored).unwrap();
+    assert_eq!(new, record);
+}
@@ -0,0 +1,283 @@
+//! Flattening of nested records into top-level fields and back
+//!
+//! Exporters with flat columns (e.g. CSV or database tables) cannot store
+//! nested [Value::Record]s and [Value::Collection]s. [Record::flatten] joins
+//! the names of nested fields with a separator, e.g. `customer.address.city`.
+//! Collections are either indexed (`items[0].sku`) or written as JSON text,
+//! see [ArrayPolicy]. [Record::unflatten] rebuilds the tree.
+//!
+//! The round trip is lossless, if no field name contains the separator or a
+//! `[`. [ArrayPolicy::Json] writes collections with the tagged JSON encoding
+//! of [Value::to_tagged_json], so every item keeps its exact variant. Strings,
+//! that look like such an encoding, are written as tagged strings, so that
+//! [Record::unflatten] does not mistake them for collections.
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     record::{Record, flatten::ArrayPolicy},
+//!     value::Value,
+//! };
+//! use serde_json::json;
+//!
+//! let record = Record::from(json!({
+//!     "customer": {"name": "John", "ids": [1, 2]},
+//!     "items": [{"sku": "a-1"}, {"sku": "b-2"}]
+//! }));
+//!
+//! let flat = record.flatten(".", ArrayPolicy::Index);
+//! let names: Vec<&str> = flat.iter().map(|(name, _)| name).collect();
+//! assert_eq!(
+//!     vec![
+//!         "customer.ids[0]",
+//!         "customer.ids[1]",
+//!         "customer.name",
+//!         "items[0].sku",
+//!         "items[1].sku"
+//!     ],
+//!     names
+//! );
+//! assert_eq!(record, flat.unflatten(".", ArrayPolicy::Index));
+//!
+//! let flat = record.flatten("_", ArrayPolicy::Json);
+//! assert_eq!(
+//!     Some(&Value::from(r#"{"Collection":[{"U8":1},{"U8":2}]}"#)),
+//!     flat.get("customer_ids")
+//! );
+//! assert_eq!(record, flat.unflatten("_", ArrayPolicy::Json));
+//! ```
+use serde_json::Value as JsonValue;
+
+use crate::{field::Field, value::Value};
+
+use super::{Record, path::Segment};
+
+/// How [Record::flatten] handles collections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayPolicy {
+    /// Every item becomes a field with the index in brackets, e.g. `tags[0]`
+    #[default]
+    Index,
+    /// The collection becomes a [Value::String] with its tagged JSON text,
+    /// see [Value::to_tagged_json]
+    Json,
+}
+
+impl Record {
+    /// Returns a record without nested records and, with
+    /// [ArrayPolicy::Index], without collections. Empty records and
+    /// collections are kept as values
+    ///
+    /// # Arguments
+    /// * `separator` - Joins the names of nested fields
+    /// * `array_policy` - How to handle collections
+    ///
+    /// # Panics
+    /// If the `separator` is empty
+    pub fn flatten(&self, separator: &str, array_policy: ArrayPolicy) -> Record {
+        assert!(!separator.is_empty(), "The separator must not be empty");
+        let mut flat = Record::new();
+        for (name, value) in self.iter() {
+            flatten_value(name.to_string(), value, separator, array_policy, &mut flat);
+        }
+        flat
+    }
+
+    /// Rebuilds the nested records and collections from the names of a
+    /// record created by [Record::flatten]. A name with an index, that would
+    /// skip items of a collection, is kept as it is
+    ///
+    /// # Arguments
+    /// * `separator` - The separator used for flattening
+    /// * `array_policy` - The policy used for flattening
+    ///
+    /// # Panics
+    /// If the `separator` is empty
+    pub fn unflatten(&self, separator: &str, array_policy: ArrayPolicy) -> Record {
+        assert!(!separator.is_empty(), "The separator must not be empty");
+        let mut record = Record::new();
+        for (name, value) in self.iter() {
+            let segments = split_name(name, separator, array_policy);
+            let value = match (array_policy, value) {
+                (ArrayPolicy::Json, Value::String(text)) => {
+                    decode_tagged(text).unwrap_or_else(|| value.clone())
+                }
+                _ => value.clone(),
+            };
+            if fits(&record, &segments) {
+                insert_into_record(&mut record, &segments, value);
+            } else {
+                record.set(name, value);
+            }
+        }
+        record
+    }
+}
+
+fn flatten_value(
+    name: String,
+    value: &Value,
+    separator: &str,
+    array_policy: ArrayPolicy,
+    flat: &mut Record,
+) {
+    match value {
+        Value::Record(record) if !record.fields().is_empty() => {
+            for (field_name, value) in record.iter() {
+                let name = format!("{}{}{}", name, separator, field_name);
+                flatten_value(name, value, separator, array_policy, flat);
+            }
+        }
+        Value::Collection(items) if !items.is_empty() && array_policy == ArrayPolicy::Index => {
+            for (index, item) in items.iter().enumerate() {
+                let name = format!("{}[{}]", name, index);
+                flatten_value(name, item, separator, array_policy, flat);
+            }
+        }
+        Value::Collection(_) if array_policy == ArrayPolicy::Json => {
+            let text = value.to_tagged_json().to_string();
+            flat.fields_as_mut()
+                .push(Field::new_value(&name, Value::from(text)));
+        }
+        Value::String(text)
+            if array_policy == ArrayPolicy::Json && decode_tagged(text).is_some() =>
+        {
+            let text = value.to_tagged_json().to_string();
+            flat.fields_as_mut()
+                .push(Field::new_value(&name, Value::from(text)));
+        }
+        _ => flat
+            .fields_as_mut()
+            .push(Field::new_value(&name, value.clone())),
+    }
+}
+
+/// Returns the collection or string, if `text` is its tagged JSON encoding
+fn decode_tagged(text: &str) -> Option<Value> {
+    if !text.starts_with("{\"Collection\"") && !text.starts_with("{\"String\"") {
+        return None;
+    }
+    let json = serde_json::from_str::<JsonValue>(text).ok()?;
+    match Value::from_tagged_json(&json).ok()? {
+        value @ (Value::Collection(_) | Value::String(_)) => Some(value),
+        _ => None,
+    }
+}
+
+/// Splits a flattened name into [Segment::Field]s and, with
+/// [ArrayPolicy::Index], [Segment::Index]es
+fn split_name(name: &str, separator: &str, array_policy: ArrayPolicy) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    for part in name.split(separator) {
+        match array_policy {
+            ArrayPolicy::Index => match split_indexes(part) {
+                Some((field, indexes)) => {
+                    segments.push(Segment::Field(field.to_string()));
+                    segments.extend(indexes.into_iter().map(Segment::Index));
+                }
+                None => segments.push(Segment::Field(part.to_string())),
+            },
+            ArrayPolicy::Json => segments.push(Segment::Field(part.to_string())),
+        }
+    }
+    segments
+}
+
+/// Splits `name[1][2]` into `name` and the indexes. Returns [None], if the
+/// part has no valid indexes
+fn split_indexes(part: &str) -> Option<(&str, Vec<usize>)> {
+    let start = part.find('[')?;
+    let mut indexes = Vec::new();
+    let mut rest = &part[start..];
+    while !rest.is_empty() {
+        if !rest.starts_with('[') {
+            return None;
+        }
+        let end = rest.find(']')?;
+        let index = &rest[1..end];
+        if !index.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        indexes.push(index.parse().ok()?);
+        rest = &rest[end + 1..];
+    }
+    Some((&part[..start], indexes))
+}
+
+/// Returns true, if every index of `segments` at most appends one item to a
+/// collection, that `record` already has. Other names are kept as they are,
+/// so that a name like `x[99999999999]` does not allocate a huge collection
+fn fits(record: &Record, segments: &[Segment]) -> bool {
+    let Segment::Field(name) = &segments[0] else {
+        return false;
+    };
+    let mut current = record.get(name);
+    for segment in &segments[1..] {
+        current = match (segment, current) {
+            (Segment::Field(name), Some(Value::Record(record))) => record.get(name),
+            (Segment::Index(index), Some(Value::Collection(items))) => {
+                if *index > items.len() {
+                    return false;
+                }
+                items.get(*index)
+            }
+            (Segment::Index(index), _) => {
+                if *index > 0 {
+                    return false;
+                }
+                None
+            }
+            _ => None,
+        };
+    }
+    true
+}
+
+fn insert_into_record(record: &mut Record, segments: &[Segment], value: Value) {
+    let Segment::Field(name) = &segments[0] else {
+        return;
+    };
+    let rest = &segments[1..];
+    if rest.is_empty() {
+        record.set(name, value);
+        return;
+    }
+    if !record.contains(name) {
+        record.set(name, Value::None);
+    }
+    if let Some(target) = record.get_mut(name) {
+        insert_into_value(target, rest, value);
+    }
+}
+
+fn insert_into_value(target: &mut Value, segments: &[Segment], value: Value) {
+    match &segments[0] {
+        Segment::Field(_) => {
+            if !matches!(target, Value::Record(_)) {
+                *target = Value::Record(Record::new());
+            }
+            if let Value::Record(record) = target {
+                insert_into_record(record, segments, value);
+            }
+        }
+        Segment::Index(index) => {
+            if !matches!(target, Value::Collection(_)) {
+                *target = Value::Collection(Vec::new());
+            }
+            if let Value::Collection(items) = target {
+                if items.len() == *index {
+                    items.push(Value::None);
+                }
+                if segments.len() == 1 {
+                    items[*index] = value;
+                } else {
+                    insert_into_value(&mut items[*index], &segments[1..], value);
+                }
+            }
+        }
+        Segment::Wildcard => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
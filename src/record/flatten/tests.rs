@@ -0,0 +1,201 @@
+use serde_json::json;
+
+use crate::{field::add_field, record::Record, value::Value};
+
+use super::{ArrayPolicy, split_indexes};
+
+fn nested() -> Record {
+    let mut record = Record::from(json!({
+        "id": 1,
+        "customer": {"name": "John", "address": {"city": "Vienna", "zip": null}},
+        "items": [{"sku": "a-1", "qty": 2}, {"sku": "b-2", "tags": ["x", "yz"]}],
+        "matrix": [[1, 2], [3]],
+        "empty_record": {},
+        "empty_list": []
+    }));
+    record.set("price", Value::Decimal(rust_decimal::Decimal::new(1999, 2)));
+    record
+}
+
+fn names(record: &Record) -> Vec<&str> {
+    record.iter().map(|(name, _)| name).collect()
+}
+
+#[test]
+fn test_split_indexes() {
+    assert_eq!(Some(("a", vec![0])), split_indexes("a[0]"));
+    assert_eq!(Some(("m", vec![1, 20])), split_indexes("m[1][20]"));
+    assert_eq!(None, split_indexes("a"));
+    assert_eq!(None, split_indexes("a[x]"));
+    assert_eq!(None, split_indexes("a[1"));
+    assert_eq!(None, split_indexes("a[1]b"));
+    assert_eq!(None, split_indexes("a[0]]"));
+    assert_eq!(None, split_indexes("a]"));
+}
+
+#[test]
+fn test_flatten_index() {
+    let flat = nested().flatten(".", ArrayPolicy::Index);
+    assert_eq!(
+        vec![
+            "customer.address.city",
+            "customer.address.zip",
+            "customer.name",
+            "empty_list",
+            "empty_record",
+            "id",
+            "items[0].qty",
+            "items[0].sku",
+            "items[1].sku",
+            "items[1].tags[0]",
+            "items[1].tags[1]",
+            "matrix[0][0]",
+            "matrix[0][1]",
+            "matrix[1][0]",
+            "price",
+        ],
+        names(&flat)
+    );
+    assert_eq!(Some(&Value::None), flat.get("customer.address.zip"));
+    assert_eq!(Some(&Value::Collection(vec![])), flat.get("empty_list"));
+    assert_eq!(
+        Some(&Value::Record(Record::new())),
+        flat.get("empty_record")
+    );
+    assert_eq!(Some(&Value::U8(3)), flat.get("matrix[1][0]"));
+
+    assert_eq!(nested(), flat.unflatten(".", ArrayPolicy::Index));
+}
+
+#[test]
+fn test_flatten_json() {
+    let flat = nested().flatten("__", ArrayPolicy::Json);
+    assert_eq!(
+        vec![
+            "customer__address__city",
+            "customer__address__zip",
+            "customer__name",
+            "empty_list",
+            "empty_record",
+            "id",
+            "items",
+            "matrix",
+            "price",
+        ],
+        names(&flat)
+    );
+    assert_eq!(
+        Some(&Value::from(
+            r#"{"Collection":[{"Collection":[{"U8":1},{"U8":2}]},{"Collection":[{"U8":3}]}]}"#
+        )),
+        flat.get("matrix")
+    );
+    assert_eq!(
+        Some(&Value::from(r#"{"Collection":[]}"#)),
+        flat.get("empty_list")
+    );
+
+    assert_eq!(nested(), flat.unflatten("__", ArrayPolicy::Json));
+}
+
+#[test]
+fn test_flat_record() {
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::I32(1));
+    add_field(record.fields_as_mut(), "b", "[not json".into());
+    assert_eq!(record, record.flatten(".", ArrayPolicy::Index));
+    assert_eq!(record, record.unflatten(".", ArrayPolicy::Json));
+}
+
+#[test]
+fn test_unflatten_names() {
+    let mut flat = Record::new();
+    add_field(flat.fields_as_mut(), "a.b", Value::I32(1));
+    add_field(flat.fields_as_mut(), "list[0]", Value::I32(0));
+    add_field(flat.fields_as_mut(), "list[2]", Value::I32(2));
+    add_field(flat.fields_as_mut(), "list[1]", Value::I32(1));
+    add_field(flat.fields_as_mut(), "a.c[x]", Value::I32(3));
+    add_field(flat.fields_as_mut(), "a.b", Value::I32(4));
+
+    let record = flat.unflatten(".", ArrayPolicy::Index);
+    assert_eq!(
+        Some(&Value::I32(4)),
+        record.get_path(&"a.b".parse().unwrap())
+    );
+    // An index, that skips items, is kept as a plain name
+    assert_eq!(
+        Some(&Value::Collection(vec![Value::I32(0), Value::I32(1)])),
+        record.get("list")
+    );
+    assert_eq!(Some(&Value::I32(2)), record.get("list[2]"));
+    let a = record.get("a").unwrap();
+    let Value::Record(a) = a else {
+        panic!("Expected a record");
+    };
+    assert_eq!(Some(&Value::I32(3)), a.get("c[x]"));
+
+    // Brackets are part of the name with the JSON policy
+    let record = flat.unflatten(".", ArrayPolicy::Json);
+    assert_eq!(Some(&Value::I32(2)), record.get("list[2]"));
+}
+
+#[test]
+#[should_panic(expected = "The separator must not be empty")]
+fn test_empty_separator() {
+    nested().flatten("", ArrayPolicy::Index);
+}
+
+#[test]
+fn test_unflatten_large_indexes() {
+    let mut flat = Record::new();
+    add_field(
+        flat.fields_as_mut(),
+        "x[18446744073709551615]",
+        Value::I32(1),
+    );
+    add_field(flat.fields_as_mut(), "y[99999999999]", Value::I32(2));
+    add_field(flat.fields_as_mut(), "z[0][5]", Value::I32(3));
+    add_field(flat.fields_as_mut(), "a.b[1]", Value::I32(4));
+
+    // The names are kept instead of allocating the collections
+    let record = flat.unflatten(".", ArrayPolicy::Index);
+    assert_eq!(Some(&Value::I32(1)), record.get("x[18446744073709551615]"));
+    assert_eq!(Some(&Value::I32(2)), record.get("y[99999999999]"));
+    assert_eq!(Some(&Value::I32(3)), record.get("z[0][5]"));
+    assert_eq!(Some(&Value::I32(4)), record.get("a.b[1]"));
+    assert_eq!(4, record.fields().len());
+}
+
+#[test]
+fn test_json_lossless() {
+    let mut record = Record::new();
+    record.set("note", "[1,2]".into());
+    record.set("tagged", r#"{"Collection":[]}"#.into());
+    record.set(
+        "items",
+        Value::Collection(vec![
+            Value::Decimal(rust_decimal::Decimal::new(1999, 2)),
+            Value::I128(-1),
+            Value::from("x"),
+        ]),
+    );
+
+    let flat = record.flatten(".", ArrayPolicy::Json);
+    assert_eq!(Some(&Value::from("[1,2]")), flat.get("note"));
+    assert_eq!(
+        Some(&Value::from(r#"{"String":"{\"Collection\":[]}"}"#)),
+        flat.get("tagged")
+    );
+    assert_eq!(record, flat.unflatten(".", ArrayPolicy::Json));
+}
+
+#[test]
+fn test_unflatten_invalid_indexes() {
+    let mut flat = Record::new();
+    for (i, name) in ["a[0]]", "a]", "a[0]x", "b[]]"].into_iter().enumerate() {
+        add_field(flat.fields_as_mut(), name, Value::USize(i));
+    }
+
+    let record = flat.unflatten(".", ArrayPolicy::Index);
+    assert_eq!(flat, record);
+}
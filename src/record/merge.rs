@@ -0,0 +1,120 @@
+//! Merging of records
+//!
+//! # Example
+//! ```
+//! use model::record::{Record, merge::MergeStrategy};
+//! use serde_json::json;
+//!
+//! let mut record = Record::from(json!({"id": 1, "address": {"city": "Vienna"}}));
+//! let update = Record::from(json!({"address": {"zip": "1010"}, "name": "John"}));
+//! record.merge(&update, MergeStrategy::DeepMerge);
+//! assert_eq!(
+//!     Record::from(json!({
+//!         "id": 1,
+//!         "address": {"city": "Vienna", "zip": "1010"},
+//!         "name": "John"
+//!     })),
+//!     record
+//! );
+//! ```
+use crate::value::Value;
+
+use super::Record;
+
+/// How [Record::merge] handles fields, that exist in both records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MergeStrategy {
+    /// The value of the other record replaces the existing value
+    #[default]
+    Overwrite,
+    /// The existing value is kept
+    KeepExisting,
+    /// Nested records are merged recursively, all other values are replaced
+    DeepMerge,
+}
+
+impl Record {
+    /// Merges the fields of `other` into this record. Fields, that only exist
+    /// in `other`, are appended in their order
+    ///
+    /// # Arguments
+    /// * `other` - The record to merge from
+    /// * `strategy` - How to handle fields, that exist in both records
+    pub fn merge(&mut self, other: &Record, strategy: MergeStrategy) {
+        for (name, value) in other.iter() {
+            match (self.get_mut(name), strategy) {
+                (None, _) => {
+                    self.set(name, value.clone());
+                }
+                (Some(_), MergeStrategy::KeepExisting) => {}
+                (Some(existing), MergeStrategy::DeepMerge) => match (existing, value) {
+                    (Value::Record(existing), Value::Record(other)) => {
+                        existing.merge(other, strategy)
+                    }
+                    (existing, value) => *existing = value.clone(),
+                },
+                (Some(existing), MergeStrategy::Overwrite) => *existing = value.clone(),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::record::Record;
+
+    use super::MergeStrategy;
+
+    fn base() -> Record {
+        Record::from(json!({"a": 1, "nested": {"x": 1, "y": 2}}))
+    }
+
+    fn update() -> Record {
+        Record::from(json!({"a": 3, "b": 2, "nested": {"y": 3, "z": 4}}))
+    }
+
+    fn merged(strategy: MergeStrategy) -> Record {
+        let mut record = base();
+        record.merge(&update(), strategy);
+        record
+    }
+
+    #[test]
+    fn test_order() {
+        let record = merged(MergeStrategy::Overwrite);
+        let names: Vec<&str> = record.iter().map(|(name, _)| name).collect();
+        assert_eq!(vec!["a", "nested", "b"], names);
+    }
+
+    #[test]
+    fn test_overwrite() {
+        let expected = Record::from(json!({"a": 3, "b": 2, "nested": {"y": 3, "z": 4}}));
+        assert!(merged(MergeStrategy::Overwrite).diff(&expected).is_empty());
+    }
+
+    #[test]
+    fn test_keep_existing() {
+        let expected = Record::from(json!({"a": 1, "b": 2, "nested": {"x": 1, "y": 2}}));
+        assert!(
+            merged(MergeStrategy::KeepExisting)
+                .diff(&expected)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_deep_merge() {
+        let expected = Record::from(json!({"a": 3, "b": 2, "nested": {"x": 1, "y": 3, "z": 4}}));
+        assert!(merged(MergeStrategy::DeepMerge).diff(&expected).is_empty());
+
+        // A record is replaced by a value of another type
+        let mut record = base();
+        record.merge(
+            &Record::from(json!({"nested": [1]})),
+            MergeStrategy::DeepMerge,
+        );
+        assert_eq!(Record::from(json!({"a": 1, "nested": [1]})), record);
+    }
+}
@@ -0,0 +1,238 @@
+//! The context of a [Record]
+//!
+//! A [RecordMeta] tells where a record came from: the source, the sequence
+//! number, the offset or line in the source, the time of the import,
+//! arbitrary string headers and the [Checkpoint] to resume after the record.
+//! Importers fill it in, the [crate::pipeline] carries it through the
+//! transformers and exporters and error reports can point to the source with
+//! it.
+//!
+//! The meta data is not part of the equality of records and is not
+//! serialized with them.
+//!
+//! # Example
+//! ```
+//! use model::record::{Record, meta::RecordMeta};
+//!
+//! let record = Record::new().with_meta(
+//!     RecordMeta::new()
+//!         .with_source("customers.csv")
+//!         .with_sequence(7)
+//!         .with_offset(8)
+//!         .with_header("delimiter", ";"),
+//! );
+//!
+//! assert_eq!(Some("customers.csv"), record.meta().source());
+//! assert_eq!(Some(";"), record.meta().header("delimiter"));
+//! assert_eq!(
+//!     "source 'customers.csv', offset 8, sequence 7",
+//!     record.meta().to_string()
+//! );
+//! ```
+use std::{collections::BTreeMap, fmt::Display};
+
+use chrono::{DateTime, Utc};
+
+use crate::checkpoint::Checkpoint;
+
+/// The context of a [super::Record]
+///
+/// # Members
+/// * `source` - An identifier of the import source, e.g. a file name or table
+/// * `sequence` - The number of the record within the import
+/// * `offset` - The offset or line of the record in the source
+/// * `ingested_at` - The time, the record was imported
+/// * `headers` - Arbitrary string headers
+/// * `checkpoint` - The position in the source after the record
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RecordMeta {
+    source: Option<String>,
+    sequence: Option<u64>,
+    offset: Option<u64>,
+    ingested_at: Option<DateTime<Utc>>,
+    headers: BTreeMap<String, String>,
+    checkpoint: Option<Checkpoint>,
+}
+
+impl RecordMeta {
+    /// Creates an empty [RecordMeta]
+    pub const fn new() -> Self {
+        Self {
+            source: None,
+            sequence: None,
+            offset: None,
+            ingested_at: None,
+            headers: BTreeMap::new(),
+            checkpoint: None,
+        }
+    }
+
+    /// Returns true, if nothing is known about the record
+    pub fn is_empty(&self) -> bool {
+        self == &Self::new()
+    }
+
+    /// Sets the identifier of the import source
+    pub fn with_source(mut self, source: &str) -> Self {
+        self.source = Some(source.to_string());
+        self
+    }
+
+    /// Sets the number of the record within the import
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        self.sequence = Some(sequence);
+        self
+    }
+
+    /// Sets the offset or line of the record in the source
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    /// Sets the time, the record was imported
+    pub fn with_ingested_at(mut self, ingested_at: DateTime<Utc>) -> Self {
+        self.ingested_at = Some(ingested_at);
+        self
+    }
+
+    /// Adds a header
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.set_header(name, value);
+        self
+    }
+
+    /// Sets the position in the source after the record
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Returns the identifier of the import source
+    pub fn source(&self) -> Option<&str> {
+        self.source.as_deref()
+    }
+
+    /// Returns the number of the record within the import
+    pub fn sequence(&self) -> Option<u64> {
+        self.sequence
+    }
+
+    /// Returns the offset or line of the record in the source
+    pub fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Returns the time, the record was imported
+    pub fn ingested_at(&self) -> Option<DateTime<Utc>> {
+        self.ingested_at
+    }
+
+    /// Returns the header with the given `name`
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    /// Returns all headers ordered by name
+    pub fn headers(&self) -> &BTreeMap<String, String> {
+        &self.headers
+    }
+
+    /// Returns the position in the source after the record
+    pub fn checkpoint(&self) -> Option<&Checkpoint> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Sets the position in the source after the record
+    pub fn set_checkpoint(&mut self, checkpoint: Option<Checkpoint>) {
+        self.checkpoint = checkpoint;
+    }
+
+    /// Sets the header `name` and returns its previous value
+    pub fn set_header(&mut self, name: &str, value: &str) -> Option<String> {
+        self.headers.insert(name.to_string(), value.to_string())
+    }
+
+    /// Removes the header `name` and returns its value
+    pub fn remove_header(&mut self, name: &str) -> Option<String> {
+        self.headers.remove(name)
+    }
+}
+
+/// Writes the position of the record, e.g.
+/// `source 'customers.csv', offset 8, sequence 7`
+impl Display for RecordMeta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(source) = &self.source {
+            parts.push(format!("source '{}'", source));
+        }
+        if let Some(offset) = self.offset {
+            parts.push(format!("offset {}", offset));
+        }
+        if let Some(sequence) = self.sequence {
+            parts.push(format!("sequence {}", sequence));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::{checkpoint::Checkpoint, record::Record, value::Value};
+
+    use super::RecordMeta;
+
+    #[test]
+    fn test_empty() {
+        let meta = RecordMeta::new();
+        assert!(meta.is_empty());
+        assert_eq!("", meta.to_string());
+        assert!(Record::new().meta().is_empty());
+        assert!(!RecordMeta::new().with_header("a", "b").is_empty());
+    }
+
+    #[test]
+    fn test_members() {
+        let time = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let mut meta = RecordMeta::new()
+            .with_sequence(3)
+            .with_ingested_at(time)
+            .with_header("topic", "orders");
+        assert_eq!(None, meta.source());
+        assert_eq!(None, meta.offset());
+        assert_eq!(Some(3), meta.sequence());
+        assert_eq!(Some(time), meta.ingested_at());
+        assert_eq!("sequence 3", meta.to_string());
+
+        assert_eq!(
+            Some("orders".to_string()),
+            meta.set_header("topic", "items")
+        );
+        assert_eq!(Some("items"), meta.header("topic"));
+        assert_eq!(1, meta.headers().len());
+        assert_eq!(Some("items".to_string()), meta.remove_header("topic"));
+        assert_eq!(None, meta.header("topic"));
+
+        assert_eq!(None, meta.checkpoint());
+        meta.set_checkpoint(Some(Checkpoint::new("42")));
+        assert_eq!(Some("42"), meta.checkpoint().map(Checkpoint::token));
+        assert_eq!("sequence 3", meta.to_string());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut record = Record::new();
+        record.set("a", Value::I32(1));
+        record.meta_mut().set_header("key", "1");
+        let copy = Record::copy(&record);
+        assert_eq!(Some("1"), copy.meta().header("key"));
+
+        // The meta data is not part of the equality
+        let mut other = Record::new().with_meta(RecordMeta::new().with_source("other"));
+        other.set("a", Value::I32(1));
+        assert_eq!(record, other);
+    }
+}
@@ -0,0 +1,512 @@
+//! Paths into nested records and collections
+//!
+//! A [Path] addresses values inside a [Record], that contains nested
+//! [Value::Record]s and [Value::Collection]s:
+//! * `customer.address.city` - The field `city` of the record in the field
+//!   `address` of the record in the field `customer`
+//! * `items[2].price` - The field `price` of the third item of the collection
+//!   `items`
+//! * `items[*].sku` - The field `sku` of all items. `*` is a wildcard for all
+//!   items of a collection or all fields of a record and can be written as
+//!   `[*]` or `.*`
+//!
+//! A `.`, `[`, `]`, `*` or `\` in a field name is escaped with a `\`, e.g.
+//! `prices.net\.value` addresses the field `net.value` of `prices`.
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     record::{Record, path::Path},
+//!     value::Value,
+//! };
+//! use serde_json::json;
+//!
+//! let mut record = Record::from(json!({
+//!     "items": [{"sku": "a-1", "price": 10}, {"sku": "b-2", "price": 20}]
+//! }));
+//!
+//! let path: Path = "items[1].price".parse().unwrap();
+//! assert_eq!(Some(&Value::U8(20)), record.get_path(&path));
+//!
+//! let skus = record.select_path(&"items[*].sku".parse().unwrap());
+//! assert_eq!(vec![&Value::from("a-1"), &Value::from("b-2")], skus);
+//!
+//! record
+//!     .set_path(&"customer.address.city".parse().unwrap(), "Vienna".into())
+//!     .unwrap();
+//! assert_eq!(
+//!     Some(&Value::from("Vienna")),
+//!     record.get_path(&"customer.address.city".parse().unwrap())
+//! );
+//!
+//! let removed = record.remove_path(&"items[*].price".parse().unwrap());
+//! assert_eq!(2, removed.len());
+//! ```
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::value::Value;
+
+use super::Record;
+
+/// The characters of a field name, that are escaped with a `\`
+const ESCAPED: [char; 5] = ['.', '[', ']', '*', '\\'];
+
+/// A segment of a [Path]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A field of a record
+    Field(String),
+    /// An item of a collection
+    Index(usize),
+    /// All fields of a record or all items of a collection
+    Wildcard,
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Segment::Field(name) => {
+                for c in name.chars() {
+                    if ESCAPED.contains(&c) {
+                        write!(f, "\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                Ok(())
+            }
+            Segment::Index(index) => write!(f, "[{}]", index),
+            Segment::Wildcard => write!(f, "*"),
+        }
+    }
+}
+
+/// The error returned for an invalid path or when a [Path] cannot be set
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathError {
+    path: String,
+    message: String,
+}
+
+impl PathError {
+    pub(crate) fn new(path: &str, message: impl Display) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the path, that caused the error
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Path '{}': {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// A parsed path expression, see the [module documentation](self)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    segments: Vec<Segment>,
+}
+
+impl Path {
+    /// Parses a path expression
+    ///
+    /// # Arguments
+    /// * `input` - The path, e.g. `items[2].price`
+    pub fn parse(input: &str) -> Result<Path, PathError> {
+        let error = |position: usize, message: &str| {
+            PathError::new(input, format!("{} at position {}", message, position))
+        };
+
+        let mut segments = Vec::new();
+        let mut rest = input;
+        let mut position = 0;
+        let mut expect_name = true;
+        loop {
+            if expect_name {
+                let (name, end) = unescape_name(rest);
+                if end == 0 {
+                    return Err(error(position, "expected a field name"));
+                }
+                segments.push(if &rest[..end] == "*" {
+                    Segment::Wildcard
+                } else {
+                    Segment::Field(name)
+                });
+                rest = &rest[end..];
+                position += end;
+            }
+
+            match rest.chars().next() {
+                None => break,
+                Some('.') => {
+                    rest = &rest[1..];
+                    position += 1;
+                    expect_name = true;
+                }
+                Some('[') => {
+                    let Some(end) = rest.find(']') else {
+                        return Err(error(position, "missing ']'"));
+                    };
+                    let index = &rest[1..end];
+                    segments.push(match index {
+                        "*" => Segment::Wildcard,
+                        _ if !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()) => {
+                            Segment::Index(
+                                index
+                                    .parse()
+                                    .map_err(|_| error(position + 1, "index too large"))?,
+                            )
+                        }
+                        _ => return Err(error(position + 1, "expected an index or '*'")),
+                    });
+                    rest = &rest[end + 1..];
+                    position += end + 1;
+                    expect_name = false;
+                }
+                Some(c) => return Err(error(position, &format!("unexpected '{}'", c))),
+            }
+        }
+        Ok(Path { segments })
+    }
+
+    /// Creates a path to the top-level field `name`. Unlike [Path::parse],
+    /// the name may contain any character
+    pub fn new(name: &str) -> Path {
+        Path {
+            segments: vec![Segment::Field(name.to_string())],
+        }
+    }
+
+    /// Returns a new path with the `segment` appended
+    pub fn child(&self, segment: Segment) -> Path {
+        let mut segments = self.segments.clone();
+        segments.push(segment);
+        Path { segments }
+    }
+
+    /// Returns the path without the last segment or [None] for a top-level
+    /// path
+    pub fn parent(&self) -> Option<Path> {
+        match self.segments.len() {
+            0 | 1 => None,
+            length => Some(Path {
+                segments: self.segments[..length - 1].to_vec(),
+            }),
+        }
+    }
+
+    /// Returns the last segment
+    pub fn last(&self) -> &Segment {
+        &self.segments[self.segments.len() - 1]
+    }
+
+    /// Returns the segments of the path. The first one is always a
+    /// [Segment::Field] or [Segment::Wildcard]
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Returns true, if the path contains a [Segment::Wildcard]
+    pub fn has_wildcard(&self) -> bool {
+        self.segments.contains(&Segment::Wildcard)
+    }
+}
+
+/// Returns the field name at the start of `input` without its escapes and
+/// its length in `input`. A `\` before another character is kept
+fn unescape_name(input: &str) -> (String, usize) {
+    let mut name = String::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '.' | '[' | ']' => return (name, position),
+            '\\' => match chars.peek() {
+                Some((_, next)) if ESCAPED.contains(next) => {
+                    name.push(*next);
+                    chars.next();
+                }
+                _ => name.push(c),
+            },
+            _ => name.push(c),
+        }
+    }
+    (name, input.len())
+}
+
+impl FromStr for Path {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Path::parse(s)
+    }
+}
+
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (position, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Field(_) if position > 0 => write!(f, ".{}", segment)?,
+                Segment::Wildcard if position > 0 => write!(f, "[*]")?,
+                _ => write!(f, "{}", segment)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A path is serialized as its string
+impl Serialize for Path {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Path {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Path::parse(&path).map_err(de::Error::custom)
+    }
+}
+
+impl Record {
+    /// Returns the value addressed by a [Path] without wildcards as a mutable
+    /// reference. A wildcard never matches
+    pub fn get_path_mut(&mut self, path: &Path) -> Option<&mut Value> {
+        let Segment::Field(name) = &path.segments[0] else {
+            return None;
+        };
+        let mut value = self.get_mut(name)?;
+        for segment in &path.segments[1..] {
+            value = match (segment, value) {
+                (Segment::Field(name), Value::Record(record)) => record.get_mut(name)?,
+                (Segment::Index(index), Value::Collection(items)) => items.get_mut(*index)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+
+    /// Returns the first value addressed by the [Path]
+    pub fn get_path(&self, path: &Path) -> Option<&Value> {
+        self.select_path(path).into_iter().next()
+    }
+
+    /// Returns all values addressed by the [Path] in the order of the fields
+    /// and items. Paths without a wildcard address at most one value
+    pub fn select_path(&self, path: &Path) -> Vec<&Value> {
+        let mut values = Vec::new();
+        select_in_record(self, &path.segments, &mut values);
+        values
+    }
+
+    /// Sets all values addressed by the [Path]
+    ///
+    /// A missing last field is appended. A missing or [Value::None]
+    /// intermediate field becomes an empty record, if the path goes on with a
+    /// field name. Collection items are never created.
+    ///
+    /// # Errors
+    /// If the path goes through a value, that is not a record or collection,
+    /// or an index is out of bounds. Values set before the error are kept
+    pub fn set_path(&mut self, path: &Path, value: Value) -> Result<(), PathError> {
+        set_in_record(self, &path.segments, &value, path)
+    }
+
+    /// Removes all fields and items addressed by the [Path] and returns their
+    /// values
+    pub fn remove_path(&mut self, path: &Path) -> Vec<Value> {
+        let mut removed = Vec::new();
+        remove_in_record(self, &path.segments, &mut removed);
+        removed
+    }
+}
+
+fn select_in_record<'a>(record: &'a Record, segments: &[Segment], values: &mut Vec<&'a Value>) {
+    match &segments[0] {
+        Segment::Field(name) => {
+            if let Some(value) = record.get(name) {
+                select_in_value(value, &segments[1..], values);
+            }
+        }
+        Segment::Wildcard => {
+            for (_, value) in record.iter() {
+                select_in_value(value, &segments[1..], values);
+            }
+        }
+        Segment::Index(_) => {}
+    }
+}
+
+fn select_in_value<'a>(value: &'a Value, segments: &[Segment], values: &mut Vec<&'a Value>) {
+    match (segments.first(), value) {
+        (None, _) => values.push(value),
+        (Some(Segment::Field(_) | Segment::Wildcard), Value::Record(record)) => {
+            select_in_record(record, segments, values)
+        }
+        (Some(Segment::Index(index)), Value::Collection(items)) => {
+            if let Some(item) = items.get(*index) {
+                select_in_value(item, &segments[1..], values);
+            }
+        }
+        (Some(Segment::Wildcard), Value::Collection(items)) => {
+            for item in items {
+                select_in_value(item, &segments[1..], values);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn set_in_record(
+    record: &mut Record,
+    segments: &[Segment],
+    value: &Value,
+    path: &Path,
+) -> Result<(), PathError> {
+    let rest = &segments[1..];
+    match &segments[0] {
+        Segment::Field(name) if rest.is_empty() => {
+            record.set(name, value.clone());
+        }
+        Segment::Field(name) => {
+            if matches!(rest[0], Segment::Field(_))
+                && matches!(record.get(name), None | Some(Value::None))
+            {
+                record.set(name, Value::Record(Record::new()));
+            }
+            match record.get_mut(name) {
+                Some(target) => set_in_value(target, rest, value, path)?,
+                None => {
+                    return Err(PathError::new(
+                        &path.to_string(),
+                        format!("missing field '{}'", name),
+                    ));
+                }
+            }
+        }
+        Segment::Wildcard => {
+            for field in record.fields_as_mut() {
+                set_or_descend(field.value_as_mut(), rest, value, path)?;
+            }
+        }
+        Segment::Index(index) => {
+            return Err(PathError::new(
+                &path.to_string(),
+                format!("cannot apply '[{}]' to Record", index),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn set_in_value(
+    target: &mut Value,
+    segments: &[Segment],
+    value: &Value,
+    path: &Path,
+) -> Result<(), PathError> {
+    match (&segments[0], target) {
+        (Segment::Field(_) | Segment::Wildcard, Value::Record(record)) => {
+            set_in_record(record, segments, value, path)
+        }
+        (Segment::Index(index), Value::Collection(items)) => {
+            let length = items.len();
+            match items.get_mut(*index) {
+                Some(item) => set_or_descend(item, &segments[1..], value, path),
+                None => Err(PathError::new(
+                    &path.to_string(),
+                    format!("index {} out of bounds for {} items", index, length),
+                )),
+            }
+        }
+        (Segment::Wildcard, Value::Collection(items)) => {
+            for item in items {
+                set_or_descend(item, &segments[1..], value, path)?;
+            }
+            Ok(())
+        }
+        (segment, target) => Err(PathError::new(
+            &path.to_string(),
+            format!("cannot apply '{}' to {}", segment, target.type_name()),
+        )),
+    }
+}
+
+/// Replaces the `target`, if the `segments` are consumed, and descends into
+/// it otherwise
+fn set_or_descend(
+    target: &mut Value,
+    segments: &[Segment],
+    value: &Value,
+    path: &Path,
+) -> Result<(), PathError> {
+    if segments.is_empty() {
+        *target = value.clone();
+        Ok(())
+    } else {
+        set_in_value(target, segments, value, path)
+    }
+}
+
+fn remove_in_record(record: &mut Record, segments: &[Segment], removed: &mut Vec<Value>) {
+    let rest = &segments[1..];
+    match &segments[0] {
+        Segment::Field(name) if rest.is_empty() => removed.extend(record.remove(name)),
+        Segment::Field(name) => {
+            if let Some(value) = record.get_mut(name) {
+                remove_in_value(value, rest, removed);
+            }
+        }
+        Segment::Wildcard if rest.is_empty() => removed.extend(
+            record
+                .fields_as_mut()
+                .drain(..)
+                .map(|field| field.into_value()),
+        ),
+        Segment::Wildcard => {
+            for field in record.fields_as_mut() {
+                remove_in_value(field.value_as_mut(), rest, removed);
+            }
+        }
+        Segment::Index(_) => {}
+    }
+}
+
+fn remove_in_value(value: &mut Value, segments: &[Segment], removed: &mut Vec<Value>) {
+    let rest = &segments[1..];
+    match (&segments[0], value) {
+        (Segment::Field(_) | Segment::Wildcard, Value::Record(record)) => {
+            remove_in_record(record, segments, removed)
+        }
+        (Segment::Index(index), Value::Collection(items)) if *index < items.len() => {
+            if rest.is_empty() {
+                removed.push(items.remove(*index));
+            } else {
+                remove_in_value(&mut items[*index], rest, removed);
+            }
+        }
+        (Segment::Wildcard, Value::Collection(items)) => {
+            if rest.is_empty() {
+                removed.append(items);
+            } else {
+                for item in items {
+                    remove_in_value(item, rest, removed);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
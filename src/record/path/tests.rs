@@ -0,0 +1,256 @@
+use serde_json::json;
+
+use crate::{record::Record, value::Value};
+
+use super::{Path, Segment};
+
+fn path(input: &str) -> Path {
+    Path::parse(input).unwrap()
+}
+
+fn order() -> Record {
+    Record::from(json!({
+        "customer": {"name": "John", "address": {"city": "Vienna"}},
+        "items": [
+            {"sku": "a-1", "price": 10},
+            {"sku": "b-2", "price": 20},
+            {"sku": "c-3"}
+        ],
+        "tags": ["x", "y"]
+    }))
+}
+
+#[test]
+fn test_parse() {
+    assert_eq!(
+        &[
+            Segment::Field("items".to_string()),
+            Segment::Index(2),
+            Segment::Field("price".to_string())
+        ],
+        path("items[2].price").segments()
+    );
+    assert_eq!(
+        path("items[*].sku").segments(),
+        path("items.*.sku").segments()
+    );
+    assert_eq!(&[Segment::Wildcard], path("*").segments());
+    assert_eq!(
+        &[
+            Segment::Field("matrix".to_string()),
+            Segment::Index(0),
+            Segment::Index(1)
+        ],
+        path("matrix[0][1]").segments()
+    );
+    assert!(path("a[*]").has_wildcard());
+    assert!(!path("a[1]").has_wildcard());
+
+    for input in ["a.b", "items[2].price", "*", "a[*].b[0][*]"] {
+        assert_eq!(input, path(input).to_string());
+    }
+    assert_eq!("a[*]", path("a.*").to_string());
+}
+
+#[test]
+fn test_escape() {
+    assert_eq!(
+        &[
+            Segment::Field("net.value".to_string()),
+            Segment::Field("*".to_string()),
+            Segment::Field("a[0]".to_string()),
+            Segment::Field(r"c:\x\".to_string())
+        ],
+        path(r"net\.value.\*.a\[0\].c:\x\\").segments()
+    );
+
+    let path = Path::new(r"a.b[*]\")
+        .child(Segment::Index(1))
+        .child(Segment::Field("*".to_string()))
+        .child(Segment::Wildcard);
+    assert_eq!(r"a\.b\[\*\]\\[1].\*[*]", path.to_string());
+    assert_eq!(path, Path::parse(&path.to_string()).unwrap());
+}
+
+#[test]
+fn test_parse_errors() {
+    for (input, message) in [
+        ("", "expected a field name at position 0"),
+        ("a..b", "expected a field name at position 2"),
+        ("a.", "expected a field name at position 2"),
+        ("[0]", "expected a field name at position 0"),
+        ("a[1", "missing ']' at position 1"),
+        ("a[x]", "expected an index or '*' at position 2"),
+        ("a[]", "expected an index or '*' at position 2"),
+        ("a[-1]", "expected an index or '*' at position 2"),
+        ("a[1]b", "unexpected 'b' at position 4"),
+        ("a]", "unexpected ']' at position 1"),
+    ] {
+        let error = Path::parse(input).unwrap_err();
+        assert_eq!(input, error.path());
+        assert_eq!(format!("Path '{}': {}", input, message), error.to_string());
+    }
+}
+
+#[test]
+fn test_get() {
+    let record = order();
+    assert_eq!(
+        Some(&Value::from("Vienna")),
+        record.get_path(&path("customer.address.city"))
+    );
+    assert_eq!(
+        Some(&Value::U8(20)),
+        record.get_path(&path("items[1].price"))
+    );
+    assert_eq!(Some(&Value::Char('y')), record.get_path(&path("tags[1]")));
+    assert_eq!(None, record.get_path(&path("items[3].price")));
+    assert_eq!(None, record.get_path(&path("customer.name.first")));
+    assert_eq!(None, record.get_path(&path("customer[0]")));
+    assert_eq!(None, record.get_path(&path("unknown")));
+
+    assert_eq!(
+        vec![&Value::U8(10), &Value::U8(20)],
+        record.select_path(&path("items[*].price"))
+    );
+    assert_eq!(
+        vec![&Value::from("John")],
+        record.select_path(&path("*.name"))
+    );
+    assert_eq!(3, record.select_path(&path("*")).len());
+}
+
+#[test]
+fn test_set() {
+    let mut record = order();
+    record
+        .set_path(&path("customer.address.zip"), "1010".into())
+        .unwrap();
+    record
+        .set_path(&path("items[0].price"), Value::U8(11))
+        .unwrap();
+    record.set_path(&path("tags[0]"), Value::Char('z')).unwrap();
+    record
+        .set_path(&path("meta.source.name"), "test".into())
+        .unwrap();
+    record
+        .set_path(&path("items[*].qty"), Value::U8(1))
+        .unwrap();
+
+    assert_eq!(
+        Some(&Value::from("1010")),
+        record.get_path(&path("customer.address.zip"))
+    );
+    assert_eq!(
+        vec![&Value::U8(11), &Value::U8(20)],
+        record.select_path(&path("items[*].price"))
+    );
+    assert_eq!(
+        vec![&Value::U8(1); 3],
+        record.select_path(&path("items[*].qty"))
+    );
+    assert_eq!(
+        Some(&Value::Collection(vec![Value::Char('z'), Value::Char('y')])),
+        record.get("tags")
+    );
+    assert_eq!(
+        Some(&Value::Record(Record::from(
+            json!({"source": {"name": "test"}})
+        ))),
+        record.get("meta")
+    );
+
+    // None is replaced by a record
+    let mut record = Record::from(json!({"a": null}));
+    record.set_path(&path("a.b"), Value::Bool(true)).unwrap();
+    assert_eq!(Record::from(json!({"a": {"b": true}})), record);
+}
+
+#[test]
+fn test_set_errors() {
+    let mut record = order();
+    let error = record
+        .set_path(&path("items[5].price"), Value::None)
+        .unwrap_err();
+    assert_eq!(
+        "Path 'items[5].price': index 5 out of bounds for 3 items",
+        error.to_string()
+    );
+    let error = record
+        .set_path(&path("customer.name.first"), Value::None)
+        .unwrap_err();
+    assert_eq!(
+        "Path 'customer.name.first': cannot apply 'first' to String",
+        error.to_string()
+    );
+    let error = record
+        .set_path(&path("unknown[0]"), Value::None)
+        .unwrap_err();
+    assert_eq!(
+        "Path 'unknown[0]': missing field 'unknown'",
+        error.to_string()
+    );
+    assert!(record.set_path(&path("tags.x"), Value::None).is_err());
+    assert!(record.set_path(&path("*[0]"), Value::None).is_err());
+}
+
+#[test]
+fn test_remove() {
+    let mut record = order();
+    assert_eq!(
+        vec![Value::U8(10), Value::U8(20)],
+        record.remove_path(&path("items[*].price"))
+    );
+    assert_eq!(vec![Value::Char('x')], record.remove_path(&path("tags[0]")));
+    assert_eq!(
+        vec![Value::from("John")],
+        record.remove_path(&path("customer.name"))
+    );
+    assert!(record.remove_path(&path("tags[5]")).is_empty());
+    assert!(record.remove_path(&path("customer.name.x")).is_empty());
+    assert_eq!(
+        Record::from(json!({
+            "customer": {"address": {"city": "Vienna"}},
+            "items": [{"sku": "a-1"}, {"sku": "b-2"}, {"sku": "c-3"}],
+            "tags": ["y"]
+        })),
+        record
+    );
+
+    assert_eq!(3, record.remove_path(&path("items[*]")).len());
+    assert_eq!(Some(&Value::Collection(vec![])), record.get("items"));
+    assert_eq!(3, record.remove_path(&path("*")).len());
+    assert!(record.fields().is_empty());
+}
+
+#[test]
+fn test_build() {
+    let path = Path::new("items")
+        .child(Segment::Index(1))
+        .child(Segment::Field("sku".to_string()));
+    assert_eq!("items[1].sku", path.to_string());
+    assert_eq!(&Segment::Field("sku".to_string()), path.last());
+    assert_eq!("items[1]", path.parent().unwrap().to_string());
+    assert_eq!(None, Path::new("items").parent());
+    assert_eq!(r"a\.b", Path::new("a.b").to_string());
+    assert_eq!(1, Path::new("a.b").segments().len());
+
+    let json = serde_json::to_string(&path).unwrap();
+    assert_eq!(r#""items[1].sku""#, json);
+    assert_eq!(path, serde_json::from_str::<Path>(&json).unwrap());
+    assert!(serde_json::from_str::<Path>(r#""a[""#).is_err());
+}
+
+#[test]
+fn test_get_mut() {
+    let mut record = order();
+    *record.get_path_mut(&path("items[1].price")).unwrap() = Value::U8(21);
+    assert_eq!(
+        Some(&Value::U8(21)),
+        record.get_path(&path("items[1].price"))
+    );
+    assert!(record.get_path_mut(&path("items[*].price")).is_none());
+    assert!(record.get_path_mut(&path("*")).is_none());
+    assert!(record.get_path_mut(&path("items[3]")).is_none());
+    assert!(record.get_path_mut(&path("customer.name.x")).is_none());
+}
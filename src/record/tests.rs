@@ -1,6 +1,9 @@
 use serde_json::json;
 
-use crate::value::Value;
+use crate::{
+    field::add_field,
+    value::{Value, shared::SharedBytes},
+};
 
 use super::*;
 
@@ -14,9 +17,11 @@ fn test_record_new() {
 fn test_record_field_by_name() {
     let mut record = Record::new();
     record
-        .fields
-        .push(Field::new_value("name", Value::String("Alice".to_string())));
-    record.fields.push(Field::new_value("age", Value::I32(30)));
+        .fields_as_mut()
+        .push(Field::new_value("name", Value::String("Alice".into())));
+    record
+        .fields_as_mut()
+        .push(Field::new_value("age", Value::I32(30)));
 
     assert!(record.field_by_name("unknown").is_none());
     assert!(record.field_by_name("name").is_some());
@@ -34,9 +39,11 @@ fn test_record_field_by_name() {
 fn test_record_fields() {
     let mut record = Record::new();
     record
-        .fields
-        .push(Field::new_value("name", Value::String("Alice".to_string())));
-    record.fields.push(Field::new_value("age", Value::I32(30)));
+        .fields_as_mut()
+        .push(Field::new_value("name", Value::String("Alice".into())));
+    record
+        .fields_as_mut()
+        .push(Field::new_value("age", Value::I32(30)));
 
     let fields = record.fields_as_mut();
     assert_eq!(fields.len(), 2);
@@ -48,7 +55,7 @@ fn test_record_fields() {
 fn test_record_fields_immutability() {
     let mut record = Record::new();
     record
-        .fields
+        .fields_as_mut()
         .push(Field::new_value("active", Value::Bool(true)));
 
     let fields = record.fields_as_mut();
@@ -62,13 +69,14 @@ fn test_record_fields_immutability() {
 #[test]
 fn test_record_multiple_fields() {
     let mut record = Record::new();
-    record.fields.push(Field::new_value(
-        "name",
-        Value::String("Charlie".to_string()),
-    ));
-    record.fields.push(Field::new_value("age", Value::I32(25)));
     record
-        .fields
+        .fields_as_mut()
+        .push(Field::new_value("name", Value::String("Charlie".into())));
+    record
+        .fields_as_mut()
+        .push(Field::new_value("age", Value::I32(25)));
+    record
+        .fields_as_mut()
         .push(Field::new_value("student", Value::Bool(false)));
 
     let fields = record.fields_as_mut();
@@ -119,7 +127,7 @@ fn test_record_from_json_single_field() {
     let mut expected_record = Record::new();
     expected_record.fields_as_mut().push(Field::new_value(
         "name",
-        crate::value::Value::String("John Doe".to_string()),
+        crate::value::Value::String("John Doe".into()),
     ));
 
     assert_eq!(record, expected_record);
@@ -153,7 +161,7 @@ fn test_record_from_json_multiple_fields() {
     ));
     expected_record.fields_as_mut().push(Field::new_value(
         "name",
-        crate::value::Value::String("John Doe".to_string()),
+        crate::value::Value::String("John Doe".into()),
     ));
 
     assert_eq!(record, expected_record);
@@ -176,7 +184,7 @@ fn test_record_from_json_nested_object() {
         .push(Field::new_value("age", crate::value::Value::U8(25)));
     nested_record.fields_as_mut().push(Field::new_value(
         "name",
-        crate::value::Value::String("Jane Smith".to_string()),
+        crate::value::Value::String("Jane Smith".into()),
     ));
 
     expected_record.fields_as_mut().push(Field::new_value(
@@ -186,3 +194,129 @@ fn test_record_from_json_nested_object() {
 
     assert_eq!(record, expected_record);
 }
+
+fn wide_record(count: usize) -> Record {
+    let mut record = Record::new();
+    for i in 0..count {
+        record.set(&format!("f{}", i), Value::USize(i));
+    }
+    record
+}
+
+#[test]
+fn test_record_map_api() {
+    let mut record = Record::new();
+    assert_eq!(None, record.set("a", Value::I32(1)));
+    assert_eq!(None, record.set("b", Value::I32(2)));
+    assert_eq!(Some(Value::I32(1)), record.set("a", Value::I32(3)));
+    assert_eq!(Some(&Value::I32(3)), record.get("a"));
+    assert!(record.contains("b"));
+    assert!(!record.contains("c"));
+
+    *record.get_mut("b").unwrap() = Value::Bool(true);
+    assert_eq!(Some(&Value::Bool(true)), record.get("b"));
+    assert!(record.get_mut("c").is_none());
+
+    assert_eq!(None, record.insert_at(1, "c", Value::U8(1)));
+    assert_eq!(Some(Value::I32(3)), record.insert_at(2, "a", Value::U8(2)));
+    let names: Vec<&str> = record.iter().map(|(name, _)| name).collect();
+    assert_eq!(vec!["c", "b", "a"], names);
+
+    assert_eq!(Some(Value::Bool(true)), record.remove("b"));
+    assert_eq!(None, record.remove("b"));
+    assert_eq!(Some(1), record.position("a"));
+}
+
+#[test]
+fn test_record_rename() {
+    let mut record = Record::new();
+    record.set("a", Value::I32(1));
+    record.set("b", Value::I32(2));
+
+    assert!(record.rename("a", "c"));
+    assert!(!record.rename("a", "d"));
+    assert!(!record.rename("c", "b"));
+    assert!(record.rename("b", "b"));
+    assert_eq!(Some(0), record.position("c"));
+    assert_eq!(Some(&Value::I32(1)), record.get("c"));
+    assert!(!record.contains("a"));
+}
+
+#[test]
+fn test_record_duplicate_names() {
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::I32(1));
+    add_field(record.fields_as_mut(), "a", Value::I32(2));
+
+    assert_eq!(Some(&Value::I32(1)), record.get("a"));
+    assert_eq!(Some(Value::I32(1)), record.set("a", Value::I32(3)));
+    assert_eq!(Some(Value::I32(3)), record.remove("a"));
+    assert_eq!(Some(&Value::I32(2)), record.get("a"));
+}
+
+#[test]
+fn test_record_index() {
+    let mut record = wide_record(100);
+    assert_eq!(Some(&Value::USize(42)), record.get("f42"));
+    assert_eq!(Some(99), record.position("f99"));
+
+    // The index follows all changes
+    record.set("new", Value::None);
+    assert_eq!(Some(100), record.position("new"));
+    record.remove("f0");
+    assert_eq!(Some(&Value::USize(42)), record.get("f42"));
+    assert_eq!(Some(41), record.position("f42"));
+    assert!(record.rename("f42", "answer"));
+    assert!(!record.contains("f42"));
+    assert_eq!(Some(41), record.position("answer"));
+    record.insert_at(0, "first", Value::None);
+    assert_eq!(Some(42), record.position("answer"));
+    record.fields_as_mut().truncate(10);
+    assert!(!record.contains("answer"));
+    add_field(record.fields_as_mut(), "answer", Value::None);
+    assert_eq!(Some(10), record.position("answer"));
+
+    // The index is not part of the equality
+    let mut other = wide_record(100);
+    assert!(other.contains("f1"));
+    assert_eq!(wide_record(100), other);
+    other.set("f1", Value::None);
+    assert_ne!(wide_record(100), other);
+}
+
+#[test]
+fn test_record_copy_on_write() {
+    let mut original = wide_record(20);
+    original.set("blob", Value::from(vec![0u8; 1024]));
+    assert_eq!(Some(20), original.position("blob"));
+
+    // A copy shares the fields and the blob
+    let mut copy = Record::copy(&original);
+    assert!(Record::ptr_eq(&original, &copy));
+    let blob = |record: &Record| {
+        record
+            .get("blob")
+            .unwrap()
+            .as_shared_bytes()
+            .unwrap()
+            .clone()
+    };
+    assert!(SharedBytes::ptr_eq(&blob(&original), &blob(&copy)));
+
+    // Changing the copy copies the fields, but still shares the blob
+    copy.set("f1", Value::None);
+    copy.remove("f0");
+    assert!(!Record::ptr_eq(&original, &copy));
+    assert!(SharedBytes::ptr_eq(&blob(&original), &blob(&copy)));
+    assert_eq!(Some(&Value::USize(1)), original.get("f1"));
+    assert_eq!(Some(&Value::None), copy.get("f1"));
+    assert_eq!(Some(20), original.position("blob"));
+    assert_eq!(Some(19), copy.position("blob"));
+
+    // Changing the blob of the copy copies only this blob
+    if let Some(Value::Blob(bytes)) = copy.get_mut("blob") {
+        bytes.make_mut()[0] = 1;
+    }
+    assert_eq!(0, blob(&original)[0]);
+    assert_eq!(1, blob(&copy)[0]);
+}
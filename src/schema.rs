@@ -0,0 +1,324 @@
+//! Module for the Schema of a [Record]
+//!
+//! A [Schema] declares the fields of a record: their names, [ValueType]s,
+//! nullability and default values. Fields of type [ValueType::Record] can
+//! have a nested [Schema], fields of type [ValueType::Collection] a
+//! [FieldDef] for their items.
+//!
+//! Importers can advertise the schema of their records with
+//! [crate::import::Importer::schema], so that exporters can e.g. create a
+//! table before the first record arrives. [Record::validate] checks a record
+//! against a schema. A value matches the declared type, if it widens to it
+//! without loss, see [ValueType::widens_to]. Sources without a declared
+//! schema can be sampled with an [infer::SchemaInferrer].
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     field::add_field,
+//!     record::Record,
+//!     schema::{FieldDef, Schema},
+//!     value::{Value, value_type::ValueType},
+//! };
+//!
+//! let schema = Schema::new(vec![
+//!     FieldDef::new("id", ValueType::I32),
+//!     FieldDef::new("name", ValueType::String).nullable(true),
+//! ]);
+//!
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::I32(1));
+//! add_field(record.fields_as_mut(), "name", Value::None);
+//! assert!(record.validate(&schema).is_ok());
+//!
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::U8(1));
+//! assert!(record.validate(&schema).is_ok());
+//!
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::I64(1));
+//! let violations = record.validate(&schema).unwrap_err();
+//! assert_eq!("Field 'id': expected I32, found I64", violations[0].to_string());
+//! ```
+use std::fmt::Display;
+
+use crate::{
+    field::Field,
+    record::Record,
+    value::{Value, value_type::ValueType},
+};
+
+pub mod infer;
+
+/// The definition of a field in a [Schema]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDef {
+    name: String,
+    value_type: ValueType,
+    nullable: bool,
+    default: Option<Value>,
+    schema: Option<Schema>,
+    items: Option<Box<FieldDef>>,
+}
+
+impl FieldDef {
+    /// Creates a non-nullable field definition without default value
+    ///
+    /// # Arguments
+    /// * `name` - The name of the field
+    /// * `value_type` - The [ValueType] of the field
+    pub fn new(name: &str, value_type: ValueType) -> Self {
+        Self {
+            name: name.to_string(),
+            value_type,
+            nullable: false,
+            default: None,
+            schema: None,
+            items: None,
+        }
+    }
+
+    /// Sets, if the field may be missing or [Value::None]
+    pub fn nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    /// Sets the default value for a missing field
+    pub fn default_value(mut self, value: Value) -> Self {
+        self.default = Some(value);
+        self
+    }
+
+    /// Sets the nested schema of a field of type [ValueType::Record]
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    /// Sets the definition of the items of a field of type
+    /// [ValueType::Collection]. The name of the item definition is ignored
+    pub fn items(mut self, items: FieldDef) -> Self {
+        self.items = Some(Box::new(items));
+        self
+    }
+
+    /// Returns the name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the [ValueType] of the field
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+
+    /// Returns true, if the field may be missing or [Value::None]
+    pub fn is_nullable(&self) -> bool {
+        self.nullable
+    }
+
+    /// Returns the default value for a missing field
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    /// Returns the nested schema of a field of type [ValueType::Record]
+    pub fn nested_schema(&self) -> Option<&Schema> {
+        self.schema.as_ref()
+    }
+
+    /// Returns the definition of the items of a field of type
+    /// [ValueType::Collection]
+    pub fn item_def(&self) -> Option<&FieldDef> {
+        self.items.as_deref()
+    }
+}
+
+/// The schema of a [Record]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Schema {
+    fields: Vec<FieldDef>,
+    additional_fields: bool,
+}
+
+impl Schema {
+    /// Creates a schema with the given field definitions. Fields, that are not
+    /// defined, are allowed in the records
+    pub fn new(fields: Vec<FieldDef>) -> Self {
+        Self {
+            fields,
+            additional_fields: true,
+        }
+    }
+
+    /// Sets, if records may contain fields, that are not defined in the schema
+    pub fn additional_fields(mut self, allowed: bool) -> Self {
+        self.additional_fields = allowed;
+        self
+    }
+
+    /// Returns the field definitions
+    pub fn fields(&self) -> &[FieldDef] {
+        &self.fields
+    }
+
+    /// Returns a field definition by name
+    pub fn field(&self, name: &str) -> Option<&FieldDef> {
+        self.fields.iter().find(|def| def.name == name)
+    }
+
+    /// Returns true, if records may contain fields, that are not defined in
+    /// the schema
+    pub fn allows_additional_fields(&self) -> bool {
+        self.additional_fields
+    }
+}
+
+/// The kind of a schema [Violation]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    /// A non-nullable field without default value is missing
+    MissingField,
+    /// A non-nullable field is [Value::None]
+    UnexpectedNull,
+    /// The value has a different [ValueType]
+    TypeMismatch {
+        expected: ValueType,
+        found: ValueType,
+    },
+    /// The field is not defined in a schema, that does not allow additional
+    /// fields
+    UnknownField,
+}
+
+/// A violation of a [Schema] found by [Record::validate]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// The path of the field, e.g. `address.city` or `items[2]`
+    pub path: String,
+    /// What is wrong with the field
+    pub kind: ViolationKind,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Field '{}': ", self.path)?;
+        match &self.kind {
+            ViolationKind::MissingField => write!(f, "missing"),
+            ViolationKind::UnexpectedNull => write!(f, "must not be None"),
+            ViolationKind::TypeMismatch { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            ViolationKind::UnknownField => write!(f, "not defined in the schema"),
+        }
+    }
+}
+
+impl Record {
+    /// Validates this record against a [Schema]
+    ///
+    /// Returns all [Violation]s, including those of nested records and
+    /// collection items
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<Violation>> {
+        let mut violations = Vec::new();
+        validate_record(self, schema, "", &mut violations);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Adds all missing fields, that have a default value in the [Schema]
+    pub fn apply_defaults(&mut self, schema: &Schema) {
+        for def in schema.fields() {
+            if let Some(default) = def.default()
+                && self.field_by_name(def.name()).is_none()
+            {
+                self.fields_as_mut()
+                    .push(Field::new_value(def.name(), default.clone()));
+            }
+        }
+    }
+}
+
+fn join(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", prefix, name)
+    }
+}
+
+fn validate_record(
+    record: &Record,
+    schema: &Schema,
+    prefix: &str,
+    violations: &mut Vec<Violation>,
+) {
+    for def in schema.fields() {
+        let path = join(prefix, def.name());
+        match record.field_by_name(def.name()) {
+            Some(field) => validate_value(field.value_as_ref(), def, path, violations),
+            None if def.is_nullable() || def.default().is_some() => {}
+            None => violations.push(Violation {
+                path,
+                kind: ViolationKind::MissingField,
+            }),
+        }
+    }
+
+    if !schema.allows_additional_fields() {
+        for field in record.fields() {
+            if schema.field(field.name()).is_none() {
+                violations.push(Violation {
+                    path: join(prefix, field.name()),
+                    kind: ViolationKind::UnknownField,
+                });
+            }
+        }
+    }
+}
+
+fn validate_value(value: &Value, def: &FieldDef, path: String, violations: &mut Vec<Violation>) {
+    if let Value::None = value {
+        if !def.is_nullable() {
+            violations.push(Violation {
+                path,
+                kind: ViolationKind::UnexpectedNull,
+            });
+        }
+        return;
+    }
+
+    if !value.value_type().widens_to(def.value_type()) {
+        violations.push(Violation {
+            path,
+            kind: ViolationKind::TypeMismatch {
+                expected: def.value_type(),
+                found: value.value_type(),
+            },
+        });
+        return;
+    }
+
+    match value {
+        Value::Record(record) => {
+            if let Some(schema) = def.nested_schema() {
+                validate_record(record, schema, &path, violations);
+            }
+        }
+        Value::Collection(items) => {
+            if let Some(item_def) = def.item_def() {
+                for (index, item) in items.iter().enumerate() {
+                    validate_value(item, item_def, format!("{}[{}]", path, index), violations);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests;
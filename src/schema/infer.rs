@@ -0,0 +1,236 @@
+//! Inference of a [Schema] from a sample of records
+//!
+//! Sources without a declared schema (e.g. JSON) can be sampled with a
+//! [SchemaInferrer]. It takes records one at a time and keeps the widest
+//! compatible [ValueType] per field:
+//! * Integers are widened to a common integer type, see
+//!   [ValueType::common_integer]
+//! * Integers and floats become [ValueType::F64], unless both are
+//!   [ValueType::F32]
+//! * Integers and decimals become [ValueType::Decimal]
+//! * Chars and strings become [ValueType::String]
+//! * Nested records are merged field by field, collection items are widened
+//! * All other combinations fall back to [ValueType::String]
+//!
+//! A field is nullable, if it is missing in some records or was
+//! [Value::None]. A field, that was only ever [Value::None], has the type
+//! [ValueType::None].
+//!
+//! The records of the sample validate against the inferred schema with
+//! [Record::validate](crate::record::Record::validate), unless a field fell
+//! back to [ValueType::String] or became a [ValueType::F64] for decimals or
+//! integers wider than 32 bits, since these values do not widen without
+//! loss, see [ValueType::widens_to].
+//!
+//! # Example
+//! ```
+//! use model::{
+//!     field::add_field,
+//!     record::Record,
+//!     schema::infer::SchemaInferrer,
+//!     value::{Value, value_type::ValueType},
+//! };
+//!
+//! let mut inferrer = SchemaInferrer::new();
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::U8(1));
+//! inferrer.add(&record);
+//!
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::I32(-1000));
+//! add_field(record.fields_as_mut(), "name", "John".into());
+//! inferrer.add(&record);
+//!
+//! let schema = inferrer.schema();
+//! assert_eq!(ValueType::I32, schema.field("id").unwrap().value_type());
+//! assert!(!schema.field("id").unwrap().is_nullable());
+//! assert!(schema.field("name").unwrap().is_nullable());
+//! ```
+use std::collections::HashMap;
+
+use crate::{
+    BoxedError,
+    import::{Flow, RecordHandler},
+    record::Record,
+    value::{Value, value_type::ValueType},
+};
+
+use super::{FieldDef, Schema};
+
+/// The name of the [FieldDef] for the items of a collection
+const ITEM_NAME: &str = "item";
+
+/// The values observed for a field or the items of a collection
+#[derive(Debug, Clone, Default)]
+struct Observed {
+    /// The widest type so far, [None] if only [Value::None] was seen
+    value_type: Option<ValueType>,
+    nulls: usize,
+    nested: Option<SchemaInferrer>,
+    items: Option<Box<Observed>>,
+}
+
+impl Observed {
+    fn add(&mut self, value: &Value) {
+        if let Value::None = value {
+            self.nulls += 1;
+            return;
+        }
+
+        let value_type = value.value_type();
+        self.value_type = Some(match self.value_type {
+            Some(current) => widen(current, value_type),
+            None => value_type,
+        });
+
+        match value {
+            Value::Record(record) => self.nested.get_or_insert_default().add(record),
+            Value::Collection(items) => {
+                let observed = self.items.get_or_insert_default();
+                for item in items {
+                    observed.add(item);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn field_def(&self, name: &str, nullable: bool) -> FieldDef {
+        let value_type = self.value_type.unwrap_or(ValueType::None);
+        let mut def = FieldDef::new(name, value_type).nullable(nullable || self.nulls > 0);
+        match (value_type, &self.nested, &self.items) {
+            (ValueType::Record, Some(nested), _) => def = def.schema(nested.schema()),
+            (ValueType::Collection, _, Some(items)) if items.value_type.is_some() => {
+                def = def.items(items.field_def(ITEM_NAME, false))
+            }
+            _ => {}
+        }
+        def
+    }
+}
+
+/// Returns the narrowest type, that can hold values of both types
+fn widen(a: ValueType, b: ValueType) -> ValueType {
+    use ValueType::*;
+
+    if a == b {
+        return a;
+    }
+    if let Some(integer) = a.common_integer(b) {
+        return integer;
+    }
+    match (a, b) {
+        (Decimal, other) | (other, Decimal) if other.is_integer() => Decimal,
+        (Decimal, F32 | F64) | (F32 | F64, Decimal) => F64,
+        (a, b) if a.is_numeric() && b.is_numeric() => F64,
+        _ => String,
+    }
+}
+
+/// Statistics about a field seen by a [SchemaInferrer]
+#[derive(Debug, Clone)]
+pub struct FieldStats {
+    name: String,
+    present: usize,
+    observed: Observed,
+}
+
+impl FieldStats {
+    /// Returns the name of the field
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the number of records, that contained the field
+    pub fn present(&self) -> usize {
+        self.present
+    }
+
+    /// Returns the number of records, where the field was [Value::None]
+    pub fn nulls(&self) -> usize {
+        self.observed.nulls
+    }
+
+    /// Returns the widest [ValueType] seen so far or [None], if the field was
+    /// only [Value::None]
+    pub fn value_type(&self) -> Option<ValueType> {
+        self.observed.value_type
+    }
+}
+
+/// Infers a [Schema] from the records passed to [SchemaInferrer::add]
+///
+/// It is also a [RecordHandler], so it can be passed directly to
+/// [crate::import::Importer::read].
+#[derive(Debug, Clone, Default)]
+pub struct SchemaInferrer {
+    fields: Vec<FieldStats>,
+    /// The position of each field name in `fields`
+    index: HashMap<String, usize>,
+    records: usize,
+}
+
+impl SchemaInferrer {
+    /// Creates an inferrer, that has not seen any record yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a record to the sample
+    pub fn add(&mut self, record: &Record) {
+        self.records += 1;
+        for field in record.fields() {
+            let stats = match self.index.get(field.name()) {
+                Some(&index) => &mut self.fields[index],
+                None => {
+                    self.index
+                        .insert(field.name().to_string(), self.fields.len());
+                    self.fields.push(FieldStats {
+                        name: field.name().to_string(),
+                        present: 0,
+                        observed: Observed::default(),
+                    });
+                    self.fields.last_mut().unwrap()
+                }
+            };
+            stats.present += 1;
+            stats.observed.add(field.value_as_ref());
+        }
+    }
+
+    /// Returns the number of records added so far
+    pub fn records(&self) -> usize {
+        self.records
+    }
+
+    /// Returns the statistics of all fields in the order of their first
+    /// occurrence
+    pub fn field_stats(&self) -> &[FieldStats] {
+        &self.fields
+    }
+
+    /// Returns the [Schema] inferred from the records added so far. The
+    /// fields are in the order of their first occurrence
+    pub fn schema(&self) -> Schema {
+        Schema::new(
+            self.fields
+                .iter()
+                .map(|stats| {
+                    stats
+                        .observed
+                        .field_def(&stats.name, stats.present < self.records)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl RecordHandler for SchemaInferrer {
+    fn handle_record(&mut self, record: &mut Record) -> Result<Flow, BoxedError> {
+        self.add(record);
+        Ok(Flow::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,185 @@
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::{
+    field::add_field,
+    import::RecordHandler,
+    record::Record,
+    schema::{FieldDef, Schema},
+    value::{Value, value_type::ValueType},
+};
+
+use super::{SchemaInferrer, widen};
+
+fn record(fields: Vec<(&str, Value)>) -> Record {
+    let mut record = Record::new();
+    for (name, value) in fields {
+        add_field(record.fields_as_mut(), name, value);
+    }
+    record
+}
+
+#[test]
+fn test_widen() {
+    assert_eq!(ValueType::U16, widen(ValueType::U8, ValueType::U16));
+    assert_eq!(ValueType::I32, widen(ValueType::U16, ValueType::I32));
+    assert_eq!(ValueType::I16, widen(ValueType::U8, ValueType::I8));
+    assert_eq!(ValueType::F32, widen(ValueType::F32, ValueType::F32));
+    assert_eq!(ValueType::F64, widen(ValueType::F32, ValueType::I8));
+    assert_eq!(
+        ValueType::Decimal,
+        widen(ValueType::U64, ValueType::Decimal)
+    );
+    assert_eq!(ValueType::F64, widen(ValueType::Decimal, ValueType::F32));
+    assert_eq!(ValueType::String, widen(ValueType::Char, ValueType::String));
+    assert_eq!(ValueType::String, widen(ValueType::Date, ValueType::I32));
+    assert_eq!(ValueType::String, widen(ValueType::Record, ValueType::Bool));
+}
+
+#[test]
+fn test_empty() {
+    let inferrer = SchemaInferrer::new();
+    assert_eq!(0, inferrer.records());
+    assert_eq!(Schema::new(vec![]), inferrer.schema());
+}
+
+#[test]
+fn test_infer() {
+    let mut inferrer = SchemaInferrer::new();
+    inferrer.add(&record(vec![
+        ("id", Value::U8(1)),
+        ("price", Value::Decimal(Decimal::new(199, 2))),
+        ("code", Value::Char('a')),
+        ("comment", Value::None),
+    ]));
+    inferrer.add(&record(vec![
+        ("id", Value::U16(1000)),
+        ("price", Value::U32(5)),
+        ("code", "abc".into()),
+        ("comment", Value::None),
+        ("extra", Value::Bool(true)),
+    ]));
+    inferrer.add(&record(vec![
+        ("id", Value::I32(-1)),
+        ("price", Value::None),
+        ("code", "x".into()),
+    ]));
+
+    assert_eq!(3, inferrer.records());
+    assert_eq!(
+        Schema::new(vec![
+            FieldDef::new("id", ValueType::I32),
+            FieldDef::new("price", ValueType::Decimal).nullable(true),
+            FieldDef::new("code", ValueType::String),
+            FieldDef::new("comment", ValueType::None).nullable(true),
+            FieldDef::new("extra", ValueType::Bool).nullable(true),
+        ]),
+        inferrer.schema()
+    );
+
+    let stats = inferrer.field_stats();
+    assert_eq!("price", stats[1].name());
+    assert_eq!(3, stats[1].present());
+    assert_eq!(1, stats[1].nulls());
+    assert_eq!(Some(ValueType::Decimal), stats[1].value_type());
+    assert_eq!(None, stats[3].value_type());
+    assert_eq!(1, stats[4].present());
+}
+
+#[test]
+fn test_nested() {
+    let mut inferrer = SchemaInferrer::new();
+    inferrer.add(&Record::from(json!({
+        "address": {"city": "Vienna", "zip": 1010},
+        "tags": ["ab", "b"],
+    })));
+    inferrer.add(&Record::from(json!({
+        "address": {"city": "Graz", "zip": 80100, "street": null},
+        "tags": ["c", null],
+    })));
+    inferrer.add(&Record::from(json!({"address": null, "tags": []})));
+
+    assert_eq!(
+        Schema::new(vec![
+            FieldDef::new("address", ValueType::Record)
+                .nullable(true)
+                .schema(Schema::new(vec![
+                    FieldDef::new("city", ValueType::String),
+                    FieldDef::new("zip", ValueType::U32),
+                    FieldDef::new("street", ValueType::None).nullable(true),
+                ])),
+            FieldDef::new("tags", ValueType::Collection)
+                .items(FieldDef::new("item", ValueType::String).nullable(true)),
+        ]),
+        inferrer.schema()
+    );
+}
+
+#[test]
+fn test_handler() {
+    let mut inferrer = SchemaInferrer::new();
+    let mut record = record(vec![("id", Value::I64(1))]);
+    assert!(inferrer.handle_record(&mut record).unwrap().is_continue());
+    assert!(inferrer.handle_record(&mut record).unwrap().is_continue());
+    assert_eq!(2, inferrer.records());
+
+    let schema = inferrer.schema();
+    assert_eq!(ValueType::I64, schema.field("id").unwrap().value_type());
+    assert!(record.validate(&schema).is_ok());
+}
+
+#[test]
+fn test_wide_records() {
+    let names: Vec<String> = (0..1000).map(|i| format!("f{}", i)).collect();
+    let mut inferrer = SchemaInferrer::new();
+    inferrer.add(&record(
+        names.iter().map(|n| (n.as_str(), Value::U8(1))).collect(),
+    ));
+    // The same fields in reverse order and one new field
+    let mut reversed: Vec<(&str, Value)> = names
+        .iter()
+        .rev()
+        .map(|n| (n.as_str(), Value::I32(-1)))
+        .collect();
+    reversed.push(("new", Value::Bool(true)));
+    inferrer.add(&record(reversed));
+
+    let stats = inferrer.field_stats();
+    assert_eq!(1001, stats.len());
+    assert_eq!("f0", stats[0].name());
+    assert_eq!("new", stats[1000].name());
+    assert!(stats[..1000].iter().all(|s| s.present() == 2));
+    assert_eq!(Some(ValueType::I32), stats[999].value_type());
+}
+
+#[test]
+fn test_validate_sample() {
+    let sample = vec![
+        record(vec![
+            ("id", Value::U8(1)),
+            ("delta", Value::I8(-1)),
+            ("price", Value::Decimal(Decimal::new(199, 2))),
+            ("ratio", Value::F32(0.5)),
+        ]),
+        record(vec![
+            ("id", Value::I32(-1000)),
+            ("delta", Value::U8(200)),
+            ("price", Value::U32(5)),
+            ("ratio", Value::I16(2)),
+        ]),
+        Record::from(json!({"address": {"zip": 1010}, "tags": ["ab", "b"]})),
+        Record::from(json!({"address": {"zip": 80100}, "tags": ["c", null]})),
+    ];
+    let mut inferrer = SchemaInferrer::new();
+    for record in &sample {
+        inferrer.add(record);
+    }
+
+    // The inferred schema accepts the records it was inferred from
+    let schema = inferrer.schema();
+    assert_eq!(ValueType::I32, schema.field("id").unwrap().value_type());
+    assert_eq!(ValueType::I16, schema.field("delta").unwrap().value_type());
+    for record in &sample {
+        assert_eq!(Ok(()), record.validate(&schema));
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    field::add_field,
+    record::Record,
+    value::{Value, value_type::ValueType},
+};
+
+use super::{FieldDef, Schema, Violation, ViolationKind};
+
+fn address_schema() -> Schema {
+    Schema::new(vec![
+        FieldDef::new("city", ValueType::String),
+        FieldDef::new("zip", ValueType::String).nullable(true),
+    ])
+}
+
+fn person_schema() -> Schema {
+    Schema::new(vec![
+        FieldDef::new("id", ValueType::I32),
+        FieldDef::new("name", ValueType::String),
+        FieldDef::new("active", ValueType::Bool).default_value(Value::Bool(true)),
+        FieldDef::new("address", ValueType::Record)
+            .nullable(true)
+            .schema(address_schema()),
+        FieldDef::new("tags", ValueType::Collection)
+            .nullable(true)
+            .items(FieldDef::new("tag", ValueType::String)),
+    ])
+}
+
+fn person() -> Record {
+    let mut address = Record::new();
+    add_field(address.fields_as_mut(), "city", "Vienna".into());
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "id", Value::I32(1));
+    add_field(record.fields_as_mut(), "name", "John".into());
+    add_field(record.fields_as_mut(), "address", Value::Record(address));
+    add_field(
+        record.fields_as_mut(),
+        "tags",
+        Value::Collection(vec!["a".into(), "b".into()]),
+    );
+    record
+}
+
+#[test]
+fn test_field_def() {
+    let def = FieldDef::new("tags", ValueType::Collection)
+        .nullable(true)
+        .default_value(Value::Collection(vec![]))
+        .items(FieldDef::new("tag", ValueType::String));
+    assert_eq!("tags", def.name());
+    assert_eq!(ValueType::Collection, def.value_type());
+    assert!(def.is_nullable());
+    assert_eq!(Some(&Value::Collection(vec![])), def.default());
+    assert_eq!(ValueType::String, def.item_def().unwrap().value_type());
+    assert!(def.nested_schema().is_none());
+
+    let schema = person_schema();
+    assert_eq!(5, schema.fields().len());
+    assert!(schema.field("address").unwrap().nested_schema().is_some());
+    assert!(schema.field("unknown").is_none());
+    assert!(schema.allows_additional_fields());
+}
+
+#[test]
+fn test_valid() {
+    assert_eq!(Ok(()), person().validate(&person_schema()));
+
+    // Nullable fields can be None or missing
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "id", Value::I32(1));
+    add_field(record.fields_as_mut(), "name", "John".into());
+    add_field(record.fields_as_mut(), "address", Value::None);
+    add_field(record.fields_as_mut(), "extra", Value::U8(1));
+    assert_eq!(Ok(()), record.validate(&person_schema()));
+
+    // Values, that widen without loss, match the declared type
+    let mut record = person();
+    record.set("id", Value::U16(1));
+    record.set("name", Value::Char('J'));
+    assert_eq!(Ok(()), record.validate(&person_schema()));
+}
+
+#[test]
+fn test_violations() {
+    let mut address = Record::new();
+    add_field(address.fields_as_mut(), "zip", Value::U16(1010));
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "id", Value::U32(1));
+    add_field(record.fields_as_mut(), "active", Value::None);
+    add_field(record.fields_as_mut(), "address", Value::Record(address));
+    add_field(
+        record.fields_as_mut(),
+        "tags",
+        Value::Collection(vec!["a".into(), Value::I32(2)]),
+    );
+
+    let violations = record.validate(&person_schema()).unwrap_err();
+    assert_eq!(
+        vec![
+            Violation {
+                path: "id".to_string(),
+                kind: ViolationKind::TypeMismatch {
+                    expected: ValueType::I32,
+                    found: ValueType::U32
+                }
+            },
+            Violation {
+                path: "name".to_string(),
+                kind: ViolationKind::MissingField
+            },
+            Violation {
+                path: "active".to_string(),
+                kind: ViolationKind::UnexpectedNull
+            },
+            Violation {
+                path: "address.city".to_string(),
+                kind: ViolationKind::MissingField
+            },
+            Violation {
+                path: "address.zip".to_string(),
+                kind: ViolationKind::TypeMismatch {
+                    expected: ValueType::String,
+                    found: ValueType::U16
+                }
+            },
+            Violation {
+                path: "tags[1]".to_string(),
+                kind: ViolationKind::TypeMismatch {
+                    expected: ValueType::String,
+                    found: ValueType::I32
+                }
+            },
+        ],
+        violations
+    );
+
+    let messages: Vec<String> = violations.iter().map(|v| v.to_string()).collect();
+    assert_eq!("Field 'name': missing", messages[1]);
+    assert_eq!("Field 'active': must not be None", messages[2]);
+    assert_eq!("Field 'tags[1]': expected String, found I32", messages[5]);
+}
+
+#[test]
+fn test_additional_fields() {
+    let schema = address_schema().additional_fields(false);
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "city", "Vienna".into());
+    add_field(record.fields_as_mut(), "country", "AT".into());
+
+    let violations = record.validate(&schema).unwrap_err();
+    assert_eq!(1, violations.len());
+    assert_eq!(
+        "Field 'country': not defined in the schema",
+        violations[0].to_string()
+    );
+}
+
+#[test]
+fn test_apply_defaults() {
+    let mut record = person();
+    assert!(record.field_by_name("active").is_none());
+    record.apply_defaults(&person_schema());
+    assert_eq!(
+        Value::Bool(true),
+        record.field_by_name("active").unwrap().value()
+    );
+
+    // Existing values are kept
+    let mut record = person();
+    add_field(record.fields_as_mut(), "active", Value::Bool(false));
+    record.apply_defaults(&person_schema());
+    assert_eq!(
+        Value::Bool(false),
+        record.field_by_name("active").unwrap().value()
+    );
+    assert_eq!(5, record.fields().len());
+}
@@ -0,0 +1,517 @@
+//! Module for the serde support of [Value], [Field] and [Record]
+//!
+//! There are two representations:
+//! * The natural representation is used by the [Serialize] and [Deserialize]
+//!   implementations of [Value], [Field] and [Record]. A record is a map of
+//!   field names to values, a collection is a sequence and all other values
+//!   are written as the closest serde data type. Deserializing it needs a
+//!   self-describing format (e.g. JSON) and does not restore the exact
+//!   variants (e.g. every unsigned integer becomes a [Value::U64]).
+//! * The self-describing representation is opt-in with the [Tagged] wrapper.
+//!   Every value is written as an enum variant with the name of the [Value]
+//!   variant, and records keep the order of their fields. It restores every
+//!   value exactly. Human-readable formats like JSON get the tagged JSON
+//!   encoding of [Value::to_tagged_json], so [Value::I128], [Value::U128] and
+//!   [Value::Decimal] are strings there. Non-self-describing formats like
+//!   bincode get an enum with the native types, in which a [Value::Json]
+//!   document is written as its JSON text.
+//!
+//! # Example
+//! ```
+//! use model::{record::Record, serialization::Tagged, field::add_field, value::Value};
+//!
+//! let mut record = Record::new();
+//! add_field(record.fields_as_mut(), "id", Value::U16(42));
+//!
+//! // Natural representation
+//! assert_eq!(r#"{"id":42}"#, serde_json::to_string(&record).unwrap());
+//!
+//! // Self-describing representation
+//! let json = serde_json::to_string(&Tagged(&record)).unwrap();
+//! assert_eq!(r#"[{"name":"id","value":{"U16":42}}]"#, json);
+//! let Tagged(restored) = serde_json::from_str::<Tagged<Record>>(&json).unwrap();
+//! assert_eq!(record, restored);
+//! ```
+use std::fmt;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::{SerializeMap, SerializeSeq, SerializeStruct},
+};
+use serde_json::Value as JsonValue;
+
+use crate::{
+    field::Field,
+    record::Record,
+    value::{
+        Value,
+        json::{DATE_FORMAT, DATE_TIME_FORMAT, DATE_TIME_TZ_FORMAT, TIME_FORMAT},
+        parse::parse_duration,
+    },
+};
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Bool(v) => serializer.serialize_bool(*v),
+            Value::Char(v) => serializer.serialize_char(*v),
+            Value::I8(v) => serializer.serialize_i8(*v),
+            Value::I16(v) => serializer.serialize_i16(*v),
+            Value::I32(v) => serializer.serialize_i32(*v),
+            Value::I64(v) => serializer.serialize_i64(*v),
+            Value::I128(v) => serializer.serialize_i128(*v),
+            Value::ISize(v) => serializer.serialize_i64(*v as i64),
+            Value::U8(v) => serializer.serialize_u8(*v),
+            Value::U16(v) => serializer.serialize_u16(*v),
+            Value::U32(v) => serializer.serialize_u32(*v),
+            Value::U64(v) => serializer.serialize_u64(*v),
+            Value::U128(v) => serializer.serialize_u128(*v),
+            Value::USize(v) => serializer.serialize_u64(*v as u64),
+            Value::F32(v) => serializer.serialize_f32(*v),
+            Value::F64(v) => serializer.serialize_f64(*v),
+            Value::Decimal(v) => serializer.collect_str(v),
+            Value::String(v) => serializer.serialize_str(v),
+            Value::Blob(v) => serializer.serialize_bytes(v),
+            Value::Date(v) => serializer.collect_str(&v.format(DATE_FORMAT)),
+            Value::DateTime(v) => serializer.collect_str(&v.format(DATE_TIME_FORMAT)),
+            Value::Time(v) => serializer.collect_str(&v.format(TIME_FORMAT)),
+            Value::DateTimeTz(v) => serializer.collect_str(&v.format(DATE_TIME_TZ_FORMAT)),
+            Value::Duration(v) => serializer.collect_str(v),
+            Value::Uuid(v) => serializer.collect_str(v),
+            Value::Json(v) => v.serialize(serializer),
+            Value::Collection(v) => serializer.collect_seq(v),
+            Value::Record(v) => v.serialize(serializer),
+            Value::None => serializer.serialize_none(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Visitor for the natural representation of a [Value]
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::I64(v))
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Value, E> {
+        Ok(Value::I128(v))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::U64(v))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Value, E> {
+        Ok(Value::U128(v))
+    }
+
+    fn visit_f32<E: de::Error>(self, v: f32) -> Result<Value, E> {
+        Ok(Value::F32(v))
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::F64(v))
+    }
+
+    fn visit_char<E: de::Error>(self, v: char) -> Result<Value, E> {
+        Ok(Value::Char(v))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Value, E> {
+        Ok(Value::Blob(v.into()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::from(v))
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            values.push(value);
+        }
+        Ok(Value::Collection(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Value, A::Error> {
+        RecordVisitor.visit_map(map).map(Value::Record)
+    }
+}
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields().len()))?;
+        for field in self.fields() {
+            map.serialize_entry(field.name(), field.value_as_ref())?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Record {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(RecordVisitor)
+    }
+}
+
+/// Visitor for the natural representation of a [Record]
+struct RecordVisitor;
+
+impl<'de> Visitor<'de> for RecordVisitor {
+    type Value = Record;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of field names to values")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Record, A::Error> {
+        let mut record = Record::new();
+        while let Some((name, value)) = map.next_entry::<String, Value>()? {
+            record.fields_as_mut().push(Field::new_value(&name, value));
+        }
+        Ok(record)
+    }
+}
+
+/// A wrapper to opt-in the self-describing representation
+///
+/// [Tagged] can be serialized for [Value], [Record] and references to them,
+/// and deserialized for [Value] and [Record]. With a human-readable format
+/// the output is the same as [Value::to_tagged_json] and
+/// [Record::to_tagged_json]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tagged<T>(pub T);
+
+/// The names of the variants of the self-describing representation, in the
+/// order of [Repr]
+const VARIANTS: &[&str] = &[
+    "Bool",
+    "Char",
+    "I8",
+    "I16",
+    "I32",
+    "I64",
+    "I128",
+    "ISize",
+    "U8",
+    "U16",
+    "U32",
+    "U64",
+    "U128",
+    "USize",
+    "F32",
+    "F64",
+    "Decimal",
+    "String",
+    "Blob",
+    "Date",
+    "DateTime",
+    "Time",
+    "DateTimeTz",
+    "Duration",
+    "Uuid",
+    "Json",
+    "Collection",
+    "Record",
+    "None",
+];
+
+/// Serializes `content` as the variant of `value` of the self-describing
+/// representation
+fn serialize_variant<S, T>(serializer: S, value: &Value, content: &T) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + ?Sized,
+{
+    let name = value.type_name();
+    let index = VARIANTS
+        .iter()
+        .position(|variant| *variant == name)
+        .unwrap_or_default() as u32;
+    serializer.serialize_newtype_variant("Value", index, VARIANTS[index as usize], content)
+}
+
+impl Serialize for Tagged<&Value> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value = self.0;
+        if serializer.is_human_readable() {
+            return value.to_tagged_json().serialize(serializer);
+        }
+        match value {
+            Value::Bool(v) => serialize_variant(serializer, value, v),
+            Value::Char(v) => serialize_variant(serializer, value, v),
+            Value::I8(v) => serialize_variant(serializer, value, v),
+            Value::I16(v) => serialize_variant(serializer, value, v),
+            Value::I32(v) => serialize_variant(serializer, value, v),
+            Value::I64(v) => serialize_variant(serializer, value, v),
+            Value::I128(v) => serialize_variant(serializer, value, v),
+            Value::ISize(v) => serialize_variant(serializer, value, &(*v as i64)),
+            Value::U8(v) => serialize_variant(serializer, value, v),
+            Value::U16(v) => serialize_variant(serializer, value, v),
+            Value::U32(v) => serialize_variant(serializer, value, v),
+            Value::U64(v) => serialize_variant(serializer, value, v),
+            Value::U128(v) => serialize_variant(serializer, value, v),
+            Value::USize(v) => serialize_variant(serializer, value, &(*v as u64)),
+            Value::F32(v) => serialize_variant(serializer, value, v),
+            Value::F64(v) => serialize_variant(serializer, value, v),
+            Value::Decimal(v) => serialize_variant(serializer, value, &v.to_string()),
+            Value::String(v) => serialize_variant(serializer, value, v.as_str()),
+            Value::Blob(v) => serialize_variant(serializer, value, v.as_slice()),
+            Value::Date(v) => {
+                serialize_variant(serializer, value, &v.format(DATE_FORMAT).to_string())
+            }
+            Value::DateTime(v) => {
+                serialize_variant(serializer, value, &v.format(DATE_TIME_FORMAT).to_string())
+            }
+            Value::Time(v) => {
+                serialize_variant(serializer, value, &v.format(TIME_FORMAT).to_string())
+            }
+            Value::DateTimeTz(v) => serialize_variant(
+                serializer,
+                value,
+                &v.format(DATE_TIME_TZ_FORMAT).to_string(),
+            ),
+            Value::Duration(v) => serialize_variant(serializer, value, &v.to_string()),
+            Value::Uuid(v) => serialize_variant(serializer, value, &v.to_string()),
+            Value::Json(v) => serialize_variant(serializer, value, &v.to_string()),
+            Value::Collection(v) => serialize_variant(serializer, value, &TaggedValues(v)),
+            Value::Record(v) => serialize_variant(serializer, value, &Tagged(v)),
+            Value::None => serialize_variant(serializer, value, &()),
+        }
+    }
+}
+
+impl Serialize for Tagged<Value> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Tagged(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tagged<Value> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let json = JsonValue::deserialize(deserializer)?;
+            return Value::from_tagged_json(&json)
+                .map(Tagged)
+                .map_err(de::Error::custom);
+        }
+        let repr = Repr::deserialize(deserializer)?;
+        value_from_repr(repr).map(Tagged).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Tagged<&Record> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            return self.0.to_tagged_json().serialize(serializer);
+        }
+        let fields = self.0.fields();
+        let mut seq = serializer.serialize_seq(Some(fields.len()))?;
+        for field in fields {
+            seq.serialize_element(&TaggedField(field))?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for Tagged<Record> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Tagged(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Tagged<Record> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let json = JsonValue::deserialize(deserializer)?;
+            return Record::from_tagged_json(&json)
+                .map(Tagged)
+                .map_err(de::Error::custom);
+        }
+        let fields = Vec::<FieldRepr>::deserialize(deserializer)?;
+        record_from_repr(fields)
+            .map(Tagged)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// The self-describing representation of a collection
+struct TaggedValues<'a>(&'a [Value]);
+
+impl Serialize for TaggedValues<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(Tagged))
+    }
+}
+
+/// The self-describing representation of a field
+struct TaggedField<'a>(&'a Field);
+
+impl Serialize for TaggedField<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Field", 2)?;
+        state.serialize_field("name", self.0.name())?;
+        state.serialize_field("value", &Tagged(self.0.value_as_ref()))?;
+        state.end()
+    }
+}
+
+/// The deserialized self-describing representation of a [Value]. The variants
+/// must be in the order of [VARIANTS]
+#[derive(Deserialize)]
+#[serde(rename = "Value")]
+enum Repr {
+    Bool(bool),
+    Char(char),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    ISize(i64),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    USize(u64),
+    F32(f32),
+    F64(f64),
+    Decimal(String),
+    String(String),
+    Blob(Vec<u8>),
+    Date(String),
+    DateTime(String),
+    Time(String),
+    DateTimeTz(String),
+    Duration(String),
+    Uuid(String),
+    Json(String),
+    Collection(Vec<Repr>),
+    Record(Vec<FieldRepr>),
+    None(()),
+}
+
+/// The deserialized self-describing representation of a [Field]
+#[derive(Deserialize)]
+#[serde(rename = "Field")]
+struct FieldRepr {
+    name: String,
+    value: Repr,
+}
+
+/// Converts the deserialized representation into a [Value]
+fn value_from_repr(repr: Repr) -> Result<Value, String> {
+    let value = match repr {
+        Repr::Bool(v) => Value::Bool(v),
+        Repr::Char(v) => Value::Char(v),
+        Repr::I8(v) => Value::I8(v),
+        Repr::I16(v) => Value::I16(v),
+        Repr::I32(v) => Value::I32(v),
+        Repr::I64(v) => Value::I64(v),
+        Repr::I128(v) => Value::I128(v),
+        Repr::ISize(v) => Value::ISize(isize::try_from(v).map_err(|e| e.to_string())?),
+        Repr::U8(v) => Value::U8(v),
+        Repr::U16(v) => Value::U16(v),
+        Repr::U32(v) => Value::U32(v),
+        Repr::U64(v) => Value::U64(v),
+        Repr::U128(v) => Value::U128(v),
+        Repr::USize(v) => Value::USize(usize::try_from(v).map_err(|e| e.to_string())?),
+        Repr::F32(v) => Value::F32(v),
+        Repr::F64(v) => Value::F64(v),
+        Repr::Decimal(v) => Value::Decimal(
+            v.parse::<Decimal>()
+                .map_err(|e| format!("Invalid Decimal '{}': {}", v, e))?,
+        ),
+        Repr::String(v) => Value::from(v),
+        Repr::Blob(v) => Value::from(v),
+        Repr::Date(v) => Value::Date(
+            NaiveDate::parse_from_str(&v, DATE_FORMAT)
+                .map_err(|e| format!("Invalid Date '{}': {}", v, e))?,
+        ),
+        Repr::DateTime(v) => Value::DateTime(
+            NaiveDateTime::parse_from_str(&v, DATE_TIME_FORMAT)
+                .map_err(|e| format!("Invalid DateTime '{}': {}", v, e))?,
+        ),
+        Repr::Time(v) => Value::Time(
+            NaiveTime::parse_from_str(&v, TIME_FORMAT)
+                .map_err(|e| format!("Invalid Time '{}': {}", v, e))?,
+        ),
+        Repr::DateTimeTz(v) => Value::DateTimeTz(
+            DateTime::parse_from_str(&v, DATE_TIME_TZ_FORMAT)
+                .map_err(|e| format!("Invalid DateTimeTz '{}': {}", v, e))?,
+        ),
+        Repr::Duration(v) => Value::Duration(
+            parse_duration(&v).map_err(|e| format!("Invalid Duration '{}': {}", v, e))?,
+        ),
+        Repr::Uuid(v) => Value::Uuid(
+            v.parse()
+                .map_err(|e| format!("Invalid Uuid '{}': {}", v, e))?,
+        ),
+        Repr::Json(v) => Value::Json(
+            serde_json::from_str(&v).map_err(|e| format!("Invalid Json '{}': {}", v, e))?,
+        ),
+        Repr::Collection(v) => Value::Collection(
+            v.into_iter()
+                .map(value_from_repr)
+                .collect::<Result<Vec<Value>, String>>()?,
+        ),
+        Repr::Record(v) => Value::Record(record_from_repr(v)?),
+        Repr::None(()) => Value::None,
+    };
+    Ok(value)
+}
+
+/// Converts the deserialized representation into a [Record]
+fn record_from_repr(fields: Vec<FieldRepr>) -> Result<Record, String> {
+    let mut record = Record::new();
+    for field in fields {
+        record
+            .fields_as_mut()
+            .push(Field::new_value(&field.name, value_from_repr(field.value)?));
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests;
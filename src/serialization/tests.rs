@@ -0,0 +1,308 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    field::{Field, add_field},
+    record::Record,
+    value::Value,
+};
+
+use super::Tagged;
+
+fn all_variants() -> Vec<Value> {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_nano_opt(23, 59, 59, 1).unwrap();
+
+    let mut nested = Record::new();
+    add_field(nested.fields_as_mut(), "z", Value::I32(1));
+    add_field(nested.fields_as_mut(), "a", Value::None);
+    add_field(nested.fields_as_mut(), "z", Value::Char('a'));
+
+    vec![
+        Value::Bool(false),
+        Value::Char('ß'),
+        Value::I8(i8::MIN),
+        Value::I16(i16::MIN),
+        Value::I32(i32::MIN),
+        Value::I64(i64::MIN),
+        Value::I128(i128::MIN),
+        Value::ISize(isize::MIN),
+        Value::U8(u8::MAX),
+        Value::U16(u16::MAX),
+        Value::U32(u32::MAX),
+        Value::U64(u64::MAX),
+        Value::U128(u128::MAX),
+        Value::USize(usize::MAX),
+        Value::F32(0.1),
+        Value::F64(-1.0e-300),
+        Value::Decimal(Decimal::new(-123456789, 4)),
+        Value::String("2024-05-01".into()),
+        Value::Blob(vec![0, 127, 255].into()),
+        Value::Date(date),
+        Value::DateTime(date.and_time(time)),
+        Value::Time(time),
+        Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T10:00:00.25+02:00").unwrap()),
+        Value::Duration(TimeDelta::new(-90061, 5).unwrap()),
+        Value::Uuid(Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)),
+        Value::Json(json!({"a": [1, null, {"b": "c"}]})),
+        Value::Collection(vec![Value::U8(1), Value::String("a".into())]),
+        Value::Record(nested),
+        Value::None,
+    ]
+}
+
+fn all_variants_record() -> Record {
+    let mut record = Record::new();
+    for (index, value) in all_variants().into_iter().enumerate() {
+        record
+            .fields_as_mut()
+            .push(Field::new_value(&format!("field{}", index), value));
+    }
+    record
+}
+
+#[test]
+fn test_natural_serialize() -> Result<(), serde_json::Error> {
+    let mut inner = Record::new();
+    add_field(inner.fields_as_mut(), "city", "Vienna".into());
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "name", "John Doe".into());
+    add_field(record.fields_as_mut(), "age", Value::U8(30));
+    add_field(
+        record.fields_as_mut(),
+        "price",
+        Value::Decimal(Decimal::new(1999, 2)),
+    );
+    add_field(
+        record.fields_as_mut(),
+        "since",
+        Value::Date(NaiveDate::from_ymd_opt(2024, 5, 1).unwrap()),
+    );
+    add_field(
+        record.fields_as_mut(),
+        "tags",
+        Value::Collection(vec!["a".into(), Value::None]),
+    );
+    add_field(record.fields_as_mut(), "address", Value::Record(inner));
+
+    // The field order is kept
+    assert_eq!(
+        r#"{"name":"John Doe","age":30,"price":"19.99","since":"2024-05-01","tags":["a",null],"address":{"city":"Vienna"}}"#,
+        serde_json::to_string(&record)?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_natural_deserialize() -> Result<(), serde_json::Error> {
+    let record: Record =
+        serde_json::from_str(r#"{"b":-1,"a":[1.5,"x",true,null],"c":{"d":18446744073709551615}}"#)?;
+
+    let mut inner = Record::new();
+    add_field(inner.fields_as_mut(), "d", Value::U64(u64::MAX));
+    let mut expected = Record::new();
+    add_field(expected.fields_as_mut(), "b", Value::I64(-1));
+    add_field(
+        expected.fields_as_mut(),
+        "a",
+        Value::Collection(vec![
+            Value::F64(1.5),
+            Value::String("x".into()),
+            Value::Bool(true),
+            Value::None,
+        ]),
+    );
+    add_field(expected.fields_as_mut(), "c", Value::Record(inner));
+    assert_eq!(expected, record);
+
+    assert!(serde_json::from_str::<Record>("[1]").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_natural_value() -> Result<(), serde_json::Error> {
+    assert_eq!("null", serde_json::to_string(&Value::None)?);
+    assert_eq!(
+        "340282366920938463463374607431768211455",
+        serde_json::to_string(&Value::U128(u128::MAX))?
+    );
+    assert_eq!(
+        "[1,2]",
+        serde_json::to_string(&Value::Blob(vec![1, 2].into()))?
+    );
+    assert_eq!("\"c\"", serde_json::to_string(&Value::Char('c'))?);
+
+    assert_eq!(Value::None, serde_json::from_str::<Value>("null")?);
+    assert_eq!(Value::I64(-5), serde_json::from_str::<Value>("-5")?);
+    assert_eq!(Value::U64(5), serde_json::from_str::<Value>("5")?);
+    Ok(())
+}
+
+#[test]
+fn test_natural_field() -> Result<(), serde_json::Error> {
+    let field = Field::new_value("id", Value::I32(7));
+    let json = serde_json::to_value(&field)?;
+    assert_eq!(json!({"name": "id", "value": 7}), json);
+
+    let field: Field = serde_json::from_value(json)?;
+    assert_eq!(Field::new_value("id", Value::U64(7)), field);
+    Ok(())
+}
+
+#[test]
+fn test_tagged_json() -> Result<(), serde_json::Error> {
+    assert_eq!(
+        json!({"U16": 42}),
+        serde_json::to_value(Tagged(&Value::U16(42)))?
+    );
+    assert_eq!(
+        json!({"Decimal": "1.50"}),
+        serde_json::to_value(Tagged(Value::Decimal(Decimal::new(150, 2))))?
+    );
+    assert_eq!(
+        json!({"Json": [1, 2]}),
+        serde_json::to_value(Tagged(Value::Json(json!([1, 2]))))?
+    );
+    assert_eq!(
+        json!({"Collection": [{"Bool": true}, {"None": null}]}),
+        serde_json::to_value(Tagged(&Value::Collection(vec![
+            Value::Bool(true),
+            Value::None
+        ])))?
+    );
+
+    for value in all_variants() {
+        let json = serde_json::to_string(&Tagged(&value))?;
+        let Tagged(restored) = serde_json::from_str::<Tagged<Value>>(&json)?;
+        assert_eq!(value, restored, "{}", json);
+    }
+
+    let record = all_variants_record();
+    let json = serde_json::to_string(&Tagged(record.clone()))?;
+    let Tagged(restored) = serde_json::from_str::<Tagged<Record>>(&json)?;
+    assert_eq!(record, restored);
+    Ok(())
+}
+
+#[test]
+fn test_tagged_invalid() {
+    assert!(serde_json::from_value::<Tagged<Value>>(json!({"Unknown": 1})).is_err());
+    assert!(serde_json::from_value::<Tagged<Value>>(json!({"U8": 256})).is_err());
+
+    let error = serde_json::from_value::<Tagged<Value>>(json!({"Decimal": "abc"})).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invalid tagged value for Decimal: \"abc\"")
+    );
+    let error = serde_json::from_value::<Tagged<Value>>(json!({"Date": "1.5.2024"})).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invalid tagged value for Date: \"1.5.2024\"")
+    );
+    let error =
+        serde_json::from_value::<Tagged<Record>>(json!([{"name": "a", "value": {"Time": "x"}}]))
+            .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invalid tagged value for Time: \"x\"")
+    );
+    let error = serde_json::from_value::<Tagged<Value>>(json!({"DateTime": "x"})).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invalid tagged value for DateTime: \"x\"")
+    );
+    let error = serde_json::from_value::<Tagged<Value>>(json!({"Duration": "x"})).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .starts_with("Invalid tagged value for Duration: \"x\"")
+    );
+    assert!(serde_json::from_value::<Tagged<Record>>(json!({"a": 1})).is_err());
+}
+
+#[test]
+fn test_tagged_same_as_tagged_json() -> Result<(), serde_json::Error> {
+    for value in all_variants() {
+        assert_eq!(
+            value.to_tagged_json(),
+            serde_json::to_value(Tagged(&value))?
+        );
+        assert_eq!(
+            value.to_tagged_json().to_string(),
+            serde_json::to_string(&Tagged(&value))?
+        );
+    }
+    assert_eq!(
+        json!({"I128": "-170141183460469231731687303715884105728"}),
+        serde_json::to_value(Tagged(Value::I128(i128::MIN)))?,
+        "I128 is a string, so that JavaScript keeps all digits"
+    );
+
+    let record = all_variants_record();
+    assert_eq!(
+        record.to_tagged_json().to_string(),
+        serde_json::to_string(&Tagged(&record))?
+    );
+
+    // Both decoders accept the output of the other encoder
+    let Tagged(restored) = serde_json::from_value::<Tagged<Record>>(record.to_tagged_json())?;
+    assert_eq!(record, restored);
+    let json = serde_json::to_value(Tagged(&record)).unwrap();
+    assert_eq!(record, Record::from_tagged_json(&json).unwrap());
+    Ok(())
+}
+
+#[test]
+fn test_tagged_bincode() {
+    let config = bincode::config::standard();
+
+    for value in all_variants() {
+        let bytes = bincode::serde::encode_to_vec(Tagged(&value), config).unwrap();
+        let (Tagged(restored), _): (Tagged<Value>, usize) =
+            bincode::serde::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(value, restored);
+    }
+
+    let record = all_variants_record();
+    let bytes = bincode::serde::encode_to_vec(Tagged(&record), config).unwrap();
+    let (Tagged(restored), _): (Tagged<Record>, usize) =
+        bincode::serde::decode_from_slice(&bytes, config).unwrap();
+    assert_eq!(record, restored);
+}
+
+#[test]
+fn test_natural_bincode_not_self_describing() {
+    let config = bincode::config::standard();
+    let bytes = bincode::serde::encode_to_vec(Value::U8(1), config).unwrap();
+    let result: Result<(Value, usize), _> = bincode::serde::decode_from_slice(&bytes, config);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_natural_new_variants() -> Result<(), serde_json::Error> {
+    let mut record = Record::new();
+    add_field(
+        record.fields_as_mut(),
+        "at",
+        Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap()),
+    );
+    add_field(
+        record.fields_as_mut(),
+        "took",
+        Value::Duration(TimeDelta::milliseconds(1500)),
+    );
+    add_field(record.fields_as_mut(), "id", Value::Uuid(Uuid::nil()));
+    add_field(record.fields_as_mut(), "doc", Value::Json(json!({"a": 1})));
+    assert_eq!(
+        r#"{"at":"2024-05-01T10:00:00+02:00","took":"PT1.5S","id":"00000000-0000-0000-0000-000000000000","doc":{"a":1}}"#,
+        serde_json::to_string(&record)?
+    );
+    Ok(())
+}
@@ -1,7 +1,77 @@
-use super::{record::Record, BoxedError, Initializable};
+use super::{
+    BoxedError, Initializable,
+    record::{Record, batch::RecordBatch},
+};
 
 pub trait Transformer: Initializable {
     /// Transforms the `record` and returns a new [Record]
     ///
     fn process(&self, record: &Record) -> Result<Record, BoxedError>;
+
+    /// Transforms all records of the `batch` and returns a new [RecordBatch]
+    /// With default implementation, that calls `process` for every record.
+    /// Transformers, that work on whole columns, can do it without
+    /// converting the batch into records
+    ///
+    /// # Note
+    /// The records passed to `process` differ from the records pushed into
+    /// the batch: every record has all columns of the batch, a missing field
+    /// is [crate::value::Value::None], only the first field of duplicate
+    /// names is kept and the field metadata is lost. Call
+    /// [Transformer::process] for every record, if that matters
+    fn process_batch(&self, batch: &RecordBatch) -> Result<RecordBatch, BoxedError> {
+        let mut result = RecordBatch::new();
+        for record in batch.records() {
+            result.push(&self.process(&record)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        BoxedError, Initializable,
+        record::{Record, batch::RecordBatch},
+        value::Value,
+        xml::config::Configuration,
+    };
+
+    use super::Transformer;
+
+    struct Double;
+
+    impl Initializable for Double {
+        fn init(&mut self, _config: Option<Configuration>) -> Result<(), BoxedError> {
+            Ok(())
+        }
+    }
+
+    impl Transformer for Double {
+        fn process(&self, record: &Record) -> Result<Record, BoxedError> {
+            let mut result = Record::copy(record);
+            let value = record.get("value").ok_or("no value")?.as_i64()?;
+            result.set("value", Value::I64(value * 2));
+            Ok(result)
+        }
+    }
+
+    #[test]
+    fn test_process_batch() {
+        let batch: RecordBatch = (1..=3)
+            .map(|i| {
+                let mut record = Record::new();
+                record.set("value", Value::I64(i));
+                record
+            })
+            .collect();
+        let result = Double.process_batch(&batch).unwrap();
+        let values: Vec<Value> = result.column("value").unwrap().iter().collect();
+        assert_eq!(vec![Value::I64(2), Value::I64(4), Value::I64(6)], values);
+
+        let mut invalid = Record::new();
+        invalid.set("other", Value::None);
+        let error = Double.process_batch(&RecordBatch::from(vec![invalid]));
+        assert_eq!("no value", error.unwrap_err().to_string());
+    }
 }
@@ -1,12 +1,25 @@
 //! Module for the Value
 //!
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
 use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
+use shared::{SharedBytes, SharedString};
 use std::fmt::Display;
+use uuid::Uuid;
 
+pub mod arithmetic;
 pub mod from;
+pub mod json;
+pub mod ordering;
+pub mod parse;
+pub mod shared;
+pub mod try_from;
+pub mod value_type;
 
 /// An enum for all known field values.
+///
+/// Cloning a value is cheap for strings, blobs and records, since they share
+/// their data (see [shared] and [crate::record::Record]).
 #[derive(Clone, Debug, PartialEq)]
 pub enum Value {
     Bool(bool),
@@ -26,16 +39,37 @@ pub enum Value {
     F32(f32),
     F64(f64),
     Decimal(Decimal),
-    String(String),
-    Blob(Vec<u8>),
+    String(SharedString),
+    Blob(SharedBytes),
     Date(NaiveDate),
     DateTime(NaiveDateTime),
     Time(NaiveTime),
+    /// A date and time with the offset from UTC, e.g. from the ISO 8601
+    /// timestamp `2024-05-01T10:00:00+02:00`
+    DateTimeTz(DateTime<FixedOffset>),
+    /// A signed duration with nanosecond precision
+    Duration(TimeDelta),
+    Uuid(Uuid),
+    /// An opaque JSON document, that is kept as it is. Use [Value::Record] and
+    /// [Value::Collection] for structured data
+    Json(JsonValue),
     Collection(Vec<Value>),
     Record(crate::record::Record),
     None,
 }
 
+impl Value {
+    /// Returns the name of the variant of this value
+    ///
+    /// # Example
+    /// ```
+    /// assert_eq!("U16", model::value::Value::U16(42).type_name());
+    /// ```
+    pub fn type_name(&self) -> &'static str {
+        self.value_type().name()
+    }
+}
+
 /// Implements the [Display] trait for the [Value]
 impl Display for Value {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -61,6 +95,10 @@ impl Display for Value {
             Value::Date(val) => write!(f, "{}", val),
             Value::DateTime(val) => write!(f, "{}", val),
             Value::Time(val) => write!(f, "{}", val),
+            Value::DateTimeTz(val) => write!(f, "{}", val.format(json::DATE_TIME_TZ_FORMAT)),
+            Value::Duration(val) => write!(f, "{}", val),
+            Value::Uuid(val) => write!(f, "{}", val),
+            Value::Json(val) => write!(f, "{}", val),
             Value::Blob(vec) => {
                 // Displaying bytes as hexadecimal
                 let hex: Vec<String> = vec.iter().map(|b| format!("{:02x}", b)).collect();
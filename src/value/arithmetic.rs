@@ -0,0 +1,512 @@
+//! Checked arithmetic and string operations on [Value]
+//!
+//! [Add], [Sub], [Mul], [Div] and [Rem] are implemented for [Value] and
+//! &[Value]. Every operation returns a `Result<Value, ArithmeticError>`, so
+//! that overflows and unsupported operands are reported instead of panicking.
+//!
+//! # Numbers
+//! * Two integers give the wider integer type. If a signed and an unsigned
+//!   type are mixed, the result is a signed type, that can hold both, e.g.
+//!   `I8 + U8 = I16` and `I64 + U64 = I128`. [Value::ISize] and
+//!   [Value::USize] are kept only if both operands have this type. Division is
+//!   an integer division, that truncates towards zero
+//! * An integer and a [Value::Decimal] give a [Value::Decimal]
+//! * If one operand is a float, the result is a [Value::F64]. Only
+//!   `F32 op F32` stays a [Value::F32]. Floats follow IEEE 754, so a division
+//!   by zero gives an infinite value or `NaN`
+//! * Integer and decimal overflows and divisions by zero are errors
+//!
+//! # Dates and times
+//! * `Date ± integer` adds or subtracts days, `Date - Date` gives the days
+//!   between them as a [Value::I64]
+//! * `DateTime ± integer` adds or subtracts seconds, `DateTime - DateTime`
+//!   gives the [Value::Duration] between them
+//! * `Time ± integer` adds or subtracts seconds and wraps around midnight,
+//!   `Time - Time` gives the [Value::Duration] between them
+//! * `DateTimeTz ± integer` adds or subtracts seconds and keeps the offset,
+//!   `DateTimeTz - DateTimeTz` gives the [Value::Duration] between the
+//!   instants
+//! * `DateTime`, `DateTimeTz` and `Time ± Duration` add or subtract the
+//!   [Value::Duration]
+//!
+//! # Durations
+//! `Duration ± Duration` gives a [Value::Duration], as do `Duration * integer`
+//! and `Duration / integer`.
+//!
+//! # Strings
+//! `+` concatenates [Value::String] and [Value::Char] operands into a
+//! [Value::String].
+//!
+//! # None
+//! If one of the operands is [Value::None], the result is [Value::None].
+//!
+//! # Example
+//! ```
+//! use model::value::Value;
+//! use rust_decimal::Decimal;
+//!
+//! assert_eq!(Ok(Value::I16(-100)), Value::I8(-128) + Value::U8(28));
+//! assert_eq!(
+//!     Ok(Value::Decimal(Decimal::new(300, 2))),
+//!     Value::U8(2) * Value::Decimal(Decimal::new(150, 2))
+//! );
+//! assert_eq!(Ok(Value::from("ab")), Value::from("a") + Value::Char('b'));
+//!
+//! let error = (Value::U8(255) + Value::U8(1)).unwrap_err();
+//! assert_eq!("Overflow in U8 + U8", error.to_string());
+//! ```
+use std::{
+    fmt::Display,
+    ops::{Add, Div, Mul, Rem, Sub},
+};
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::{
+    Decimal,
+    prelude::{FromPrimitive, ToPrimitive},
+};
+
+use super::{Value, value_type::ValueType};
+
+/// The reason, why an arithmetic operation failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticErrorKind {
+    /// The operation is not defined for the variants of the operands
+    Unsupported,
+    /// The result does not fit into the result type
+    Overflow,
+    /// An integer or decimal division or remainder by zero
+    DivisionByZero,
+}
+
+/// The error returned, when an arithmetic operation on [Value]s fails
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArithmeticError {
+    operator: &'static str,
+    left: &'static str,
+    right: &'static str,
+    kind: ArithmeticErrorKind,
+}
+
+impl ArithmeticError {
+    fn new(op: Op, left: &Value, right: &Value, kind: ArithmeticErrorKind) -> Self {
+        Self {
+            operator: op.symbol(),
+            left: left.type_name(),
+            right: right.type_name(),
+            kind,
+        }
+    }
+
+    /// Returns the operator, e.g. `+`
+    pub fn operator(&self) -> &'static str {
+        self.operator
+    }
+
+    /// Returns the name of the [Value] variant of the left operand
+    pub fn left(&self) -> &'static str {
+        self.left
+    }
+
+    /// Returns the name of the [Value] variant of the right operand
+    pub fn right(&self) -> &'static str {
+        self.right
+    }
+
+    /// Returns the reason of the error
+    pub fn kind(&self) -> ArithmeticErrorKind {
+        self.kind
+    }
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.kind {
+            ArithmeticErrorKind::Unsupported => "Unsupported operation",
+            ArithmeticErrorKind::Overflow => "Overflow in",
+            ArithmeticErrorKind::DivisionByZero => "Division by zero in",
+        };
+        write!(
+            f,
+            "{} {} {} {}",
+            reason, self.left, self.operator, self.right
+        )
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+/// The arithmetic operators
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+        }
+    }
+}
+
+/// Implements an operator for [Value] and &[Value]
+macro_rules! value_operator {
+    ($($trait:ident, $method:ident, $op:expr;)*) => {$(
+        impl $trait for &Value {
+            type Output = Result<Value, ArithmeticError>;
+
+            fn $method(self, rhs: &Value) -> Self::Output {
+                apply($op, self, rhs)
+            }
+        }
+
+        impl $trait for Value {
+            type Output = Result<Value, ArithmeticError>;
+
+            fn $method(self, rhs: Value) -> Self::Output {
+                apply($op, &self, &rhs)
+            }
+        }
+    )*};
+}
+
+value_operator! {
+    Add, add, Op::Add;
+    Sub, sub, Op::Sub;
+    Mul, mul, Op::Mul;
+    Div, div, Op::Div;
+    Rem, rem, Op::Rem;
+}
+
+fn apply(op: Op, left: &Value, right: &Value) -> Result<Value, ArithmeticError> {
+    let error = |kind| ArithmeticError::new(op, left, right, kind);
+
+    if matches!(left, Value::None) || matches!(right, Value::None) {
+        return Ok(Value::None);
+    }
+    if let (Some(a), Some(b)) = (Integer::of(left), Integer::of(right)) {
+        return integer(op, a, b).map_err(error);
+    }
+    if is_float(left) || is_float(right) {
+        if let (Value::F32(a), Value::F32(b)) = (left, right) {
+            return Ok(Value::F32(float(op, *a, *b)));
+        }
+        if let (Some(a), Some(b)) = (to_f64(left), to_f64(right)) {
+            return Ok(Value::F64(float(op, a, b)));
+        }
+    }
+    if let (Some(a), Some(b)) = (to_decimal(left), to_decimal(right)) {
+        return a.and_then(|a| decimal(op, a, b?)).map_err(error);
+    }
+
+    let result = match (op, left, right) {
+        (Op::Sub, Value::Date(a), Value::Date(b)) => Some(Ok(Value::I64((*a - *b).num_days()))),
+        (Op::Sub, Value::DateTime(a), Value::DateTime(b)) => Some(Ok(Value::Duration(*a - *b))),
+        (Op::Sub, Value::Time(a), Value::Time(b)) => Some(Ok(Value::Duration(*a - *b))),
+        (Op::Sub, Value::DateTimeTz(a), Value::DateTimeTz(b)) => Some(Ok(Value::Duration(*a - *b))),
+        (Op::Add | Op::Sub, _, Value::Duration(delta)) | (Op::Add, Value::Duration(delta), _) => {
+            let other = if matches!(right, Value::Duration(_)) {
+                left
+            } else {
+                right
+            };
+            let delta = if op == Op::Sub { -*delta } else { *delta };
+            duration_plus(other, delta)
+        }
+        (Op::Mul, Value::Duration(delta), other) | (Op::Mul, other, Value::Duration(delta)) => {
+            Some(
+                Integer::of(other).map_or(Err(ArithmeticErrorKind::Unsupported), |factor| {
+                    i32::try_from(factor.value)
+                        .ok()
+                        .filter(|_| factor.large.is_none())
+                        .and_then(|factor| delta.checked_mul(factor))
+                        .map(Value::Duration)
+                        .ok_or(ArithmeticErrorKind::Overflow)
+                }),
+            )
+        }
+        (Op::Div, Value::Duration(delta), other) => {
+            Integer::of(other).map(|divisor| match (divisor.value, divisor.large) {
+                (0, None) => Err(ArithmeticErrorKind::DivisionByZero),
+                (divisor, None) => i32::try_from(divisor)
+                    .ok()
+                    .and_then(|divisor| delta.checked_div(divisor))
+                    .map(Value::Duration)
+                    .ok_or(ArithmeticErrorKind::Overflow),
+                // Any duration divided by a larger number is zero
+                (_, Some(_)) => Ok(Value::Duration(TimeDelta::zero())),
+            })
+        }
+        (Op::Add | Op::Sub, Value::Date(date), other) | (Op::Add, other, Value::Date(date)) => {
+            date_plus(*date, signed(op, other))
+        }
+        (Op::Add | Op::Sub, Value::DateTime(date_time), other)
+        | (Op::Add, other, Value::DateTime(date_time)) => {
+            date_time_plus(*date_time, signed(op, other))
+        }
+        (Op::Add | Op::Sub, Value::Time(time), other) | (Op::Add, other, Value::Time(time)) => {
+            time_plus(*time, signed(op, other))
+        }
+        (Op::Add | Op::Sub, Value::DateTimeTz(date_time), other)
+        | (Op::Add, other, Value::DateTimeTz(date_time)) => {
+            date_time_tz_plus(*date_time, signed(op, other))
+        }
+        (Op::Add, Value::String(_) | Value::Char(_), Value::String(_) | Value::Char(_)) => {
+            let mut s = left.to_string();
+            s.push_str(&right.to_string());
+            return Ok(Value::from(s));
+        }
+        _ => return Err(error(ArithmeticErrorKind::Unsupported)),
+    };
+    // The integer operand of a date or time operation is missing
+    result
+        .unwrap_or(Err(ArithmeticErrorKind::Unsupported))
+        .map_err(error)
+}
+
+/// An integer operand
+#[derive(Debug, Clone, Copy)]
+struct Integer {
+    int_type: ValueType,
+    value: i128,
+    /// The value of a [Value::U128] larger than [i128::MAX]
+    large: Option<u128>,
+}
+
+impl Integer {
+    fn of(value: &Value) -> Option<Integer> {
+        let (int_type, value) = match value {
+            Value::I8(v) => (ValueType::I8, i128::from(*v)),
+            Value::I16(v) => (ValueType::I16, i128::from(*v)),
+            Value::I32(v) => (ValueType::I32, i128::from(*v)),
+            Value::I64(v) => (ValueType::I64, i128::from(*v)),
+            Value::I128(v) => (ValueType::I128, *v),
+            Value::ISize(v) => (ValueType::ISize, *v as i128),
+            Value::U8(v) => (ValueType::U8, i128::from(*v)),
+            Value::U16(v) => (ValueType::U16, i128::from(*v)),
+            Value::U32(v) => (ValueType::U32, i128::from(*v)),
+            Value::U64(v) => (ValueType::U64, i128::from(*v)),
+            Value::USize(v) => (ValueType::USize, *v as i128),
+            Value::U128(v) => {
+                return Some(match i128::try_from(*v) {
+                    Ok(i) => Integer {
+                        int_type: ValueType::U128,
+                        value: i,
+                        large: None,
+                    },
+                    Err(_) => Integer {
+                        int_type: ValueType::U128,
+                        value: 0,
+                        large: Some(*v),
+                    },
+                });
+            }
+            _ => return None,
+        };
+        Some(Integer {
+            int_type,
+            value,
+            large: None,
+        })
+    }
+
+    fn as_u128(&self) -> u128 {
+        self.large.unwrap_or(self.value as u128)
+    }
+}
+
+fn integer(op: Op, a: Integer, b: Integer) -> Result<Value, ArithmeticErrorKind> {
+    use ArithmeticErrorKind::{DivisionByZero, Overflow};
+
+    let target = a
+        .int_type
+        .common_integer(b.int_type)
+        .ok_or(ArithmeticErrorKind::Unsupported)?;
+    if matches!(op, Op::Div | Op::Rem) && b.value == 0 && b.large.is_none() {
+        return Err(DivisionByZero);
+    }
+
+    if target == ValueType::U128 {
+        let (a, b) = (a.as_u128(), b.as_u128());
+        let result = match op {
+            Op::Add => a.checked_add(b),
+            Op::Sub => a.checked_sub(b),
+            Op::Mul => a.checked_mul(b),
+            Op::Div => a.checked_div(b),
+            Op::Rem => a.checked_rem(b),
+        };
+        return result.map(Value::U128).ok_or(Overflow);
+    }
+
+    if a.large.is_some() || b.large.is_some() {
+        return Err(Overflow);
+    }
+    let (a, b) = (a.value, b.value);
+    let result = match op {
+        Op::Add => a.checked_add(b),
+        Op::Sub => a.checked_sub(b),
+        Op::Mul => a.checked_mul(b),
+        Op::Div => a.checked_div(b),
+        Op::Rem => a.checked_rem(b),
+    }
+    .ok_or(Overflow)?;
+
+    let value = match target {
+        ValueType::I8 => i8::try_from(result).ok().map(Value::I8),
+        ValueType::I16 => i16::try_from(result).ok().map(Value::I16),
+        ValueType::I32 => i32::try_from(result).ok().map(Value::I32),
+        ValueType::I64 => i64::try_from(result).ok().map(Value::I64),
+        ValueType::I128 => Some(Value::I128(result)),
+        ValueType::ISize => isize::try_from(result).ok().map(Value::ISize),
+        ValueType::U8 => u8::try_from(result).ok().map(Value::U8),
+        ValueType::U16 => u16::try_from(result).ok().map(Value::U16),
+        ValueType::U32 => u32::try_from(result).ok().map(Value::U32),
+        ValueType::U64 => u64::try_from(result).ok().map(Value::U64),
+        ValueType::U128 => u128::try_from(result).ok().map(Value::U128),
+        ValueType::USize => usize::try_from(result).ok().map(Value::USize),
+        _ => None,
+    };
+    value.ok_or(Overflow)
+}
+
+fn is_float(value: &Value) -> bool {
+    matches!(value, Value::F32(_) | Value::F64(_))
+}
+
+fn to_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::F32(f) => Some(f64::from(*f)),
+        Value::F64(f) => Some(*f),
+        Value::Decimal(d) => d.to_f64(),
+        _ => Integer::of(value).map(|i| match i.large {
+            Some(u) => u as f64,
+            None => i.value as f64,
+        }),
+    }
+}
+
+fn float<F>(op: Op, a: F, b: F) -> F
+where
+    F: Add<Output = F> + Sub<Output = F> + Mul<Output = F> + Div<Output = F> + Rem<Output = F>,
+{
+    match op {
+        Op::Add => a + b,
+        Op::Sub => a - b,
+        Op::Mul => a * b,
+        Op::Div => a / b,
+        Op::Rem => a % b,
+    }
+}
+
+/// Returns the [Decimal] of a decimal or integer value. The inner result is
+/// an error, if the integer is outside of the range of a [Decimal]
+fn to_decimal(value: &Value) -> Option<Result<Decimal, ArithmeticErrorKind>> {
+    match value {
+        Value::Decimal(d) => Some(Ok(*d)),
+        _ => Integer::of(value).map(|i| {
+            match i.large {
+                Some(u) => Decimal::from_u128(u),
+                None => Decimal::from_i128(i.value),
+            }
+            .ok_or(ArithmeticErrorKind::Overflow)
+        }),
+    }
+}
+
+fn decimal(op: Op, a: Decimal, b: Decimal) -> Result<Value, ArithmeticErrorKind> {
+    if matches!(op, Op::Div | Op::Rem) && b.is_zero() {
+        return Err(ArithmeticErrorKind::DivisionByZero);
+    }
+    match op {
+        Op::Add => a.checked_add(b),
+        Op::Sub => a.checked_sub(b),
+        Op::Mul => a.checked_mul(b),
+        Op::Div => a.checked_div(b),
+        Op::Rem => a.checked_rem(b),
+    }
+    .map(Value::Decimal)
+    .ok_or(ArithmeticErrorKind::Overflow)
+}
+
+/// Returns the integer `value` as an [i64], negated for a subtraction. Returns
+/// [None] for non-integer values
+fn signed(op: Op, value: &Value) -> Option<Result<i64, ArithmeticErrorKind>> {
+    let integer = Integer::of(value)?;
+    let value = match integer.large {
+        Some(_) => None,
+        None => i64::try_from(integer.value).ok(),
+    };
+    let value = match op {
+        Op::Sub => value.and_then(i64::checked_neg),
+        _ => value,
+    };
+    Some(value.ok_or(ArithmeticErrorKind::Overflow))
+}
+
+type Delta = Option<Result<i64, ArithmeticErrorKind>>;
+
+fn date_plus(date: NaiveDate, days: Delta) -> Option<Result<Value, ArithmeticErrorKind>> {
+    Some(days?.and_then(|days| {
+        TimeDelta::try_days(days)
+            .and_then(|delta| date.checked_add_signed(delta))
+            .map(Value::Date)
+            .ok_or(ArithmeticErrorKind::Overflow)
+    }))
+}
+
+fn date_time_plus(
+    date_time: NaiveDateTime,
+    seconds: Delta,
+) -> Option<Result<Value, ArithmeticErrorKind>> {
+    Some(seconds?.and_then(|seconds| {
+        TimeDelta::try_seconds(seconds)
+            .and_then(|delta| date_time.checked_add_signed(delta))
+            .map(Value::DateTime)
+            .ok_or(ArithmeticErrorKind::Overflow)
+    }))
+}
+
+fn date_time_tz_plus(
+    date_time: DateTime<FixedOffset>,
+    seconds: Delta,
+) -> Option<Result<Value, ArithmeticErrorKind>> {
+    Some(seconds?.and_then(|seconds| {
+        TimeDelta::try_seconds(seconds)
+            .and_then(|delta| date_time.checked_add_signed(delta))
+            .map(Value::DateTimeTz)
+            .ok_or(ArithmeticErrorKind::Overflow)
+    }))
+}
+
+/// Adds `delta` to a date-time, a time or a duration. Returns [None] for all
+/// other values
+fn duration_plus(value: &Value, delta: TimeDelta) -> Option<Result<Value, ArithmeticErrorKind>> {
+    let result = match value {
+        Value::DateTime(date_time) => date_time.checked_add_signed(delta).map(Value::DateTime),
+        Value::DateTimeTz(date_time) => date_time.checked_add_signed(delta).map(Value::DateTimeTz),
+        Value::Time(time) => Some(Value::Time(time.overflowing_add_signed(delta).0)),
+        Value::Duration(duration) => duration.checked_add(&delta).map(Value::Duration),
+        _ => return None,
+    };
+    Some(result.ok_or(ArithmeticErrorKind::Overflow))
+}
+
+fn time_plus(time: NaiveTime, seconds: Delta) -> Option<Result<Value, ArithmeticErrorKind>> {
+    Some(seconds?.and_then(|seconds| {
+        TimeDelta::try_seconds(seconds)
+            .map(|delta| Value::Time(time.overflowing_add_signed(delta).0))
+            .ok_or(ArithmeticErrorKind::Overflow)
+    }))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,336 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use crate::value::Value;
+
+use super::ArithmeticErrorKind;
+
+fn kind(result: Result<Value, super::ArithmeticError>) -> ArithmeticErrorKind {
+    result.unwrap_err().kind()
+}
+
+#[test]
+fn test_integer_promotion() {
+    assert_eq!(Ok(Value::U8(3)), Value::U8(1) + Value::U8(2));
+    assert_eq!(Ok(Value::I32(3)), Value::I8(1) + Value::I32(2));
+    assert_eq!(Ok(Value::U64(3)), Value::U64(1) + Value::U16(2));
+    assert_eq!(Ok(Value::I16(-1)), Value::U8(1) - Value::I8(2));
+    assert_eq!(Ok(Value::I32(300)), Value::U16(150) * Value::I8(2));
+    assert_eq!(Ok(Value::I64(-4)), Value::I64(-2) * Value::U32(2));
+    assert_eq!(
+        Ok(Value::I128(i128::from(u64::MAX) + 1)),
+        Value::U64(u64::MAX) + Value::I64(1)
+    );
+    assert_eq!(Ok(Value::ISize(3)), Value::ISize(1) + Value::ISize(2));
+    assert_eq!(Ok(Value::I64(3)), Value::ISize(1) + Value::I8(2));
+    assert_eq!(
+        Ok(Value::U128(u128::MAX)),
+        Value::U128(u128::MAX - 1) + Value::U8(1)
+    );
+}
+
+#[test]
+fn test_integer_division() {
+    assert_eq!(Ok(Value::I32(-3)), Value::I32(-7) / Value::I32(2));
+    assert_eq!(Ok(Value::I32(-1)), Value::I32(-7) % Value::I32(2));
+    assert_eq!(
+        ArithmeticErrorKind::DivisionByZero,
+        kind(Value::U8(1) / Value::U8(0))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::DivisionByZero,
+        kind(Value::I64(1) % Value::I8(0))
+    );
+}
+
+#[test]
+fn test_integer_overflow() {
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::U8(255) + Value::U8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::U8(0) - Value::U8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::I8(i8::MIN) / Value::I8(-1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::I128(i128::MAX) + Value::I8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::U128(u128::MAX) + Value::U8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::U128(u128::MAX) - Value::I8(1))
+    );
+
+    let error = (Value::I16(i16::MAX) * Value::I16(2)).unwrap_err();
+    assert_eq!("*", error.operator());
+    assert_eq!("I16", error.left());
+    assert_eq!("I16", error.right());
+    assert_eq!("Overflow in I16 * I16", error.to_string());
+}
+
+#[test]
+fn test_decimal() {
+    let d = |m, s| Value::Decimal(Decimal::new(m, s));
+    assert_eq!(Ok(d(350, 2)), d(150, 2) + Value::U8(2));
+    assert_eq!(Ok(d(-50, 2)), Value::I8(1) - d(150, 2));
+    assert_eq!(Ok(d(5, 1)), d(15, 1) % Value::U8(1));
+    assert_eq!(Ok(d(75, 2)), d(15, 1) / Value::I32(2));
+    assert_eq!(
+        ArithmeticErrorKind::DivisionByZero,
+        kind(d(15, 1) / Value::U8(0))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::Decimal(Decimal::MAX) + Value::U8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(d(1, 0) + Value::U128(u128::MAX))
+    );
+}
+
+#[test]
+fn test_float() {
+    assert_eq!(Ok(Value::F32(1.5)), Value::F32(1.0) + Value::F32(0.5));
+    assert_eq!(Ok(Value::F64(1.5)), Value::F32(1.0) + Value::F64(0.5));
+    assert_eq!(Ok(Value::F64(2.5)), Value::U8(2) + Value::F32(0.5));
+    assert_eq!(
+        Ok(Value::F64(0.75)),
+        Value::Decimal(Decimal::new(15, 1)) * Value::F64(0.5)
+    );
+    assert_eq!(Ok(Value::F64(1.0)), Value::F64(7.0) % Value::I8(3));
+    assert_eq!(
+        Ok(Value::F64(f64::INFINITY)),
+        Value::F64(1.0) / Value::U8(0)
+    );
+}
+
+#[test]
+fn test_dates() {
+    let date = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+    let time = NaiveTime::from_hms_opt(23, 0, 0).unwrap();
+    let date_time = date.and_time(time);
+
+    assert_eq!(
+        Ok(Value::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())),
+        Value::Date(date) + Value::U8(2)
+    );
+    assert_eq!(
+        Ok(Value::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())),
+        Value::I32(2) + Value::Date(date)
+    );
+    assert_eq!(
+        Ok(Value::Date(NaiveDate::from_ymd_opt(2023, 12, 31).unwrap())),
+        Value::Date(date) - Value::I64(59)
+    );
+    assert_eq!(
+        Ok(Value::I64(-2)),
+        Value::Date(date) - Value::Date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+    );
+
+    assert_eq!(
+        Ok(Value::DateTime(
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(0, 0, 1)
+                .unwrap()
+        )),
+        Value::DateTime(date_time) + Value::U16(3601)
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::hours(-1))),
+        Value::DateTime(date_time)
+            - Value::DateTime(
+                NaiveDate::from_ymd_opt(2024, 2, 29)
+                    .unwrap()
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+            )
+    );
+
+    // Times wrap around midnight
+    assert_eq!(
+        Ok(Value::Time(NaiveTime::from_hms_opt(1, 0, 0).unwrap())),
+        Value::Time(time) + Value::U16(7200)
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::hours(23))),
+        Value::Time(time) - Value::Time(NaiveTime::MIN)
+    );
+    // Fractions of a second are kept
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::milliseconds(-1500))),
+        Value::Time(NaiveTime::from_hms_milli_opt(1, 0, 0, 500).unwrap())
+            - Value::Time(NaiveTime::from_hms_opt(1, 0, 2).unwrap())
+    );
+
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::Date(NaiveDate::MAX) + Value::U8(1))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(Value::Date(date) - Value::I64(i64::MIN))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::Date(date) * Value::U8(2))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::U8(2) - Value::Date(date))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::Date(date) + Value::F64(1.0))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::Date(date) - Value::Time(time))
+    );
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(
+        Ok(Value::String("Hello World".into())),
+        Value::from("Hello ") + Value::from("World")
+    );
+    assert_eq!(
+        Ok(Value::String("ab".into())),
+        Value::Char('a') + Value::Char('b')
+    );
+    assert_eq!(
+        Ok(Value::String("a1".into())),
+        &Value::from("a") + &Value::from("1")
+    );
+
+    let error = (Value::from("a") - Value::from("b")).unwrap_err();
+    assert_eq!("Unsupported operation String - String", error.to_string());
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::from("a") + Value::U8(1))
+    );
+}
+
+#[test]
+fn test_none_and_unsupported() {
+    assert_eq!(Ok(Value::None), Value::None + Value::U8(1));
+    assert_eq!(Ok(Value::None), Value::from("a") * Value::None);
+    assert_eq!(Ok(Value::None), Value::U8(1) / Value::None);
+
+    for value in [
+        Value::Bool(true),
+        Value::Blob(vec![1].into()),
+        Value::Collection(vec![]),
+        Value::Record(crate::record::Record::new()),
+    ] {
+        assert_eq!(
+            ArithmeticErrorKind::Unsupported,
+            kind(&value + &Value::U8(1))
+        );
+    }
+}
+
+#[test]
+fn test_date_time_tz() {
+    let date_time = DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap();
+    let later = DateTime::parse_from_rfc3339("2024-05-01T10:01:00+02:00").unwrap();
+    let result = (Value::DateTimeTz(date_time) + Value::U8(60)).unwrap();
+    assert_eq!(Value::DateTimeTz(later), result);
+    // The offset is kept
+    assert_eq!("2024-05-01T10:01:00+02:00", result.to_string());
+
+    let utc = DateTime::parse_from_rfc3339("2024-05-01T09:00:00Z").unwrap();
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::hours(1))),
+        Value::DateTimeTz(utc) - Value::DateTimeTz(date_time)
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::DateTimeTz(utc) * Value::U8(2))
+    );
+}
+
+#[test]
+fn test_duration() {
+    let hour = Value::Duration(TimeDelta::hours(1));
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::minutes(90))),
+        &hour + &Value::Duration(TimeDelta::minutes(30))
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::minutes(30))),
+        &hour - &Value::Duration(TimeDelta::minutes(30))
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::hours(3))),
+        &hour * &Value::U8(3)
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::hours(-3))),
+        Value::I8(-3) * hour.clone()
+    );
+    assert_eq!(
+        Ok(Value::Duration(TimeDelta::minutes(15))),
+        &hour / &Value::U8(4)
+    );
+    assert_eq!(
+        ArithmeticErrorKind::DivisionByZero,
+        kind(&hour / &Value::U8(0))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Overflow,
+        kind(&hour * &Value::I64(i64::MAX))
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(&hour + &Value::U8(1))
+    );
+    assert_eq!(ArithmeticErrorKind::Unsupported, kind(&hour * &hour));
+
+    // Date-times and times
+    let date = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+    let date_time = date.and_hms_opt(23, 30, 0).unwrap();
+    assert_eq!(
+        Ok(Value::DateTime(date.and_hms_opt(22, 30, 0).unwrap())),
+        Value::DateTime(date_time) - hour.clone()
+    );
+    assert_eq!(
+        Ok(Value::DateTime(
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+                .unwrap()
+                .and_hms_opt(0, 30, 0)
+                .unwrap()
+        )),
+        &hour + &Value::DateTime(date_time)
+    );
+    assert_eq!(
+        Ok(Value::Time(NaiveTime::from_hms_opt(0, 30, 0).unwrap())),
+        Value::Time(date_time.time()) + hour.clone()
+    );
+    let date_time_tz = DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap();
+    assert_eq!(
+        "2024-05-01T11:00:00+02:00",
+        (Value::DateTimeTz(date_time_tz) + hour.clone())
+            .unwrap()
+            .to_string()
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(Value::Date(date) + hour.clone())
+    );
+    assert_eq!(
+        ArithmeticErrorKind::Unsupported,
+        kind(&hour - &Value::Time(date_time.time()))
+    );
+}
@@ -1,20 +1,23 @@
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
 use rust_decimal::Decimal;
+use uuid::Uuid;
 
 use super::Value;
+use super::shared::{SharedBytes, SharedString};
+use super::json::options::JsonConversionOptions;
 use crate::record::Record;
 use serde_json::Value as JsonValue;
 
 // Implement Into<Value> for supported types
 impl From<String> for Value {
     fn from(value: String) -> Self {
-        Value::String(value.to_string())
+        Value::String(value.into())
     }
 }
 
 impl From<&str> for Value {
     fn from(value: &str) -> Self {
-        Value::String(value.to_string())
+        Value::String(value.into())
     }
 }
 
@@ -122,6 +125,18 @@ impl From<Decimal> for Value {
 
 impl From<Vec<u8>> for Value {
     fn from(value: Vec<u8>) -> Self {
+        Value::Blob(value.into())
+    }
+}
+
+impl From<SharedString> for Value {
+    fn from(value: SharedString) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<SharedBytes> for Value {
+    fn from(value: SharedBytes) -> Self {
         Value::Blob(value)
     }
 }
@@ -144,6 +159,24 @@ impl From<NaiveTime> for Value {
     }
 }
 
+impl From<DateTime<FixedOffset>> for Value {
+    fn from(value: DateTime<FixedOffset>) -> Self {
+        Value::DateTimeTz(value)
+    }
+}
+
+impl From<TimeDelta> for Value {
+    fn from(value: TimeDelta) -> Self {
+        Value::Duration(value)
+    }
+}
+
+impl From<Uuid> for Value {
+    fn from(value: Uuid) -> Self {
+        Value::Uuid(value)
+    }
+}
+
 impl From<Vec<Value>> for Value {
     fn from(value: Vec<Value>) -> Self {
         Value::Collection(value)
@@ -157,71 +190,9 @@ impl From<Record> for Value {
 }
 
 impl From<JsonValue> for Value {
+    /// Converts with the [JsonConversionOptions::heuristic] options
     fn from(json_value: JsonValue) -> Self {
-        match json_value {
-            JsonValue::Bool(b) => Value::Bool(b),
-            JsonValue::Number(n) => {
-                if let Some(u) = n.as_u64() {
-                    match u8::try_from(u) {
-                        Ok(val) => Value::U8(val),
-                        Err(_) => match u16::try_from(u) {
-                            Ok(val) => Value::U16(val),
-                            Err(_) => match u32::try_from(u) {
-                                Ok(val) => Value::U32(val),
-                                Err(_) => match u64::try_from(u) {
-                                    Ok(val) => Value::U64(val),
-                                    Err(_) => match u128::try_from(u) {
-                                        Ok(val) => Value::U128(val),
-                                        Err(_) => Value::None,
-                                    },
-                                },
-                            },
-                        },
-                    }
-                } else if let Some(i) = n.as_i64() {
-                    match i8::try_from(i) {
-                        Ok(val) => Value::I8(val),
-                        Err(_) => match i16::try_from(i) {
-                            Ok(val) => Value::I16(val),
-                            Err(_) => match i32::try_from(i) {
-                                Ok(val) => Value::I32(val),
-                                Err(_) => match i64::try_from(i) {
-                                    Ok(val) => Value::I64(val),
-                                    Err(_) => match i128::try_from(i) {
-                                        Ok(val) => Value::I128(val),
-                                        Err(_) => Value::None,
-                                    },
-                                },
-                            },
-                        },
-                    }
-                } else if let Some(f) = n.as_f64() {
-                    Value::F64(f)
-                } else {
-                    Value::None
-                }
-            }
-            JsonValue::String(s) => {
-                if s.len() == 1 {
-                    Value::Char(s.chars().next().unwrap_or_default())
-                } else if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
-                    Value::Date(date)
-                } else if let Ok(time) = NaiveTime::parse_from_str(&s, "%H:%M:%S") {
-                    Value::Time(time)
-                } else if let Ok(datetime) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S")
-                {
-                    Value::DateTime(datetime)
-                } else {
-                    Value::String(s)
-                }
-            }
-            JsonValue::Array(arr) => {
-                let collection: Vec<Value> = arr.into_iter().map(Value::from).collect();
-                Value::Collection(collection)
-            }
-            JsonValue::Object(_) => Value::Record(Record::from(json_value)),
-            JsonValue::Null => Value::None,
-        }
+        Value::from_json(json_value, &JsonConversionOptions::heuristic())
     }
 }
 
@@ -1,4 +1,4 @@
-use chrono::{Local, NaiveDate, NaiveTime};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeDelta};
 use rust_decimal::Decimal;
 use serde_json::json;
 
@@ -47,7 +47,7 @@ fn test_value_from_json_array() {
         value,
         Value::Collection(vec![
             Value::I8(-11),
-            Value::String("abc".to_string()),
+            Value::String("abc".into()),
             Value::Bool(true)
         ])
     );
@@ -95,7 +95,7 @@ fn test_value_from_json_object() {
         .push(Field::new_value("age", Value::U8(30)));
     expected_record.fields_as_mut().push(Field::new_value(
         "name",
-        Value::String("John Doe".to_string()),
+        Value::String("John Doe".into()),
     ));
 
     assert_eq!(value, Value::Record(expected_record));
@@ -154,7 +154,7 @@ fn test_value_from_json_char() {
 fn test_value_from_json_multiple_char() {
     let json_value = json!("abc");
     let value = Value::from(json_value);
-    assert_eq!(value, Value::String("abc".to_string()));
+    assert_eq!(value, Value::String("abc".into()));
 }
 
 #[test]
@@ -317,7 +317,7 @@ fn test_from_collection() {
     let vec: Vec<Value> = vec![
         Value::I8(42),
         Value::F32(73.0),
-        Value::String(String::from("string")),
+        Value::String("string".into()),
     ];
     let v: Value = vec.into();
     assert!(matches!(v, Value::Collection(_)));
@@ -329,3 +329,17 @@ fn test_from_record() {
     let v: Value = r.into();
     assert!(matches!(v, Value::Record(_)));
 }
+
+#[test]
+fn test_from_new_variants() {
+    let date_time = DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap();
+    assert_eq!(Value::DateTimeTz(date_time), Value::from(date_time));
+    assert_eq!(
+        Value::Duration(TimeDelta::seconds(1)),
+        Value::from(TimeDelta::seconds(1))
+    );
+    assert_eq!(
+        Value::Uuid(uuid::Uuid::nil()),
+        Value::from(uuid::Uuid::nil())
+    );
+}
@@ -0,0 +1,345 @@
+//! Conversions between a [Value] and a [JsonValue]
+//!
+//! The conversion from JSON into a [Value] has to guess the variant, since
+//! JSON has fewer types. How it guesses is controlled by the
+//! [options::JsonConversionOptions].
+//!
+//! There are two encodings:
+//! * The natural encoding (`From<&Value> for JsonValue`) maps every value to
+//!   the closest JSON type. It is meant for exporters, that write JSON for
+//!   other systems. Converting it back with `From<JsonValue> for Value` can
+//!   not restore the exact variant, e.g. a [Value::I32] will be read back as
+//!   the smallest integer type that can hold the number.
+//! * The tagged encoding ([Value::to_tagged_json]) wraps every value into an
+//!   object with the variant name as the only key, e.g. `{"U128": "42"}`.
+//!   [Value::from_tagged_json] restores the exact [Value], so that
+//!   `Value -> JSON -> Value` round-trips without any loss.
+//!
+//! # Example
+//! ```
+//! use model::value::Value;
+//! use serde_json::{Value as JsonValue, json};
+//!
+//! let value = Value::U128(42);
+//! assert_eq!(json!(42), JsonValue::from(&value));
+//!
+//! let tagged = value.to_tagged_json();
+//! assert_eq!(json!({"U128": "42"}), tagged);
+//! assert_eq!(value, Value::from_tagged_json(&tagged).unwrap());
+//! ```
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::Decimal;
+use serde_json::{Map, Number, Value as JsonValue};
+use uuid::Uuid;
+
+use super::{Value, parse::parse_duration};
+
+pub mod options;
+use crate::{BoxedError, field::Field, record::Record};
+
+/// Format of [Value::Date] in JSON
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+/// Format of [Value::DateTime] in JSON. Fractional seconds are only written,
+/// if they are not zero
+pub const DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+/// Format of [Value::Time] in JSON. Fractional seconds are only written, if
+/// they are not zero
+pub const TIME_FORMAT: &str = "%H:%M:%S%.f";
+/// Format of [Value::DateTimeTz] in JSON: RFC 3339, e.g.
+/// `2024-05-01T10:00:00+02:00`. Parsing accepts `Z` as offset as well
+pub const DATE_TIME_TZ_FORMAT: &str = "%+";
+
+impl From<&Value> for JsonValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Bool(v) => JsonValue::Bool(*v),
+            Value::Char(v) => JsonValue::String(v.to_string()),
+            Value::I8(v) => JsonValue::from(*v),
+            Value::I16(v) => JsonValue::from(*v),
+            Value::I32(v) => JsonValue::from(*v),
+            Value::I64(v) => JsonValue::from(*v),
+            Value::ISize(v) => JsonValue::from(*v),
+            Value::I128(v) => match i64::try_from(*v) {
+                Ok(v) => JsonValue::from(v),
+                Err(_) => JsonValue::String(v.to_string()),
+            },
+            Value::U8(v) => JsonValue::from(*v),
+            Value::U16(v) => JsonValue::from(*v),
+            Value::U32(v) => JsonValue::from(*v),
+            Value::U64(v) => JsonValue::from(*v),
+            Value::USize(v) => JsonValue::from(*v),
+            Value::U128(v) => match u64::try_from(*v) {
+                Ok(v) => JsonValue::from(v),
+                Err(_) => JsonValue::String(v.to_string()),
+            },
+            // Use the shortest representation of the f32, instead of its
+            // exact f64 value
+            Value::F32(v) => float_to_json(v.to_string().parse().unwrap_or(f64::NAN)),
+            Value::F64(v) => float_to_json(*v),
+            Value::Decimal(v) => JsonValue::String(v.to_string()),
+            Value::String(v) => JsonValue::String(v.to_string()),
+            Value::Blob(v) => JsonValue::Array(v.iter().map(|b| JsonValue::from(*b)).collect()),
+            Value::Date(v) => JsonValue::String(v.format(DATE_FORMAT).to_string()),
+            Value::DateTime(v) => JsonValue::String(v.format(DATE_TIME_FORMAT).to_string()),
+            Value::Time(v) => JsonValue::String(v.format(TIME_FORMAT).to_string()),
+            Value::DateTimeTz(v) => JsonValue::String(v.format(DATE_TIME_TZ_FORMAT).to_string()),
+            Value::Duration(v) => JsonValue::String(v.to_string()),
+            Value::Uuid(v) => JsonValue::String(v.to_string()),
+            Value::Json(v) => v.clone(),
+            Value::Collection(v) => JsonValue::Array(v.iter().map(JsonValue::from).collect()),
+            Value::Record(v) => JsonValue::from(v),
+            Value::None => JsonValue::Null,
+        }
+    }
+}
+
+impl From<Value> for JsonValue {
+    fn from(value: Value) -> Self {
+        JsonValue::from(&value)
+    }
+}
+
+/// Converts a [Record] into a JSON object. If there are fields with the same
+/// name, the last one wins
+impl From<&Record> for JsonValue {
+    fn from(record: &Record) -> Self {
+        let mut map = Map::new();
+        for field in record.fields() {
+            map.insert(
+                field.name().to_string(),
+                JsonValue::from(field.value_as_ref()),
+            );
+        }
+        JsonValue::Object(map)
+    }
+}
+
+impl From<Record> for JsonValue {
+    fn from(record: Record) -> Self {
+        JsonValue::from(&record)
+    }
+}
+
+/// Converts a float into a JSON number. Non-finite numbers become `null`
+fn float_to_json(value: f64) -> JsonValue {
+    Number::from_f64(value)
+        .map(JsonValue::Number)
+        .unwrap_or(JsonValue::Null)
+}
+
+impl Value {
+    /// Returns the tagged JSON encoding of this value
+    ///
+    /// Every value is encoded as an object with the variant name as key.
+    /// Values, that JSON numbers cannot hold exactly ([Value::I128],
+    /// [Value::U128], [Value::Decimal] and non-finite floats) are encoded as
+    /// strings. A [Value::Record] is encoded as an array of `name`/`value`
+    /// objects, to keep the order of the fields
+    ///
+    /// # Example
+    /// ```
+    /// use model::value::Value;
+    /// use serde_json::json;
+    ///
+    /// assert_eq!(json!({"Char": "x"}), Value::Char('x').to_tagged_json());
+    /// assert_eq!(json!({"None": null}), Value::None.to_tagged_json());
+    /// ```
+    pub fn to_tagged_json(&self) -> JsonValue {
+        let content = match self {
+            Value::Bool(v) => JsonValue::Bool(*v),
+            Value::Char(v) => JsonValue::String(v.to_string()),
+            Value::I8(v) => JsonValue::from(*v),
+            Value::I16(v) => JsonValue::from(*v),
+            Value::I32(v) => JsonValue::from(*v),
+            Value::I64(v) => JsonValue::from(*v),
+            Value::ISize(v) => JsonValue::from(*v),
+            Value::I128(v) => JsonValue::String(v.to_string()),
+            Value::U8(v) => JsonValue::from(*v),
+            Value::U16(v) => JsonValue::from(*v),
+            Value::U32(v) => JsonValue::from(*v),
+            Value::U64(v) => JsonValue::from(*v),
+            Value::USize(v) => JsonValue::from(*v),
+            Value::U128(v) => JsonValue::String(v.to_string()),
+            Value::F32(v) => tagged_float(f64::from(*v)),
+            Value::F64(v) => tagged_float(*v),
+            Value::Decimal(v) => JsonValue::String(v.to_string()),
+            Value::Collection(v) => JsonValue::Array(v.iter().map(Value::to_tagged_json).collect()),
+            Value::Record(v) => v.to_tagged_json(),
+            Value::String(_)
+            | Value::Blob(_)
+            | Value::Date(_)
+            | Value::DateTime(_)
+            | Value::Time(_)
+            | Value::DateTimeTz(_)
+            | Value::Duration(_)
+            | Value::Uuid(_)
+            | Value::Json(_) => JsonValue::from(self),
+            Value::None => JsonValue::Null,
+        };
+
+        let mut map = Map::new();
+        map.insert(self.type_name().to_string(), content);
+        JsonValue::Object(map)
+    }
+
+    /// Restores a [Value] from its tagged JSON encoding
+    ///
+    /// # Arguments
+    /// * `json` - A JSON value created by [Value::to_tagged_json]
+    pub fn from_tagged_json(json: &JsonValue) -> Result<Value, BoxedError> {
+        let (tag, content) = match json {
+            JsonValue::Object(map) if map.len() == 1 => map.iter().next().unwrap(),
+            _ => return Err(format!("Invalid tagged value: {}", json).into()),
+        };
+        let invalid =
+            || -> BoxedError { format!("Invalid tagged value for {}: {}", tag, content).into() };
+
+        let value = match tag.as_str() {
+            "Bool" => Value::Bool(content.as_bool().ok_or_else(invalid)?),
+            "Char" => {
+                let s = content.as_str().ok_or_else(invalid)?;
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Value::Char(c),
+                    _ => return Err(invalid()),
+                }
+            }
+            "I8" => Value::I8(tagged_integer(content).ok_or_else(invalid)?),
+            "I16" => Value::I16(tagged_integer(content).ok_or_else(invalid)?),
+            "I32" => Value::I32(tagged_integer(content).ok_or_else(invalid)?),
+            "I64" => Value::I64(tagged_integer(content).ok_or_else(invalid)?),
+            "ISize" => Value::ISize(tagged_integer(content).ok_or_else(invalid)?),
+            "I128" => Value::I128(tagged_parse(content).ok_or_else(invalid)?),
+            "U8" => Value::U8(tagged_unsigned(content).ok_or_else(invalid)?),
+            "U16" => Value::U16(tagged_unsigned(content).ok_or_else(invalid)?),
+            "U32" => Value::U32(tagged_unsigned(content).ok_or_else(invalid)?),
+            "U64" => Value::U64(tagged_unsigned(content).ok_or_else(invalid)?),
+            "USize" => Value::USize(tagged_unsigned(content).ok_or_else(invalid)?),
+            "U128" => Value::U128(tagged_parse(content).ok_or_else(invalid)?),
+            "F32" => Value::F32(untagged_float(content).ok_or_else(invalid)? as f32),
+            "F64" => Value::F64(untagged_float(content).ok_or_else(invalid)?),
+            "Decimal" => Value::Decimal(tagged_parse::<Decimal>(content).ok_or_else(invalid)?),
+            "String" => Value::from(content.as_str().ok_or_else(invalid)?),
+            "Blob" => Value::from(
+                content
+                    .as_array()
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(|b| b.as_u64().and_then(|b| u8::try_from(b).ok()))
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or_else(invalid)?,
+            ),
+            "Date" => Value::Date(
+                NaiveDate::parse_from_str(content.as_str().ok_or_else(invalid)?, DATE_FORMAT)
+                    .map_err(|_| invalid())?,
+            ),
+            "DateTime" => Value::DateTime(
+                NaiveDateTime::parse_from_str(
+                    content.as_str().ok_or_else(invalid)?,
+                    DATE_TIME_FORMAT,
+                )
+                .map_err(|_| invalid())?,
+            ),
+            "Time" => Value::Time(
+                NaiveTime::parse_from_str(content.as_str().ok_or_else(invalid)?, TIME_FORMAT)
+                    .map_err(|_| invalid())?,
+            ),
+            "DateTimeTz" => Value::DateTimeTz(
+                DateTime::parse_from_str(
+                    content.as_str().ok_or_else(invalid)?,
+                    DATE_TIME_TZ_FORMAT,
+                )
+                .map_err(|_| invalid())?,
+            ),
+            "Duration" => Value::Duration(
+                parse_duration(content.as_str().ok_or_else(invalid)?).map_err(|_| invalid())?,
+            ),
+            "Uuid" => Value::Uuid(
+                Uuid::parse_str(content.as_str().ok_or_else(invalid)?).map_err(|_| invalid())?,
+            ),
+            "Json" => Value::Json(content.clone()),
+            "Collection" => Value::Collection(
+                content
+                    .as_array()
+                    .ok_or_else(invalid)?
+                    .iter()
+                    .map(Value::from_tagged_json)
+                    .collect::<Result<Vec<Value>, BoxedError>>()?,
+            ),
+            "Record" => Value::Record(Record::from_tagged_json(content)?),
+            "None" if content.is_null() => Value::None,
+            _ => return Err(invalid()),
+        };
+        Ok(value)
+    }
+}
+
+impl Record {
+    /// Returns the tagged JSON encoding of this record: an array of objects
+    /// with the `name` and the tagged `value` of every field
+    ///
+    /// See [Value::to_tagged_json]
+    pub fn to_tagged_json(&self) -> JsonValue {
+        JsonValue::Array(
+            self.fields()
+                .iter()
+                .map(|field| {
+                    let mut map = Map::new();
+                    map.insert("name".to_string(), JsonValue::from(field.name()));
+                    map.insert("value".to_string(), field.value_as_ref().to_tagged_json());
+                    JsonValue::Object(map)
+                })
+                .collect(),
+        )
+    }
+
+    /// Restores a [Record] from its tagged JSON encoding
+    ///
+    /// # Arguments
+    /// * `json` - A JSON value created by [Record::to_tagged_json]
+    pub fn from_tagged_json(json: &JsonValue) -> Result<Record, BoxedError> {
+        let invalid = || -> BoxedError { format!("Invalid tagged record: {}", json).into() };
+
+        let mut record = Record::new();
+        for field in json.as_array().ok_or_else(invalid)? {
+            let name = field
+                .get("name")
+                .and_then(JsonValue::as_str)
+                .ok_or_else(invalid)?;
+            let value = Value::from_tagged_json(field.get("value").ok_or_else(invalid)?)?;
+            record.fields_as_mut().push(Field::new_value(name, value));
+        }
+        Ok(record)
+    }
+}
+
+/// Encodes a float as number, or as string if it is not finite
+fn tagged_float(value: f64) -> JsonValue {
+    match Number::from_f64(value) {
+        Some(number) => JsonValue::Number(number),
+        None => JsonValue::String(value.to_string()),
+    }
+}
+
+fn untagged_float(content: &JsonValue) -> Option<f64> {
+    match content {
+        JsonValue::Number(n) => n.as_f64(),
+        JsonValue::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn tagged_integer<T: TryFrom<i64>>(content: &JsonValue) -> Option<T> {
+    content.as_i64().and_then(|v| T::try_from(v).ok())
+}
+
+fn tagged_unsigned<T: TryFrom<u64>>(content: &JsonValue) -> Option<T> {
+    content.as_u64().and_then(|v| T::try_from(v).ok())
+}
+
+fn tagged_parse<T: std::str::FromStr>(content: &JsonValue) -> Option<T> {
+    content.as_str().and_then(|s| s.parse().ok())
+}
+
+#[cfg(test)]
+mod tests;
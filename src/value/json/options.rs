@@ -0,0 +1,379 @@
+//! Options for the conversion of a [JsonValue] into a [Value]
+//!
+//! JSON only knows numbers, strings, booleans, arrays, objects and `null`.
+//! [JsonConversionOptions] control, which [Value] variants are inferred from
+//! them. The default options ([JsonConversionOptions::heuristic]) are used by
+//! `From<JsonValue> for Value` and `From<JsonValue> for Record`.
+//!
+//! # Example
+//! ```
+//! use model::value::Value;
+//! use model::value::json::options::{IntegerWidth, JsonConversionOptions};
+//! use serde_json::json;
+//!
+//! let options = JsonConversionOptions::strict().integer_width(IntegerWidth::PreserveSign);
+//! assert_eq!(Value::I8(5), Value::from_json(json!(5), &options));
+//! assert_eq!(Value::from("x"), Value::from_json(json!("x"), &options));
+//!
+//! // The heuristic preset
+//! assert_eq!(Value::U8(5), Value::from(json!(5)));
+//! assert_eq!(Value::Char('x'), Value::from(json!("x")));
+//! ```
+use std::str::FromStr;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use serde_json::{Number, Value as JsonValue};
+use uuid::Uuid;
+
+use crate::{field::Field, record::Record, value::Value};
+
+/// The policy for the [Value] variant of JSON integers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IntegerWidth {
+    /// The smallest type, that can hold the number. Non-negative numbers
+    /// become unsigned types, negative numbers signed types
+    #[default]
+    Smallest,
+    /// Every integer becomes a [Value::I64]. Larger numbers become
+    /// [Value::U64], [Value::I128] or [Value::U128]
+    I64,
+    /// The smallest signed type, that can hold the number, so that
+    /// non-negative and negative numbers of a field get the same kind of type.
+    /// Only numbers larger than [i128::MAX] become [Value::U128]
+    PreserveSign,
+}
+
+/// Options for the conversion of a [JsonValue] into a [Value]
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonConversionOptions {
+    integer_width: IntegerWidth,
+    sniff_char: bool,
+    unicode_char: bool,
+    sniff_date_time: bool,
+    sniff_date_time_tz: bool,
+    sniff_uuid: bool,
+    date_formats: Vec<String>,
+    date_time_formats: Vec<String>,
+    time_formats: Vec<String>,
+    floats_as_decimal: bool,
+    integer_strings: bool,
+}
+
+/// The date formats, that are always recognised, if date sniffing is on
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+const DEFAULT_DATE_TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const DEFAULT_TIME_FORMAT: &str = "%H:%M:%S";
+
+impl JsonConversionOptions {
+    /// Returns the options, that `From<JsonValue> for Value` uses:
+    /// * Integers become the smallest type ([IntegerWidth::Smallest])
+    /// * Strings with one byte, i.e. one ASCII character, become [Value::Char]
+    /// * Strings in the formats `%Y-%m-%d`, `%H:%M:%S` and
+    ///   `%Y-%m-%dT%H:%M:%S` become [Value::Date], [Value::Time] and
+    ///   [Value::DateTime]
+    /// * Floats become [Value::F64]
+    ///
+    /// Timestamps with an offset and UUIDs stay strings, see
+    /// [JsonConversionOptions::sniff_date_time_tz] and
+    /// [JsonConversionOptions::sniff_uuid]
+    pub fn heuristic() -> Self {
+        Self {
+            integer_width: IntegerWidth::Smallest,
+            sniff_char: true,
+            unicode_char: false,
+            sniff_date_time: true,
+            sniff_date_time_tz: false,
+            sniff_uuid: false,
+            date_formats: Vec::new(),
+            date_time_formats: Vec::new(),
+            time_formats: Vec::new(),
+            floats_as_decimal: false,
+            integer_strings: false,
+        }
+    }
+
+    /// Returns options, that do not guess: integers become [Value::I64],
+    /// floats [Value::F64] and strings always stay [Value::String]
+    pub fn strict() -> Self {
+        Self {
+            integer_width: IntegerWidth::I64,
+            sniff_char: false,
+            sniff_date_time: false,
+            ..Self::heuristic()
+        }
+    }
+
+    /// Sets the [IntegerWidth] policy
+    pub fn integer_width(mut self, integer_width: IntegerWidth) -> Self {
+        self.integer_width = integer_width;
+        self
+    }
+
+    /// Sets, if strings with exactly one byte become [Value::Char]
+    pub fn sniff_char(mut self, sniff_char: bool) -> Self {
+        self.sniff_char = sniff_char;
+        self
+    }
+
+    /// Sets, if char sniffing accepts any single character, e.g. `é`, instead
+    /// of only single bytes. Only used, if char sniffing is on
+    pub fn sniff_unicode_char(mut self, unicode_char: bool) -> Self {
+        self.unicode_char = unicode_char;
+        self
+    }
+
+    /// Sets, if strings are checked for dates, times and date-times
+    pub fn sniff_date_time(mut self, sniff_date_time: bool) -> Self {
+        self.sniff_date_time = sniff_date_time;
+        self
+    }
+
+    /// Sets, if RFC 3339 timestamps with an offset, e.g.
+    /// `2024-05-01T10:00:00+02:00`, become [Value::DateTimeTz]
+    pub fn sniff_date_time_tz(mut self, sniff_date_time_tz: bool) -> Self {
+        self.sniff_date_time_tz = sniff_date_time_tz;
+        self
+    }
+
+    /// Sets, if strings in the hyphenated UUID format, e.g.
+    /// `67e55044-10b1-426f-9247-bb680e5fe0c8`, become [Value::Uuid]
+    pub fn sniff_uuid(mut self, sniff_uuid: bool) -> Self {
+        self.sniff_uuid = sniff_uuid;
+        self
+    }
+
+    /// Adds a [chrono::format::strftime] format for [Value::Date]. Only used,
+    /// if date sniffing is on
+    pub fn date_format(mut self, format: &str) -> Self {
+        self.date_formats.push(format.to_string());
+        self
+    }
+
+    /// Adds a [chrono::format::strftime] format for [Value::DateTime]. Only
+    /// used, if date sniffing is on
+    pub fn date_time_format(mut self, format: &str) -> Self {
+        self.date_time_formats.push(format.to_string());
+        self
+    }
+
+    /// Adds a [chrono::format::strftime] format for [Value::Time]. Only used,
+    /// if date sniffing is on
+    pub fn time_format(mut self, format: &str) -> Self {
+        self.time_formats.push(format.to_string());
+        self
+    }
+
+    /// Sets, if floats become [Value::Decimal] instead of [Value::F64]. The
+    /// decimal keeps the digits as written in the JSON
+    pub fn floats_as_decimal(mut self, floats_as_decimal: bool) -> Self {
+        self.floats_as_decimal = floats_as_decimal;
+        self
+    }
+
+    /// Sets, if strings, that contain only an integer, are converted like
+    /// numbers. This is the only way to get integers, that do not fit into 64
+    /// bits, since JSON numbers of that size are read as floats
+    pub fn parse_integer_strings(mut self, integer_strings: bool) -> Self {
+        self.integer_strings = integer_strings;
+        self
+    }
+
+    fn number(&self, n: &Number) -> Value {
+        if let Some(u) = n.as_u64() {
+            self.integer(i128::from(u))
+        } else if let Some(i) = n.as_i64() {
+            self.integer(i128::from(i))
+        } else if let Some(f) = n.as_f64() {
+            if self.floats_as_decimal {
+                to_decimal(n, f)
+                    .map(Value::Decimal)
+                    .unwrap_or(Value::F64(f))
+            } else {
+                Value::F64(f)
+            }
+        } else {
+            Value::None
+        }
+    }
+
+    fn integer(&self, value: i128) -> Value {
+        match self.integer_width {
+            IntegerWidth::Smallest if value >= 0 => smallest_unsigned(value as u128),
+            IntegerWidth::Smallest | IntegerWidth::PreserveSign => smallest_signed(value),
+            IntegerWidth::I64 => match i64::try_from(value) {
+                Ok(v) => Value::I64(v),
+                Err(_) => match u64::try_from(value) {
+                    Ok(v) => Value::U64(v),
+                    Err(_) => Value::I128(value),
+                },
+            },
+        }
+    }
+
+    fn string(&self, s: String) -> Value {
+        if self.sniff_char {
+            let mut chars = s.chars();
+            if let (Some(c), None) = (chars.next(), chars.next())
+                && (self.unicode_char || s.len() == 1)
+            {
+                return Value::Char(c);
+            }
+        }
+
+        if self.sniff_date_time {
+            if let Some(date) = parse_first(
+                &s,
+                DEFAULT_DATE_FORMAT,
+                &self.date_formats,
+                NaiveDate::parse_from_str,
+            ) {
+                return Value::Date(date);
+            }
+            if let Some(time) = parse_first(
+                &s,
+                DEFAULT_TIME_FORMAT,
+                &self.time_formats,
+                NaiveTime::parse_from_str,
+            ) {
+                return Value::Time(time);
+            }
+            if let Some(date_time) = parse_first(
+                &s,
+                DEFAULT_DATE_TIME_FORMAT,
+                &self.date_time_formats,
+                NaiveDateTime::parse_from_str,
+            ) {
+                return Value::DateTime(date_time);
+            }
+        }
+
+        if self.sniff_date_time_tz
+            && let Ok(date_time) = DateTime::parse_from_rfc3339(&s)
+        {
+            return Value::DateTimeTz(date_time);
+        }
+
+        if self.sniff_uuid
+            && s.len() == 36
+            && let Ok(uuid) = Uuid::try_parse(&s)
+        {
+            return Value::Uuid(uuid);
+        }
+
+        if self.integer_strings {
+            if let Ok(i) = s.parse::<i128>() {
+                return self.integer(i);
+            }
+            if let Ok(u) = s.parse::<u128>() {
+                return Value::U128(u);
+            }
+        }
+
+        Value::from(s)
+    }
+}
+
+impl Default for JsonConversionOptions {
+    fn default() -> Self {
+        Self::heuristic()
+    }
+}
+
+impl Value {
+    /// Converts a [JsonValue] into a [Value] with the given `options`
+    ///
+    /// # Arguments
+    /// * `json` - The JSON value to convert
+    /// * `options` - The [JsonConversionOptions] for the type inference
+    pub fn from_json(json: JsonValue, options: &JsonConversionOptions) -> Value {
+        match json {
+            JsonValue::Bool(b) => Value::Bool(b),
+            JsonValue::Number(n) => options.number(&n),
+            JsonValue::String(s) => options.string(s),
+            JsonValue::Array(arr) => Value::Collection(
+                arr.into_iter()
+                    .map(|v| Value::from_json(v, options))
+                    .collect(),
+            ),
+            JsonValue::Object(_) => Value::Record(Record::from_json(json, options)),
+            JsonValue::Null => Value::None,
+        }
+    }
+}
+
+impl Record {
+    /// Converts a JSON object into a [Record] with the given `options`. Any
+    /// other JSON value results in an empty record
+    ///
+    /// # Arguments
+    /// * `json` - The JSON object to convert
+    /// * `options` - The [JsonConversionOptions] for the type inference
+    pub fn from_json(json: JsonValue, options: &JsonConversionOptions) -> Record {
+        let mut record = Record::new();
+        if let JsonValue::Object(map) = json {
+            let fields = record.fields_as_mut();
+            for (key, json_value) in map {
+                fields.push(Field::new_value(
+                    &key,
+                    Value::from_json(json_value, options),
+                ));
+            }
+        }
+        record
+    }
+}
+
+fn smallest_unsigned(value: u128) -> Value {
+    if let Ok(v) = u8::try_from(value) {
+        Value::U8(v)
+    } else if let Ok(v) = u16::try_from(value) {
+        Value::U16(v)
+    } else if let Ok(v) = u32::try_from(value) {
+        Value::U32(v)
+    } else if let Ok(v) = u64::try_from(value) {
+        Value::U64(v)
+    } else {
+        Value::U128(value)
+    }
+}
+
+fn smallest_signed(value: i128) -> Value {
+    if let Ok(v) = i8::try_from(value) {
+        Value::I8(v)
+    } else if let Ok(v) = i16::try_from(value) {
+        Value::I16(v)
+    } else if let Ok(v) = i32::try_from(value) {
+        Value::I32(v)
+    } else if let Ok(v) = i64::try_from(value) {
+        Value::I64(v)
+    } else {
+        Value::I128(value)
+    }
+}
+
+/// Converts the textual representation of `n` into a [Decimal]. Falls back
+/// to the float `f` for numbers in scientific notation
+fn to_decimal(n: &Number, f: f64) -> Option<Decimal> {
+    let text = n.to_string();
+    Decimal::from_str(&text)
+        .or_else(|_| Decimal::from_scientific(&text))
+        .ok()
+        .or_else(|| Decimal::from_f64(f))
+}
+
+/// Parses `s` with the `default` format and then with all `extra` formats and
+/// returns the first success
+fn parse_first<T, E>(
+    s: &str,
+    default: &str,
+    extra: &[String],
+    parse: impl Fn(&str, &str) -> Result<T, E>,
+) -> Option<T> {
+    std::iter::once(default)
+        .chain(extra.iter().map(String::as_str))
+        .find_map(|format| parse(s, format).ok())
+}
+
+#[cfg(test)]
+mod tests;
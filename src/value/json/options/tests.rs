@@ -0,0 +1,230 @@
+use chrono::{NaiveDate, NaiveTime};
+use rust_decimal::Decimal;
+use serde_json::json;
+
+use crate::{field::add_field, record::Record, value::Value};
+
+use super::{IntegerWidth, JsonConversionOptions};
+
+#[test]
+fn test_heuristic_is_default() {
+    assert_eq!(
+        JsonConversionOptions::heuristic(),
+        JsonConversionOptions::default()
+    );
+
+    let json = json!({"a": 1, "b": [-300, "x", "2024-05-01", "10:42:00", 1.5, null]});
+    assert_eq!(
+        Value::from(json.clone()),
+        Value::from_json(json, &JsonConversionOptions::default())
+    );
+}
+
+#[test]
+fn test_integer_width() {
+    let smallest = JsonConversionOptions::heuristic();
+    assert_eq!(Value::U8(5), Value::from_json(json!(5), &smallest));
+    assert_eq!(Value::I16(-300), Value::from_json(json!(-300), &smallest));
+    assert_eq!(
+        Value::U64(u64::MAX),
+        Value::from_json(json!(u64::MAX), &smallest)
+    );
+
+    let i64 = JsonConversionOptions::heuristic().integer_width(IntegerWidth::I64);
+    assert_eq!(Value::I64(5), Value::from_json(json!(5), &i64));
+    assert_eq!(Value::I64(-300), Value::from_json(json!(-300), &i64));
+    assert_eq!(
+        Value::U64(u64::MAX),
+        Value::from_json(json!(u64::MAX), &i64)
+    );
+
+    let signed = JsonConversionOptions::heuristic().integer_width(IntegerWidth::PreserveSign);
+    assert_eq!(Value::I8(5), Value::from_json(json!(5), &signed));
+    assert_eq!(Value::I16(200), Value::from_json(json!(200), &signed));
+    assert_eq!(Value::I16(-300), Value::from_json(json!(-300), &signed));
+    assert_eq!(
+        Value::I128(i128::from(u64::MAX)),
+        Value::from_json(json!(u64::MAX), &signed)
+    );
+}
+
+#[test]
+fn test_strict() {
+    let strict = JsonConversionOptions::strict();
+    assert_eq!(Value::I64(5), Value::from_json(json!(5), &strict));
+    assert_eq!(
+        Value::String("x".into()),
+        Value::from_json(json!("x"), &strict)
+    );
+    assert_eq!(
+        Value::String("2024-05-01".into()),
+        Value::from_json(json!("2024-05-01"), &strict)
+    );
+    assert_eq!(Value::F64(1.5), Value::from_json(json!(1.5), &strict));
+}
+
+#[test]
+fn test_sniff_char() {
+    let options = JsonConversionOptions::heuristic();
+    assert_eq!(Value::Char('x'), Value::from_json(json!("x"), &options));
+    // Like before the options, only single bytes are sniffed
+    assert_eq!(
+        Value::String("ß".into()),
+        Value::from_json(json!("ß"), &options)
+    );
+    assert_eq!(
+        Value::Char('ß'),
+        Value::from_json(json!("ß"), &options.clone().sniff_unicode_char(true))
+    );
+    assert_eq!(
+        Value::String("ab".into()),
+        Value::from_json(json!("ab"), &options)
+    );
+    assert_eq!(
+        Value::String("".into()),
+        Value::from_json(json!(""), &options)
+    );
+
+    let options = options.sniff_char(false);
+    assert_eq!(
+        Value::String("x".into()),
+        Value::from_json(json!("x"), &options)
+    );
+}
+
+#[test]
+fn test_date_formats() {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 0).unwrap();
+
+    let options = JsonConversionOptions::heuristic()
+        .date_format("%d.%m.%Y")
+        .date_time_format("%d.%m.%Y %H:%M")
+        .time_format("%H:%M");
+    assert_eq!(
+        Value::Date(date),
+        Value::from_json(json!("2024-05-01"), &options)
+    );
+    assert_eq!(
+        Value::Date(date),
+        Value::from_json(json!("01.05.2024"), &options)
+    );
+    assert_eq!(
+        Value::Time(time),
+        Value::from_json(json!("10:42"), &options)
+    );
+    assert_eq!(
+        Value::DateTime(date.and_time(time)),
+        Value::from_json(json!("01.05.2024 10:42"), &options)
+    );
+
+    // Extra formats are ignored without date sniffing
+    let options = options.sniff_date_time(false);
+    assert_eq!(
+        Value::String("01.05.2024".into()),
+        Value::from_json(json!("01.05.2024"), &options)
+    );
+}
+
+#[test]
+fn test_floats_as_decimal() {
+    let options = JsonConversionOptions::heuristic().floats_as_decimal(true);
+    assert_eq!(
+        Value::Decimal(Decimal::new(1999, 2)),
+        Value::from_json(json!(19.99), &options)
+    );
+    assert_eq!(
+        Value::Decimal(Decimal::new(15, 0)),
+        Value::from_json(json!(1.5e1), &options)
+    );
+    // Integers are not affected
+    assert_eq!(Value::U8(2), Value::from_json(json!(2), &options));
+}
+
+#[test]
+fn test_parse_integer_strings() {
+    let options = JsonConversionOptions::strict().parse_integer_strings(true);
+    assert_eq!(Value::I64(-42), Value::from_json(json!("-42"), &options));
+    assert_eq!(
+        Value::I128(i128::MIN),
+        Value::from_json(json!(i128::MIN.to_string()), &options)
+    );
+    assert_eq!(
+        Value::U128(u128::MAX),
+        Value::from_json(json!(u128::MAX.to_string()), &options)
+    );
+    assert_eq!(
+        Value::String("1.5".into()),
+        Value::from_json(json!("1.5"), &options)
+    );
+}
+
+#[test]
+fn test_record_from_json() {
+    let options = JsonConversionOptions::strict();
+    let record = Record::from_json(json!({"id": 1, "tags": ["a"], "inner": {"x": 2}}), &options);
+
+    let mut inner = Record::new();
+    add_field(inner.fields_as_mut(), "x", Value::I64(2));
+    let mut expected = Record::new();
+    add_field(expected.fields_as_mut(), "id", Value::I64(1));
+    add_field(expected.fields_as_mut(), "inner", Value::Record(inner));
+    add_field(
+        expected.fields_as_mut(),
+        "tags",
+        Value::Collection(vec![Value::String("a".into())]),
+    );
+    assert_eq!(expected, record);
+
+    assert_eq!(Record::new(), Record::from_json(json!([1]), &options));
+}
+
+#[test]
+fn test_sniff_date_time_tz_and_uuid() {
+    let text = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    // The heuristic preset keeps both as strings
+    assert_eq!(
+        Value::String("2024-05-01T10:00:00+02:00".into()),
+        Value::from(json!("2024-05-01T10:00:00+02:00"))
+    );
+    assert_eq!(Value::String(text.into()), Value::from(json!(text)));
+
+    let options = JsonConversionOptions::heuristic()
+        .sniff_date_time_tz(true)
+        .sniff_uuid(true);
+    let value = Value::from_json(json!("2024-05-01T10:00:00+02:00"), &options);
+    let Value::DateTimeTz(date_time) = value else {
+        panic!("Expected DateTimeTz, got {:?}", value);
+    };
+    assert_eq!(7200, date_time.offset().local_minus_utc());
+    assert!(matches!(
+        Value::from_json(json!("2024-05-01T08:00:00.5Z"), &options),
+        Value::DateTimeTz(_)
+    ));
+    // Without an offset, it stays a DateTime
+    assert!(matches!(
+        Value::from_json(json!("2024-05-01T10:00:00"), &options),
+        Value::DateTime(_)
+    ));
+    // Without date sniffing, timestamps with an offset are still recognised
+    let tz_only = JsonConversionOptions::strict().sniff_date_time_tz(true);
+    assert!(matches!(
+        Value::from_json(json!("2024-05-01T10:00:00Z"), &tz_only),
+        Value::DateTimeTz(_)
+    ));
+
+    assert_eq!(
+        Value::Uuid(uuid::Uuid::parse_str(text).unwrap()),
+        Value::from_json(json!(text), &options)
+    );
+    assert_eq!(
+        Value::String("67e5504410b1426f9247bb680e5fe0c8".into()),
+        Value::from_json(json!("67e5504410b1426f9247bb680e5fe0c8"), &options)
+    );
+
+    let options = JsonConversionOptions::strict();
+    assert_eq!(
+        Value::String("2024-05-01T10:00:00+02:00".into()),
+        Value::from_json(json!("2024-05-01T10:00:00+02:00"), &options)
+    );
+}
@@ -0,0 +1,256 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use serde_json::{Value as JsonValue, json};
+use uuid::Uuid;
+
+use crate::{
+    field::{Field, add_field},
+    record::Record,
+    value::Value,
+};
+
+fn all_variants() -> Vec<Value> {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_micro_opt(10, 42, 7, 123456).unwrap();
+
+    let mut nested = Record::new();
+    add_field(nested.fields_as_mut(), "z", Value::I32(1));
+    add_field(nested.fields_as_mut(), "a", Value::None);
+    add_field(nested.fields_as_mut(), "z", Value::Char('a'));
+
+    vec![
+        Value::Bool(true),
+        Value::Char('x'),
+        Value::I8(i8::MIN),
+        Value::I16(i16::MIN),
+        Value::I32(i32::MIN),
+        Value::I64(i64::MIN),
+        Value::I128(i128::MIN),
+        Value::ISize(isize::MIN),
+        Value::U8(u8::MAX),
+        Value::U16(u16::MAX),
+        Value::U32(u32::MAX),
+        Value::U64(u64::MAX),
+        Value::U128(u128::MAX),
+        Value::USize(usize::MAX),
+        Value::F32(0.1),
+        Value::F64(-1.0e-300),
+        Value::F64(f64::INFINITY),
+        Value::Decimal(Decimal::new(-123456789, 4)),
+        Value::String("2024-05-01".into()),
+        Value::Blob(vec![0, 127, 255].into()),
+        Value::Date(date),
+        Value::DateTime(date.and_time(time)),
+        Value::Time(time),
+        Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T10:00:00.25+02:00").unwrap()),
+        Value::Duration(TimeDelta::new(-90061, 5).unwrap()),
+        Value::Uuid(Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8)),
+        Value::Json(json!({"a": [1, null, {"b": "c"}]})),
+        Value::Collection(vec![Value::U8(1), Value::String("a".into())]),
+        Value::Record(nested),
+        Value::None,
+    ]
+}
+
+#[test]
+fn test_natural_scalars() {
+    assert_eq!(json!(true), JsonValue::from(&Value::Bool(true)));
+    assert_eq!(json!("x"), JsonValue::from(&Value::Char('x')));
+    assert_eq!(json!(-8), JsonValue::from(&Value::I8(-8)));
+    assert_eq!(json!(-16), JsonValue::from(&Value::I16(-16)));
+    assert_eq!(json!(-32), JsonValue::from(&Value::I32(-32)));
+    assert_eq!(json!(-64), JsonValue::from(&Value::I64(-64)));
+    assert_eq!(json!(-1), JsonValue::from(&Value::ISize(-1)));
+    assert_eq!(json!(-128), JsonValue::from(&Value::I128(-128)));
+    assert_eq!(
+        json!(i128::MIN.to_string()),
+        JsonValue::from(&Value::I128(i128::MIN))
+    );
+    assert_eq!(json!(8), JsonValue::from(&Value::U8(8)));
+    assert_eq!(json!(16), JsonValue::from(&Value::U16(16)));
+    assert_eq!(json!(32), JsonValue::from(&Value::U32(32)));
+    assert_eq!(json!(64), JsonValue::from(&Value::U64(64)));
+    assert_eq!(json!(1), JsonValue::from(&Value::USize(1)));
+    assert_eq!(json!(128), JsonValue::from(&Value::U128(128)));
+    assert_eq!(
+        json!(u128::MAX.to_string()),
+        JsonValue::from(&Value::U128(u128::MAX))
+    );
+    assert_eq!(json!(0.1), JsonValue::from(&Value::F32(0.1)));
+    assert_eq!(json!(2.5), JsonValue::from(&Value::F64(2.5)));
+    assert_eq!(JsonValue::Null, JsonValue::from(&Value::F64(f64::NAN)));
+    assert_eq!(
+        json!("12.50"),
+        JsonValue::from(&Value::Decimal(Decimal::new(1250, 2)))
+    );
+    assert_eq!(json!("text"), JsonValue::from(Value::from("text")));
+    assert_eq!(json!([1, 2]), JsonValue::from(&Value::Blob(vec![1, 2].into())));
+    assert_eq!(JsonValue::Null, JsonValue::from(&Value::None));
+}
+
+#[test]
+fn test_natural_date_time() {
+    let date = NaiveDate::from_ymd_opt(2023, 10, 27).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 0).unwrap();
+    assert_eq!(json!("2023-10-27"), JsonValue::from(&Value::Date(date)));
+    assert_eq!(json!("10:42:00"), JsonValue::from(&Value::Time(time)));
+    assert_eq!(
+        json!("2023-10-27T10:42:00"),
+        JsonValue::from(&Value::DateTime(date.and_time(time)))
+    );
+
+    let time = NaiveTime::from_hms_milli_opt(10, 42, 0, 500).unwrap();
+    assert_eq!(json!("10:42:00.500"), JsonValue::from(&Value::Time(time)));
+
+    let date_time = DateTime::parse_from_rfc3339("2024-05-01T10:00:00Z").unwrap();
+    assert_eq!(
+        json!("2024-05-01T10:00:00+00:00"),
+        JsonValue::from(&Value::DateTimeTz(date_time))
+    );
+    assert_eq!(
+        json!("PT5400S"),
+        JsonValue::from(&Value::Duration(TimeDelta::minutes(90)))
+    );
+}
+
+#[test]
+fn test_natural_uuid_and_json() {
+    let uuid = Uuid::from_u128(0x67e55044_10b1_426f_9247_bb680e5fe0c8);
+    assert_eq!(
+        json!("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+        JsonValue::from(&Value::Uuid(uuid))
+    );
+    assert_eq!(
+        json!({"a": [1]}),
+        JsonValue::from(&Value::Json(json!({"a": [1]})))
+    );
+    assert_eq!(
+        json!({"Json": {"a": [1]}}),
+        Value::Json(json!({"a": [1]})).to_tagged_json()
+    );
+}
+
+#[test]
+fn test_natural_record() {
+    let mut inner = Record::new();
+    add_field(inner.fields_as_mut(), "city", "Vienna".into());
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "name", "John Doe".into());
+    add_field(record.fields_as_mut(), "age", Value::U8(30));
+    add_field(
+        record.fields_as_mut(),
+        "tags",
+        Value::Collection(vec!["a".into(), Value::None]),
+    );
+    add_field(record.fields_as_mut(), "address", Value::Record(inner));
+
+    let expected = json!({
+        "name": "John Doe",
+        "age": 30,
+        "tags": ["a", null],
+        "address": { "city": "Vienna" }
+    });
+    assert_eq!(expected, JsonValue::from(&record));
+    assert_eq!(expected, JsonValue::from(Value::Record(record.clone())));
+
+    // The natural encoding does not keep the exact variants
+    let restored = Record::from(expected);
+    assert_eq!(
+        Value::U8(30),
+        restored.field_by_name("age").unwrap().value()
+    );
+}
+
+#[test]
+fn test_natural_duplicate_fields() {
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::I32(1));
+    add_field(record.fields_as_mut(), "a", Value::I32(2));
+    assert_eq!(json!({"a": 2}), JsonValue::from(&record));
+}
+
+#[test]
+fn test_tagged_encoding() {
+    assert_eq!(json!({"U128": "42"}), Value::U128(42).to_tagged_json());
+    assert_eq!(json!({"I8": -1}), Value::I8(-1).to_tagged_json());
+    assert_eq!(json!({"F64": "NaN"}), Value::F64(f64::NAN).to_tagged_json());
+    assert_eq!(
+        json!({"Decimal": "1.50"}),
+        Value::Decimal(Decimal::new(150, 2)).to_tagged_json()
+    );
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "b", Value::Char('x'));
+    add_field(record.fields_as_mut(), "a", Value::None);
+    assert_eq!(
+        json!({"Record": [
+            {"name": "b", "value": {"Char": "x"}},
+            {"name": "a", "value": {"None": null}}
+        ]}),
+        Value::Record(record).to_tagged_json()
+    );
+}
+
+#[test]
+fn test_tagged_round_trip() {
+    for value in all_variants() {
+        let tagged = value.to_tagged_json();
+        // Round trip through the text representation too
+        let text = tagged.to_string();
+        let parsed: JsonValue = serde_json::from_str(&text).unwrap();
+        let restored = Value::from_tagged_json(&parsed).unwrap();
+        assert_eq!(value, restored, "{}", text);
+        // Equal date-times with another offset would be equal, too
+        assert_eq!(value.to_string(), restored.to_string());
+    }
+
+    let restored = Value::from_tagged_json(&Value::F64(f64::NAN).to_tagged_json()).unwrap();
+    assert!(matches!(restored, Value::F64(f) if f.is_nan()));
+}
+
+#[test]
+fn test_tagged_record_round_trip() {
+    let mut record = Record::new();
+    for (index, value) in all_variants().into_iter().enumerate() {
+        record
+            .fields_as_mut()
+            .push(Field::new_value(&format!("field{}", index), value));
+    }
+    let tagged = record.to_tagged_json();
+    assert_eq!(record, Record::from_tagged_json(&tagged).unwrap());
+}
+
+#[test]
+fn test_tagged_errors() {
+    let invalid = [
+        json!(42),
+        json!({}),
+        json!({"U8": 1, "I8": 1}),
+        json!({"Unknown": 1}),
+        json!({"U8": 256}),
+        json!({"I8": "1"}),
+        json!({"Char": "ab"}),
+        json!({"U128": 1}),
+        json!({"F32": true}),
+        json!({"Blob": [256]}),
+        json!({"Date": "27.10.2023"}),
+        json!({"Collection": [1]}),
+        json!({"Record": {"name": "a"}}),
+        json!({"Record": [{"name": "a"}]}),
+        json!({"None": 0}),
+        json!({"DateTimeTz": "2024-05-01T10:00:00"}),
+        json!({"Duration": "1h"}),
+        json!({"Uuid": "67e55044"}),
+    ];
+    for json in invalid {
+        assert!(Value::from_tagged_json(&json).is_err(), "{}", json);
+    }
+
+    assert_eq!(
+        "Invalid tagged value for U8: 256",
+        Value::from_tagged_json(&json!({"U8": 256}))
+            .unwrap_err()
+            .to_string()
+    );
+}
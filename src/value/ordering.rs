@@ -0,0 +1,451 @@
+//! A total order, equality and hashing for [Value] and [Record]
+//!
+//! The derived [PartialEq] of [Value] compares variants strictly, so
+//! `U8(5) != I32(5)`. For sorting, grouping and deduplication
+//! [Value::compare] defines a total order, in which all numeric variants are
+//! compared by their numeric value. [Ordered] wraps a [Value] or a [Record],
+//! so that it implements [Eq], [Ord] and [Hash] consistently with
+//! [Value::compare] and can be used as a key in a [std::collections::HashMap]
+//! or a [std::collections::BTreeMap].
+//!
+//! # Order of the variants
+//! Values of different kinds are ordered by their kind:
+//!
+//! `None < Bool < numbers < Char < String < Blob < Date < DateTime <
+//! DateTimeTz < Time < Duration < Uuid < Collection < Record < Json`
+//!
+//! [Value::DateTimeTz] values are compared by the instant they describe, so
+//! the same instant with different offsets is equal. JSON documents are
+//! compared by their text.
+//!
+//! # Numbers
+//! All integer variants, [Value::Decimal], [Value::F32] and [Value::F64] are
+//! compared by their numeric value, e.g. `U8(5) == I32(5) == F64(5.0) ==
+//! Decimal(5.00)`. Floats are compared with the exact decimal value of their
+//! binary representation (up to 28 digits), so `F64(0.1)` is slightly larger
+//! than `Decimal(0.1)`. Negative infinity is smaller, positive infinity larger
+//! than every other number and `NaN` is larger than positive infinity. All
+//! `NaN`s are equal, as are `0.0` and `-0.0`.
+//!
+//! # Collections and records
+//! Collections are ordered lexicographically by their items. Records are
+//! ordered lexicographically by their fields, comparing the name and then the
+//! value of each field.
+//!
+//! # Example
+//! ```
+//! use std::collections::HashSet;
+//! use model::value::{Value, ordering::Ordered};
+//!
+//! assert!(Value::U8(5).equivalent(&Value::F64(5.0)));
+//!
+//! let mut values = vec![Value::F64(f64::NAN), Value::I32(-1), Value::None, Value::U8(7)];
+//! values.sort_by(Value::compare);
+//! assert_eq!(Value::None, values[0]);
+//! assert_eq!(Value::I32(-1), values[1]);
+//!
+//! let unique: HashSet<_> = [Value::U8(1), Value::I64(1), Value::U8(2)]
+//!     .into_iter()
+//!     .map(Ordered)
+//!     .collect();
+//! assert_eq!(2, unique.len());
+//! ```
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+
+use super::Value;
+use crate::record::Record;
+
+/// A wrapper, that implements [Eq], [Ord] and [Hash] for a [Value] or a
+/// [Record] (or a reference to them) with [Value::compare] and
+/// [Record::compare]
+#[derive(Debug, Clone, Copy)]
+pub struct Ordered<T>(pub T);
+
+/// The types, that can be wrapped into [Ordered]
+pub trait TotalOrder {
+    /// Compares `self` with `other` in the total order
+    fn total_cmp(&self, other: &Self) -> Ordering;
+
+    /// Feeds `self` into the `state` so that equal values have equal hashes
+    fn total_hash<H: Hasher>(&self, state: &mut H);
+}
+
+impl TotalOrder for Value {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+
+    fn total_hash<H: Hasher>(&self, state: &mut H) {
+        hash_value(self, state);
+    }
+}
+
+impl TotalOrder for Record {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+    }
+
+    fn total_hash<H: Hasher>(&self, state: &mut H) {
+        hash_record(self, state);
+    }
+}
+
+impl<T: TotalOrder + ?Sized> TotalOrder for &T {
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        (**self).total_cmp(*other)
+    }
+
+    fn total_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).total_hash(state);
+    }
+}
+
+impl<T: TotalOrder> PartialEq for Ordered<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.total_cmp(&other.0) == Ordering::Equal
+    }
+}
+
+impl<T: TotalOrder> Eq for Ordered<T> {}
+
+impl<T: TotalOrder> PartialOrd for Ordered<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: TotalOrder> Ord for Ordered<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl<T: TotalOrder> Hash for Ordered<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.total_hash(state);
+    }
+}
+
+impl Value {
+    /// Compares two values in the total order described in the
+    /// [module documentation](self)
+    ///
+    /// # Arguments
+    /// * `other` - The value to compare with
+    pub fn compare(&self, other: &Value) -> Ordering {
+        match (self, other) {
+            (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.cmp(b),
+            (Value::String(a), Value::String(b)) => a.cmp(b),
+            (Value::Blob(a), Value::Blob(b)) => a.cmp(b),
+            (Value::Date(a), Value::Date(b)) => a.cmp(b),
+            (Value::DateTime(a), Value::DateTime(b)) => a.cmp(b),
+            (Value::Time(a), Value::Time(b)) => a.cmp(b),
+            (Value::DateTimeTz(a), Value::DateTimeTz(b)) => a.cmp(b),
+            (Value::Duration(a), Value::Duration(b)) => a.cmp(b),
+            (Value::Uuid(a), Value::Uuid(b)) => a.cmp(b),
+            (Value::Json(a), Value::Json(b)) => a.to_string().cmp(&b.to_string()),
+            (Value::Collection(a), Value::Collection(b)) => compare_all(a, b, Value::compare),
+            (Value::Record(a), Value::Record(b)) => a.compare(b),
+            _ => match (Numeric::of(self), Numeric::of(other)) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => rank(self).cmp(&rank(other)),
+            },
+        }
+    }
+
+    /// Returns true, if both values are equal in the total order, e.g.
+    /// `U8(5)` and `I32(5)`
+    pub fn equivalent(&self, other: &Value) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+impl Record {
+    /// Compares two records lexicographically by the names and values of
+    /// their fields. See [Value::compare]
+    ///
+    /// # Arguments
+    /// * `other` - The record to compare with
+    pub fn compare(&self, other: &Record) -> Ordering {
+        compare_all(self.fields(), other.fields(), |a, b| {
+            a.name()
+                .cmp(b.name())
+                .then_with(|| a.value_as_ref().compare(b.value_as_ref()))
+        })
+    }
+
+    /// Returns true, if both records are equal in the total order
+    pub fn equivalent(&self, other: &Record) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+/// Returns the position of the kind of the value in the order of the variants
+fn rank(value: &Value) -> u8 {
+    match value {
+        Value::None => 0,
+        Value::Bool(_) => 1,
+        Value::I8(_)
+        | Value::I16(_)
+        | Value::I32(_)
+        | Value::I64(_)
+        | Value::I128(_)
+        | Value::ISize(_)
+        | Value::U8(_)
+        | Value::U16(_)
+        | Value::U32(_)
+        | Value::U64(_)
+        | Value::U128(_)
+        | Value::USize(_)
+        | Value::F32(_)
+        | Value::F64(_)
+        | Value::Decimal(_) => 2,
+        Value::Char(_) => 3,
+        Value::String(_) => 4,
+        Value::Blob(_) => 5,
+        Value::Date(_) => 6,
+        Value::DateTime(_) => 7,
+        Value::DateTimeTz(_) => 8,
+        Value::Time(_) => 9,
+        Value::Duration(_) => 10,
+        Value::Uuid(_) => 11,
+        Value::Collection(_) => 12,
+        Value::Record(_) => 13,
+        Value::Json(_) => 14,
+    }
+}
+
+/// Compares two slices lexicographically with `compare`
+fn compare_all<T>(a: &[T], b: &[T], compare: impl Fn(&T, &T) -> Ordering) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        match compare(x, y) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn hash_value<H: Hasher>(value: &Value, state: &mut H) {
+    rank(value).hash(state);
+    match value {
+        Value::None => {}
+        Value::Bool(b) => b.hash(state),
+        Value::Char(c) => c.hash(state),
+        Value::String(s) => s.hash(state),
+        Value::Blob(b) => b.hash(state),
+        Value::Date(d) => d.hash(state),
+        Value::DateTime(dt) => dt.hash(state),
+        Value::Time(t) => t.hash(state),
+        Value::DateTimeTz(dt) => dt.hash(state),
+        Value::Duration(d) => d.hash(state),
+        Value::Uuid(u) => u.hash(state),
+        Value::Json(j) => j.to_string().hash(state),
+        Value::Collection(c) => {
+            c.len().hash(state);
+            for item in c {
+                hash_value(item, state);
+            }
+        }
+        Value::Record(r) => hash_record(r, state),
+        _ => {
+            if let Some(numeric) = Numeric::of(value) {
+                numeric.hash(state);
+            }
+        }
+    }
+}
+
+fn hash_record<H: Hasher>(record: &Record, state: &mut H) {
+    record.fields().len().hash(state);
+    for field in record.fields() {
+        field.name().hash(state);
+        hash_value(field.value_as_ref(), state);
+    }
+}
+
+/// The canonical form of a numeric value. Every number has exactly one
+/// canonical form, so that the derived [Hash] is consistent with the order
+#[derive(Debug, Clone, Copy)]
+enum Numeric {
+    NegInfinity,
+    Finite(Finite),
+    PosInfinity,
+    NaN,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Finite {
+    /// All integral numbers, that fit into an [i128]
+    Int(i128),
+    /// Integral numbers larger than [i128::MAX]
+    UInt(u128),
+    /// Normalized, non-integral decimals
+    Decimal(Decimal),
+    /// Floats, that are either integral and outside of the range of [i128]
+    /// and [u128], or too small to be represented by a [Decimal]
+    Float(f64),
+}
+
+impl Numeric {
+    fn of(value: &Value) -> Option<Numeric> {
+        let finite = match value {
+            Value::I8(v) => Finite::Int(i128::from(*v)),
+            Value::I16(v) => Finite::Int(i128::from(*v)),
+            Value::I32(v) => Finite::Int(i128::from(*v)),
+            Value::I64(v) => Finite::Int(i128::from(*v)),
+            Value::I128(v) => Finite::Int(*v),
+            Value::ISize(v) => Finite::Int(*v as i128),
+            Value::U8(v) => Finite::Int(i128::from(*v)),
+            Value::U16(v) => Finite::Int(i128::from(*v)),
+            Value::U32(v) => Finite::Int(i128::from(*v)),
+            Value::U64(v) => Finite::Int(i128::from(*v)),
+            Value::U128(v) => Finite::from_u128(*v),
+            Value::USize(v) => Finite::Int(*v as i128),
+            Value::Decimal(d) => Finite::from_decimal(*d),
+            Value::F32(f) => return Some(Numeric::from_f64(f64::from(*f))),
+            Value::F64(f) => return Some(Numeric::from_f64(*f)),
+            _ => return None,
+        };
+        Some(Numeric::Finite(finite))
+    }
+
+    fn from_f64(f: f64) -> Numeric {
+        const TWO_POW_127: f64 = 170141183460469231731687303715884105728.0;
+        if f.is_nan() {
+            Numeric::NaN
+        } else if f == f64::INFINITY {
+            Numeric::PosInfinity
+        } else if f == f64::NEG_INFINITY {
+            Numeric::NegInfinity
+        } else if f.fract() == 0.0 {
+            Numeric::Finite(if (-TWO_POW_127..TWO_POW_127).contains(&f) {
+                Finite::Int(f as i128)
+            } else if (0.0..2.0 * TWO_POW_127).contains(&f) {
+                Finite::UInt(f as u128)
+            } else {
+                Finite::Float(f)
+            })
+        } else {
+            Numeric::Finite(match Decimal::from_f64_retain(f) {
+                Some(d) if !d.is_zero() => Finite::from_decimal(d),
+                _ => Finite::Float(f),
+            })
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Numeric::NegInfinity => 0,
+            Numeric::Finite(_) => 1,
+            Numeric::PosInfinity => 2,
+            Numeric::NaN => 3,
+        }
+    }
+}
+
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Numeric {}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Numeric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Numeric::Finite(a), Numeric::Finite(b)) => a.compare(b),
+            _ => self.rank().cmp(&other.rank()),
+        }
+    }
+}
+
+impl Hash for Numeric {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.rank().hash(state);
+        if let Numeric::Finite(finite) = self {
+            match finite {
+                Finite::Int(i) => (0u8, i).hash(state),
+                Finite::UInt(u) => (1u8, u).hash(state),
+                Finite::Decimal(d) => (2u8, d.serialize()).hash(state),
+                Finite::Float(f) => (3u8, f.to_bits()).hash(state),
+            }
+        }
+    }
+}
+
+impl Finite {
+    fn from_u128(value: u128) -> Finite {
+        match i128::try_from(value) {
+            Ok(i) => Finite::Int(i),
+            Err(_) => Finite::UInt(value),
+        }
+    }
+
+    fn from_decimal(value: Decimal) -> Finite {
+        let normalized = value.normalize();
+        if normalized.scale() == 0 {
+            Finite::Int(normalized.mantissa())
+        } else {
+            Finite::Decimal(normalized)
+        }
+    }
+
+    /// Returns the ordering of this number compared with zero
+    fn signum(&self) -> Ordering {
+        match self {
+            Finite::Int(i) => i.cmp(&0),
+            Finite::UInt(_) => Ordering::Greater,
+            Finite::Decimal(d) => d.cmp(&Decimal::ZERO),
+            Finite::Float(f) => f.total_cmp(&0.0),
+        }
+    }
+
+    fn compare(&self, other: &Finite) -> Ordering {
+        match (self, other) {
+            (Finite::Int(a), Finite::Int(b)) => a.cmp(b),
+            (Finite::UInt(a), Finite::UInt(b)) => a.cmp(b),
+            (Finite::Decimal(a), Finite::Decimal(b)) => a.cmp(b),
+            (Finite::Float(a), Finite::Float(b)) => a.total_cmp(b),
+            (Finite::Int(_), Finite::UInt(_)) | (Finite::Decimal(_), Finite::UInt(_)) => {
+                Ordering::Less
+            }
+            (Finite::Int(i), Finite::Decimal(d)) => match Decimal::from_i128(*i) {
+                Some(i) => i.cmp(d),
+                // The integer is outside of the range of a decimal
+                None => i.cmp(&0),
+            },
+            (Finite::Float(f), other) => {
+                if f.abs() >= 1.0 {
+                    // Outside of the range of all integers
+                    f.total_cmp(&0.0)
+                } else {
+                    // Closer to zero than any other non-zero number
+                    match other.signum() {
+                        Ordering::Equal => f.total_cmp(&0.0),
+                        signum => signum.reverse(),
+                    }
+                }
+            }
+            (_, Finite::Float(_)) | (Finite::UInt(_), _) | (Finite::Decimal(_), Finite::Int(_)) => {
+                other.compare(self).reverse()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,249 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap, HashSet, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use crate::{field::add_field, record::Record, value::Value};
+
+use super::Ordered;
+
+fn hash_of(value: &Value) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    Ordered(value).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn assert_equivalent(a: Value, b: Value) {
+    assert_eq!(Ordering::Equal, a.compare(&b), "{:?} == {:?}", a, b);
+    assert_eq!(hash_of(&a), hash_of(&b), "hash {:?} == {:?}", a, b);
+}
+
+fn assert_less(a: Value, b: Value) {
+    assert_eq!(Ordering::Less, a.compare(&b), "{:?} < {:?}", a, b);
+    assert_eq!(Ordering::Greater, b.compare(&a), "{:?} > {:?}", b, a);
+}
+
+#[test]
+fn test_cross_numeric_equality() {
+    assert_equivalent(Value::U8(5), Value::I32(5));
+    assert_equivalent(Value::U128(5), Value::ISize(5));
+    assert_equivalent(Value::F64(5.0), Value::I64(5));
+    assert_equivalent(Value::F32(-2.5), Value::F64(-2.5));
+    assert_equivalent(Value::Decimal(Decimal::new(500, 2)), Value::U16(5));
+    assert_equivalent(
+        Value::Decimal(Decimal::new(250, 2)),
+        Value::Decimal(Decimal::new(25, 1)),
+    );
+    assert_equivalent(Value::Decimal(Decimal::new(5, 1)), Value::F64(0.5));
+    assert_equivalent(Value::F64(0.0), Value::F64(-0.0));
+    assert_equivalent(Value::F64(f64::NAN), Value::F32(f32::NAN));
+    assert_equivalent(Value::F64(f64::INFINITY), Value::F32(f32::INFINITY));
+    assert_equivalent(Value::U128(u128::MAX), Value::U128(u128::MAX));
+    assert_equivalent(Value::F64(2f64.powi(127)), Value::U128(1 << 127));
+
+    // The derived PartialEq stays strict
+    assert_ne!(Value::U8(5), Value::I32(5));
+}
+
+#[test]
+fn test_numeric_order() {
+    let ordered = [
+        Value::F64(f64::NEG_INFINITY),
+        Value::F64(-1e300),
+        Value::I128(i128::MIN),
+        Value::Decimal(Decimal::MIN),
+        Value::I64(-1),
+        Value::F64(-1e-300),
+        Value::U8(0),
+        Value::F64(1e-300),
+        Value::Decimal(Decimal::new(1, 1)),
+        Value::F64(0.1),
+        Value::F32(0.5),
+        Value::U8(1),
+        Value::Decimal(Decimal::new(15, 1)),
+        Value::F64(2.5),
+        Value::Decimal(Decimal::MAX),
+        Value::I128(i128::MAX),
+        Value::U128(u128::MAX),
+        Value::F64(1e300),
+        Value::F64(f64::INFINITY),
+        Value::F64(f64::NAN),
+    ];
+    for (index, a) in ordered.iter().enumerate() {
+        for b in &ordered[index + 1..] {
+            assert_less(a.clone(), b.clone());
+        }
+    }
+}
+
+#[test]
+fn test_variant_order() {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 0).unwrap();
+    let ordered = [
+        Value::None,
+        Value::Bool(false),
+        Value::Bool(true),
+        Value::F64(f64::NEG_INFINITY),
+        Value::F64(f64::NAN),
+        Value::Char('a'),
+        Value::String("".into()),
+        Value::String("a".into()),
+        Value::Blob(vec![0].into()),
+        Value::Date(date),
+        Value::DateTime(date.and_time(time)),
+        Value::Time(time),
+        Value::Collection(vec![]),
+        Value::Record(Record::new()),
+    ];
+    for (index, a) in ordered.iter().enumerate() {
+        for b in &ordered[index + 1..] {
+            assert_less(a.clone(), b.clone());
+        }
+    }
+}
+
+#[test]
+fn test_collections() {
+    assert_less(
+        Value::Collection(vec![Value::U8(1), Value::U8(2)]),
+        Value::Collection(vec![Value::U8(1), Value::U8(3)]),
+    );
+    assert_less(
+        Value::Collection(vec![Value::U8(1)]),
+        Value::Collection(vec![Value::U8(1), Value::None]),
+    );
+    assert_equivalent(
+        Value::Collection(vec![Value::U8(1), Value::F64(2.0)]),
+        Value::Collection(vec![Value::I64(1), Value::I8(2)]),
+    );
+}
+
+#[test]
+fn test_records() {
+    let mut a = Record::new();
+    add_field(a.fields_as_mut(), "id", Value::U8(1));
+    add_field(a.fields_as_mut(), "name", "a".into());
+    let mut b = Record::new();
+    add_field(b.fields_as_mut(), "id", Value::I64(1));
+    add_field(b.fields_as_mut(), "name", "a".into());
+    assert!(a.equivalent(&b));
+    assert_equivalent(Value::Record(a.clone()), Value::Record(b.clone()));
+
+    add_field(b.fields_as_mut(), "x", Value::None);
+    assert_eq!(Ordering::Less, a.compare(&b));
+
+    let mut c = Record::new();
+    add_field(c.fields_as_mut(), "id", Value::U8(2));
+    assert_eq!(Ordering::Less, a.compare(&c));
+
+    let mut d = Record::new();
+    add_field(d.fields_as_mut(), "a", Value::U8(9));
+    assert_eq!(Ordering::Greater, a.compare(&d));
+}
+
+#[test]
+// The name index of a Record is not part of its hash, see the note on Record
+#[allow(clippy::mutable_key_type)]
+fn test_sort_and_group() {
+    let mut values = vec![
+        Value::String("b".into()),
+        Value::F64(1.5),
+        Value::None,
+        Value::U8(1),
+        Value::I32(-3),
+    ];
+    values.sort_by(Value::compare);
+    assert_eq!(
+        vec![
+            Value::None,
+            Value::I32(-3),
+            Value::U8(1),
+            Value::F64(1.5),
+            Value::String("b".into()),
+        ],
+        values
+    );
+
+    let mut groups: HashMap<Ordered<Value>, usize> = HashMap::new();
+    for value in [Value::U8(1), Value::I64(1), Value::F32(1.0), Value::U8(2)] {
+        *groups.entry(Ordered(value)).or_default() += 1;
+    }
+    assert_eq!(Some(&3), groups.get(&Ordered(Value::I16(1))));
+    assert_eq!(Some(&1), groups.get(&Ordered(Value::U8(2))));
+
+    let set: BTreeSet<_> = [Value::U8(3), Value::I8(-1), Value::U16(3)]
+        .iter()
+        .map(Ordered)
+        .collect();
+    assert_eq!(2, set.len());
+}
+
+#[test]
+// The name index of a Record is not part of its hash, see the note on Record
+#[allow(clippy::mutable_key_type)]
+fn test_dedup_records() {
+    let mut a = Record::new();
+    add_field(a.fields_as_mut(), "id", Value::U8(1));
+    let mut b = Record::new();
+    add_field(b.fields_as_mut(), "id", Value::I32(1));
+    let mut c = Record::new();
+    add_field(c.fields_as_mut(), "id", Value::I32(2));
+
+    let unique: HashSet<_> = [a, b, c].into_iter().map(Ordered).collect();
+    assert_eq!(2, unique.len());
+}
+
+#[test]
+fn test_new_variants() {
+    let a = Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap());
+    let b = Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T08:00:00Z").unwrap());
+    let c = Value::DateTimeTz(DateTime::parse_from_rfc3339("2024-05-01T09:00:00+00:00").unwrap());
+    // The same instant with different offsets
+    assert!(a.equivalent(&b));
+    assert_eq!(hash_of(&a), hash_of(&b));
+    assert_eq!(Ordering::Less, a.compare(&c));
+
+    assert_eq!(
+        Ordering::Less,
+        Value::Duration(TimeDelta::seconds(-1)).compare(&Value::Duration(TimeDelta::zero()))
+    );
+    assert_eq!(
+        Ordering::Greater,
+        Value::Uuid(uuid::Uuid::max()).compare(&Value::Uuid(uuid::Uuid::nil()))
+    );
+    let json = Value::Json(serde_json::json!({"a": 1}));
+    assert!(json.equivalent(&Value::Json(serde_json::json!({"a": 1}))));
+    assert_eq!(
+        hash_of(&json),
+        hash_of(&Value::Json(serde_json::json!({"a": 1})))
+    );
+
+    let mut values = [
+        json.clone(),
+        Value::Uuid(uuid::Uuid::nil()),
+        Value::Duration(TimeDelta::zero()),
+        Value::Time(NaiveTime::MIN),
+        a.clone(),
+        Value::Record(Record::new()),
+        Value::Collection(vec![]),
+    ];
+    values.sort_by(Value::compare);
+    let names: Vec<&str> = values.iter().map(Value::type_name).collect();
+    assert_eq!(
+        vec![
+            "DateTimeTz",
+            "Time",
+            "Duration",
+            "Uuid",
+            "Collection",
+            "Record",
+            "Json"
+        ],
+        names
+    );
+}
@@ -0,0 +1,420 @@
+//! Parsing of strings into a [Value] of a given [ValueType]
+//!
+//! Text based importers (e.g. CSV or fixed width files) only know strings.
+//! [Value::parse] converts such a string into the requested [ValueType]. The
+//! [ParseOptions] describe the format of the input:
+//! * The decimal and thousands separators of numbers
+//! * The [chrono::format::strftime] formats for dates, times and date-times
+//!   with and without offset
+//! * The spellings of `true` and `false`
+//! * The encoding of blobs (hex or base64)
+//! * If empty strings become [Value::None] and if the input is trimmed
+//!
+//! [ValueType::Duration] expects an ISO 8601 duration like `PT1H30M` or
+//! `-P2DT0.5S` (years and months are not supported, since their length
+//! varies), [ValueType::Uuid] a UUID in any of the usual notations.
+//! [ValueType::Collection] and [ValueType::Record] expect a JSON array or
+//! object, [ValueType::Json] any JSON document.
+//!
+//! # Example
+//! ```
+//! use model::value::{Value, parse::ParseOptions, value_type::ValueType};
+//! use rust_decimal::Decimal;
+//!
+//! let options = ParseOptions::default()
+//!     .decimal_separator(',')
+//!     .thousands_separator('.');
+//! assert_eq!(
+//!     Value::Decimal(Decimal::new(123456, 2)),
+//!     Value::parse("1.234,56", ValueType::Decimal, &options).unwrap()
+//! );
+//! assert_eq!(Value::None, Value::parse("", ValueType::I32, &options).unwrap());
+//! assert!(Value::parse("abc", ValueType::I32, &options).is_err());
+//! ```
+use std::{fmt::Display, str::FromStr};
+
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use super::{
+    Value,
+    json::{DATE_FORMAT, DATE_TIME_FORMAT, DATE_TIME_TZ_FORMAT, TIME_FORMAT},
+    value_type::ValueType,
+};
+
+/// The encoding of a [Value::Blob] in a string
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlobEncoding {
+    /// Two hexadecimal digits per byte, optionally prefixed with `0x`
+    #[default]
+    Hex,
+    /// Standard base64 with padding
+    Base64,
+}
+
+/// The error returned, when a string cannot be parsed into a [Value]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    input: String,
+    value_type: ValueType,
+    message: String,
+}
+
+impl ParseError {
+    fn new(input: &str, value_type: ValueType, message: impl Display) -> Self {
+        Self {
+            input: input.to_string(),
+            value_type,
+            message: message.to_string(),
+        }
+    }
+
+    /// Returns the string, that could not be parsed
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns the requested [ValueType]
+    pub fn value_type(&self) -> ValueType {
+        self.value_type
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Cannot parse '{}' as {}: {}",
+            self.input, self.value_type, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Options for [Value::parse]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    decimal_separator: char,
+    thousands_separator: Option<char>,
+    date_format: String,
+    date_time_format: String,
+    time_format: String,
+    date_time_tz_format: String,
+    true_values: Vec<String>,
+    false_values: Vec<String>,
+    blob_encoding: BlobEncoding,
+    empty_as_none: bool,
+    trim: bool,
+}
+
+impl Default for ParseOptions {
+    /// Returns the default options:
+    /// * `.` as decimal separator and no thousands separator
+    /// * The ISO 8601 formats `%Y-%m-%d`, `%Y-%m-%dT%H:%M:%S%.f` and
+    ///   `%H:%M:%S%.f`, and RFC 3339 (`%+`) for date-times with offset
+    /// * `true`/`1` and `false`/`0` as booleans (like
+    ///   [crate::xml::config::Configuration::get_bool])
+    /// * Hex encoded blobs
+    /// * Empty strings become [Value::None] and the input is trimmed
+    fn default() -> Self {
+        Self {
+            decimal_separator: '.',
+            thousands_separator: None,
+            date_format: DATE_FORMAT.to_string(),
+            date_time_format: DATE_TIME_FORMAT.to_string(),
+            time_format: TIME_FORMAT.to_string(),
+            date_time_tz_format: DATE_TIME_TZ_FORMAT.to_string(),
+            true_values: vec!["true".to_string(), "1".to_string()],
+            false_values: vec!["false".to_string(), "0".to_string()],
+            blob_encoding: BlobEncoding::Hex,
+            empty_as_none: true,
+            trim: true,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Sets the decimal separator for floats and decimals
+    pub fn decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Sets the thousands separator, that is ignored in numbers
+    pub fn thousands_separator(mut self, separator: char) -> Self {
+        self.thousands_separator = Some(separator);
+        self
+    }
+
+    /// Sets the [chrono::format::strftime] format for [ValueType::Date]
+    pub fn date_format(mut self, format: &str) -> Self {
+        self.date_format = format.to_string();
+        self
+    }
+
+    /// Sets the [chrono::format::strftime] format for [ValueType::DateTime]
+    pub fn date_time_format(mut self, format: &str) -> Self {
+        self.date_time_format = format.to_string();
+        self
+    }
+
+    /// Sets the [chrono::format::strftime] format for [ValueType::Time]
+    pub fn time_format(mut self, format: &str) -> Self {
+        self.time_format = format.to_string();
+        self
+    }
+
+    /// Sets the [chrono::format::strftime] format for [ValueType::DateTimeTz].
+    /// It must contain the offset, e.g. `%z`
+    pub fn date_time_tz_format(mut self, format: &str) -> Self {
+        self.date_time_tz_format = format.to_string();
+        self
+    }
+
+    /// Sets the spellings for `true` and `false`. They are compared
+    /// case-insensitively
+    pub fn bool_values(mut self, true_values: &[&str], false_values: &[&str]) -> Self {
+        self.true_values = true_values.iter().map(|s| s.to_string()).collect();
+        self.false_values = false_values.iter().map(|s| s.to_string()).collect();
+        self
+    }
+
+    /// Sets the [BlobEncoding]
+    pub fn blob_encoding(mut self, encoding: BlobEncoding) -> Self {
+        self.blob_encoding = encoding;
+        self
+    }
+
+    /// Sets, if an empty input becomes [Value::None] for every [ValueType]
+    pub fn empty_as_none(mut self, empty_as_none: bool) -> Self {
+        self.empty_as_none = empty_as_none;
+        self
+    }
+
+    /// Sets, if leading and trailing whitespace is removed before parsing
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Removes the thousands separators and replaces the decimal separator
+    /// with a `.`
+    fn normalize_number(&self, input: &str) -> String {
+        input
+            .chars()
+            .filter(|c| Some(*c) != self.thousands_separator)
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect()
+    }
+}
+
+impl Value {
+    /// Parses the `input` into a [Value] of the given [ValueType]
+    ///
+    /// # Arguments
+    /// * `input` - The string to parse
+    /// * `value_type` - The [ValueType] of the result
+    /// * `options` - The [ParseOptions] describing the format of `input`
+    pub fn parse(
+        input: &str,
+        value_type: ValueType,
+        options: &ParseOptions,
+    ) -> Result<Value, ParseError> {
+        let text = if options.trim { input.trim() } else { input };
+        if text.is_empty() && options.empty_as_none {
+            return Ok(Value::None);
+        }
+
+        let error = |message: &dyn Display| ParseError::new(input, value_type, message);
+        macro_rules! number {
+            ($variant:ident) => {
+                options
+                    .normalize_number(text)
+                    .parse()
+                    .map(Value::$variant)
+                    .map_err(|e| error(&e))
+            };
+        }
+
+        match value_type {
+            ValueType::Bool => {
+                let matches =
+                    |values: &[String]| values.iter().any(|v| v.eq_ignore_ascii_case(text));
+                if matches(&options.true_values) {
+                    Ok(Value::Bool(true))
+                } else if matches(&options.false_values) {
+                    Ok(Value::Bool(false))
+                } else {
+                    Err(error(&"unknown boolean value"))
+                }
+            }
+            ValueType::Char => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Value::Char(c)),
+                    _ => Err(error(&"expected exactly one character")),
+                }
+            }
+            ValueType::I8 => number!(I8),
+            ValueType::I16 => number!(I16),
+            ValueType::I32 => number!(I32),
+            ValueType::I64 => number!(I64),
+            ValueType::I128 => number!(I128),
+            ValueType::ISize => number!(ISize),
+            ValueType::U8 => number!(U8),
+            ValueType::U16 => number!(U16),
+            ValueType::U32 => number!(U32),
+            ValueType::U64 => number!(U64),
+            ValueType::U128 => number!(U128),
+            ValueType::USize => number!(USize),
+            ValueType::F32 => number!(F32),
+            ValueType::F64 => number!(F64),
+            ValueType::Decimal => {
+                let normalized = options.normalize_number(text);
+                Decimal::from_str(&normalized)
+                    .or_else(|_| Decimal::from_scientific(&normalized))
+                    .map(Value::Decimal)
+                    .map_err(|e| error(&e))
+            }
+            ValueType::String => Ok(Value::from(text)),
+            ValueType::Blob => match options.blob_encoding {
+                BlobEncoding::Hex => decode_hex(text).map(Value::from).map_err(|e| error(&e)),
+                BlobEncoding::Base64 => base64::engine::general_purpose::STANDARD
+                    .decode(text)
+                    .map(Value::from)
+                    .map_err(|e| error(&e)),
+            },
+            ValueType::Date => NaiveDate::parse_from_str(text, &options.date_format)
+                .map(Value::Date)
+                .map_err(|e| error(&e)),
+            ValueType::DateTime => NaiveDateTime::parse_from_str(text, &options.date_time_format)
+                .map(Value::DateTime)
+                .map_err(|e| error(&e)),
+            ValueType::Time => NaiveTime::parse_from_str(text, &options.time_format)
+                .map(Value::Time)
+                .map_err(|e| error(&e)),
+            ValueType::DateTimeTz => DateTime::parse_from_str(text, &options.date_time_tz_format)
+                .map(Value::DateTimeTz)
+                .map_err(|e| error(&e)),
+            ValueType::Duration => parse_duration(text)
+                .map(Value::Duration)
+                .map_err(|e| error(&e)),
+            ValueType::Uuid => Uuid::parse_str(text)
+                .map(Value::Uuid)
+                .map_err(|e| error(&e)),
+            ValueType::Json => serde_json::from_str(text)
+                .map(Value::Json)
+                .map_err(|e| error(&e)),
+            ValueType::Collection | ValueType::Record => {
+                match serde_json::from_str::<JsonValue>(text).map_err(|e| error(&e))? {
+                    json @ JsonValue::Array(_) if value_type == ValueType::Collection => {
+                        Ok(Value::from(json))
+                    }
+                    json @ JsonValue::Object(_) if value_type == ValueType::Record => {
+                        Ok(Value::from(json))
+                    }
+                    _ => Err(error(&"unexpected JSON type")),
+                }
+            }
+            ValueType::None => Ok(Value::None),
+        }
+    }
+}
+
+/// Parses an ISO 8601 duration in the form `[-]P[nW][nD][T[nH][nM][n[.f]S]]`,
+/// as it is written by the [Display] of [TimeDelta]
+pub(crate) fn parse_duration(text: &str) -> Result<TimeDelta, String> {
+    let (negative, rest) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let rest = rest
+        .strip_prefix('P')
+        .ok_or_else(|| "expected an ISO 8601 duration starting with 'P'".to_string())?;
+    let overflow = || "duration out of range".to_string();
+
+    let mut duration = TimeDelta::zero();
+    let mut in_time = false;
+    let mut components = 0;
+    let mut number = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' | '.' | ',' => number.push(if c == ',' { '.' } else { c }),
+            'T' if !in_time && number.is_empty() => in_time = true,
+            _ => {
+                let seconds_per_unit = match (in_time, c) {
+                    (false, 'W') => 7 * 86_400,
+                    (false, 'D') => 86_400,
+                    (true, 'H') => 3_600,
+                    (true, 'M') => 60,
+                    (true, 'S') => 1,
+                    _ => return Err(format!("unexpected '{}'", c)),
+                };
+                let (whole, fraction) = match number.split_once('.') {
+                    Some(_) if c != 'S' => {
+                        return Err(format!("only seconds can have a fraction, not '{}'", c));
+                    }
+                    Some((whole, fraction)) => (whole, fraction),
+                    None => (number.as_str(), ""),
+                };
+                if whole.is_empty() || fraction.len() > 9 {
+                    return Err(format!("invalid number '{}'", number));
+                }
+                let units: i64 = whole.parse().map_err(|_| overflow())?;
+                let nanos = if fraction.is_empty() {
+                    0
+                } else {
+                    format!("{:0<9}", fraction)
+                        .parse::<u32>()
+                        .map_err(|e| e.to_string())?
+                };
+                let component = units
+                    .checked_mul(seconds_per_unit)
+                    .and_then(|seconds| TimeDelta::new(seconds, nanos))
+                    .ok_or_else(overflow)?;
+                duration = duration.checked_add(&component).ok_or_else(overflow)?;
+                components += 1;
+                number.clear();
+            }
+        }
+    }
+    if !number.is_empty() || components == 0 {
+        return Err("incomplete duration".to_string());
+    }
+    Ok(if negative { -duration } else { duration })
+}
+
+/// Decodes a string of hexadecimal digits, optionally prefixed with `0x`
+fn decode_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+    if let Some(position) = digits.find(|c: char| !c.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex digit at position {}", position));
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err("odd number of hex digits".to_string());
+    }
+    Ok(digits
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| (hex_digit(pair[0]) << 4) | hex_digit(pair[1]))
+        .collect())
+}
+
+/// Returns the value of a valid hexadecimal digit
+fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests;
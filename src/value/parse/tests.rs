@@ -0,0 +1,317 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use crate::{
+    field::add_field,
+    record::Record,
+    value::{Value, value_type::ValueType},
+};
+
+use super::{BlobEncoding, ParseOptions};
+
+fn parse(input: &str, value_type: ValueType) -> Value {
+    Value::parse(input, value_type, &ParseOptions::default()).unwrap()
+}
+
+#[test]
+fn test_integers() {
+    assert_eq!(Value::I8(-128), parse("-128", ValueType::I8));
+    assert_eq!(Value::U16(4711), parse(" 4711 ", ValueType::U16));
+    assert_eq!(Value::I32(42), parse("+42", ValueType::I32));
+    assert_eq!(
+        Value::I64(i64::MIN),
+        parse(&i64::MIN.to_string(), ValueType::I64)
+    );
+    assert_eq!(
+        Value::U128(u128::MAX),
+        parse(&u128::MAX.to_string(), ValueType::U128)
+    );
+    assert_eq!(Value::ISize(-1), parse("-1", ValueType::ISize));
+    assert_eq!(Value::USize(1), parse("1", ValueType::USize));
+
+    let options = ParseOptions::default().thousands_separator(',');
+    assert_eq!(
+        Value::U32(1_234_567),
+        Value::parse("1,234,567", ValueType::U32, &options).unwrap()
+    );
+
+    let error = Value::parse("256", ValueType::U8, &ParseOptions::default()).unwrap_err();
+    assert_eq!("256", error.input());
+    assert_eq!(ValueType::U8, error.value_type());
+    assert_eq!(
+        "Cannot parse '256' as U8: number too large to fit in target type",
+        error.to_string()
+    );
+    assert!(Value::parse("1.5", ValueType::I32, &ParseOptions::default()).is_err());
+}
+
+#[test]
+fn test_floats_and_decimals() {
+    assert_eq!(Value::F32(1.5), parse("1.5", ValueType::F32));
+    assert_eq!(Value::F64(-1e10), parse("-1e10", ValueType::F64));
+    assert_eq!(
+        Value::Decimal(Decimal::new(1999, 2)),
+        parse("19.99", ValueType::Decimal)
+    );
+    assert_eq!(
+        Value::Decimal(Decimal::new(15, 0)),
+        parse("1.5e1", ValueType::Decimal)
+    );
+
+    let german = ParseOptions::default()
+        .decimal_separator(',')
+        .thousands_separator('.');
+    assert_eq!(
+        Value::F64(1234.5),
+        Value::parse("1.234,5", ValueType::F64, &german).unwrap()
+    );
+    assert_eq!(
+        Value::Decimal(Decimal::new(-123456, 2)),
+        Value::parse("-1.234,56", ValueType::Decimal, &german).unwrap()
+    );
+
+    let swiss = ParseOptions::default().thousands_separator('\'');
+    assert_eq!(
+        Value::Decimal(Decimal::new(100000050, 2)),
+        Value::parse("1'000'000.50", ValueType::Decimal, &swiss).unwrap()
+    );
+    assert!(Value::parse("abc", ValueType::Decimal, &german).is_err());
+}
+
+#[test]
+fn test_bool() {
+    assert_eq!(Value::Bool(true), parse("TRUE", ValueType::Bool));
+    assert_eq!(Value::Bool(true), parse("1", ValueType::Bool));
+    assert_eq!(Value::Bool(false), parse("False", ValueType::Bool));
+    assert_eq!(Value::Bool(false), parse("0", ValueType::Bool));
+    assert!(Value::parse("yes", ValueType::Bool, &ParseOptions::default()).is_err());
+
+    let options = ParseOptions::default().bool_values(&["ja", "j"], &["nein", "n"]);
+    assert_eq!(
+        Value::Bool(true),
+        Value::parse("J", ValueType::Bool, &options).unwrap()
+    );
+    assert_eq!(
+        Value::Bool(false),
+        Value::parse("nein", ValueType::Bool, &options).unwrap()
+    );
+    assert!(Value::parse("true", ValueType::Bool, &options).is_err());
+}
+
+#[test]
+fn test_strings() {
+    assert_eq!(Value::Char('ß'), parse("ß", ValueType::Char));
+    assert!(Value::parse("ab", ValueType::Char, &ParseOptions::default()).is_err());
+    assert_eq!(
+        Value::String("text".into()),
+        parse("  text ", ValueType::String)
+    );
+
+    let options = ParseOptions::default().trim(false);
+    assert_eq!(
+        Value::String("  text ".into()),
+        Value::parse("  text ", ValueType::String, &options).unwrap()
+    );
+    assert_eq!(
+        Value::Char(' '),
+        Value::parse(" ", ValueType::Char, &options).unwrap()
+    );
+}
+
+#[test]
+fn test_empty() {
+    for value_type in ValueType::ALL {
+        assert_eq!(Value::None, parse("", value_type));
+        assert_eq!(Value::None, parse("  ", value_type));
+    }
+
+    let options = ParseOptions::default().empty_as_none(false);
+    assert_eq!(
+        Value::String("".into()),
+        Value::parse("", ValueType::String, &options).unwrap()
+    );
+    assert_eq!(
+        Value::Blob(vec![].into()),
+        Value::parse("", ValueType::Blob, &options).unwrap()
+    );
+    assert!(Value::parse("", ValueType::I32, &options).is_err());
+}
+
+#[test]
+fn test_blobs() {
+    assert_eq!(
+        Value::Blob(vec![0, 127, 255].into()),
+        parse("007fFF", ValueType::Blob)
+    );
+    assert_eq!(
+        Value::Blob(vec![0xca, 0xfe].into()),
+        parse("0xCAFE", ValueType::Blob)
+    );
+    for invalid in ["abc", "+f", "zz", "0x1"] {
+        assert!(
+            Value::parse(invalid, ValueType::Blob, &ParseOptions::default()).is_err(),
+            "{}",
+            invalid
+        );
+    }
+
+    let options = ParseOptions::default().blob_encoding(BlobEncoding::Base64);
+    assert_eq!(
+        Value::Blob(b"hello".as_slice().into()),
+        Value::parse("aGVsbG8=", ValueType::Blob, &options).unwrap()
+    );
+    assert!(Value::parse("a$", ValueType::Blob, &options).is_err());
+}
+
+#[test]
+fn test_dates() {
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 7).unwrap();
+    assert_eq!(Value::Date(date), parse("2024-05-01", ValueType::Date));
+    assert_eq!(Value::Time(time), parse("10:42:07", ValueType::Time));
+    assert_eq!(
+        Value::Time(NaiveTime::from_hms_milli_opt(10, 42, 7, 500).unwrap()),
+        parse("10:42:07.500", ValueType::Time)
+    );
+    assert_eq!(
+        Value::DateTime(date.and_time(time)),
+        parse("2024-05-01T10:42:07", ValueType::DateTime)
+    );
+
+    let options = ParseOptions::default()
+        .date_format("%d.%m.%Y")
+        .date_time_format("%d.%m.%Y %H:%M:%S")
+        .time_format("%I:%M:%S %p");
+    assert_eq!(
+        Value::Date(date),
+        Value::parse("01.05.2024", ValueType::Date, &options).unwrap()
+    );
+    assert_eq!(
+        Value::DateTime(date.and_time(time)),
+        Value::parse("01.05.2024 10:42:07", ValueType::DateTime, &options).unwrap()
+    );
+    assert_eq!(
+        Value::Time(time),
+        Value::parse("10:42:07 AM", ValueType::Time, &options).unwrap()
+    );
+    assert!(Value::parse("2024-05-01", ValueType::Date, &options).is_err());
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(
+        Value::Collection(vec![Value::U8(1), Value::String("ab".into())]),
+        parse(r#"[1, "ab"]"#, ValueType::Collection)
+    );
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "a", Value::U8(1));
+    assert_eq!(
+        Value::Record(record),
+        parse(r#"{"a": 1}"#, ValueType::Record)
+    );
+
+    let options = ParseOptions::default();
+    assert!(Value::parse("[1]", ValueType::Record, &options).is_err());
+    assert!(Value::parse("{}", ValueType::Collection, &options).is_err());
+    assert!(Value::parse("[1", ValueType::Collection, &options).is_err());
+}
+
+#[test]
+fn test_none() {
+    assert_eq!(Value::None, parse("anything", ValueType::None));
+}
+
+#[test]
+fn test_date_time_tz() {
+    let expected = DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap();
+    let Value::DateTimeTz(parsed) = parse("2024-05-01T10:00:00+02:00", ValueType::DateTimeTz)
+    else {
+        panic!("Expected DateTimeTz");
+    };
+    assert_eq!(expected, parsed);
+    assert_eq!(expected.offset(), parsed.offset());
+    assert_eq!(
+        Value::DateTimeTz(expected),
+        parse("2024-05-01T08:00:00Z", ValueType::DateTimeTz)
+    );
+
+    let options = ParseOptions::default().date_time_tz_format("%d.%m.%Y %H:%M %z");
+    assert_eq!(
+        Value::DateTimeTz(expected),
+        Value::parse("01.05.2024 10:00 +0200", ValueType::DateTimeTz, &options).unwrap()
+    );
+    assert!(
+        Value::parse(
+            "2024-05-01T10:00:00",
+            ValueType::DateTimeTz,
+            &ParseOptions::default()
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_duration() {
+    for (input, expected) in [
+        ("PT0S", TimeDelta::zero()),
+        ("P0D", TimeDelta::zero()),
+        ("PT1H30M", TimeDelta::minutes(90)),
+        (
+            "P1W2DT3H4M5.5S",
+            TimeDelta::new(9 * 86_400 + 3 * 3_600 + 4 * 60 + 5, 500_000_000).unwrap(),
+        ),
+        ("-PT0,25S", TimeDelta::milliseconds(-250)),
+        ("PT0.000000001S", TimeDelta::nanoseconds(1)),
+    ] {
+        assert_eq!(
+            Value::Duration(expected),
+            parse(input, ValueType::Duration),
+            "{}",
+            input
+        );
+    }
+
+    // The Display of a duration can be parsed again
+    let duration = TimeDelta::new(-90_061, 5).unwrap();
+    assert_eq!(
+        Value::Duration(duration),
+        parse(&duration.to_string(), ValueType::Duration)
+    );
+
+    let options = ParseOptions::default();
+    for input in [
+        "1H", "P", "PT", "P1Y", "P1M", "PT1.5H", "PT1", "P1H", "PT1S2", "PT.S",
+    ] {
+        assert!(
+            Value::parse(input, ValueType::Duration, &options).is_err(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_uuid_and_json() {
+    let expected = Value::Uuid(uuid::Uuid::from_u128(
+        0x67e55044_10b1_426f_9247_bb680e5fe0c8,
+    ));
+    assert_eq!(
+        expected,
+        parse("67e55044-10b1-426f-9247-bb680e5fe0c8", ValueType::Uuid)
+    );
+    assert_eq!(
+        expected,
+        parse("67E5504410B1426F9247BB680E5FE0C8", ValueType::Uuid)
+    );
+    assert!(Value::parse("67e55044", ValueType::Uuid, &ParseOptions::default()).is_err());
+
+    assert_eq!(
+        Value::Json(serde_json::json!({"a": [1, null]})),
+        parse(r#"{"a": [1, null]}"#, ValueType::Json)
+    );
+    assert_eq!(
+        Value::Json(serde_json::json!(1.5)),
+        parse("1.5", ValueType::Json)
+    );
+}
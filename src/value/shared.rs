@@ -0,0 +1,294 @@
+//! Shared storage for the payload of [Value::String](super::Value::String)
+//! and [Value::Blob](super::Value::Blob)
+//!
+//! Strings and blobs can be large (documents, images) and a record is cloned
+//! at several stages of a pipeline. [SharedString] and [SharedBytes] are
+//! reference counted, so cloning a [Value] only increments a counter. The
+//! data is copied only, when a shared value is changed with `make_mut`
+//! (copy-on-write) or taken out with `into_string`/`into_vec`.
+//!
+//! Both dereference to the borrowed data (`&str` and `&[u8]`), so most read
+//! access works as before.
+//!
+//! # Example
+//! ```
+//! use model::value::{Value, shared::SharedString};
+//!
+//! let text = SharedString::from("a large document");
+//! let value = Value::String(text.clone());
+//! let copy = value.clone();
+//! assert!(SharedString::ptr_eq(&text, copy.as_shared_str().unwrap()));
+//!
+//! let mut changed = text.clone();
+//! changed.make_mut().push('!');
+//! assert_eq!("a large document!", changed);
+//! assert_eq!("a large document", text);
+//! ```
+use std::{
+    borrow::Borrow,
+    fmt::{Debug, Display},
+    ops::Deref,
+    sync::Arc,
+};
+
+/// A reference counted, copy-on-write [String]
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SharedString(Arc<String>);
+
+impl SharedString {
+    /// Creates a [SharedString] without copying `value`
+    pub fn new(value: String) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns the string slice
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the string. The string is copied
+    /// before, if it is shared with other values
+    pub fn make_mut(&mut self) -> &mut String {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns the [String]. It is only copied, if it is shared with other
+    /// values
+    pub fn into_string(self) -> String {
+        Arc::unwrap_or_clone(self.0)
+    }
+
+    /// Returns true, if both share the same storage
+    pub fn ptr_eq(a: &SharedString, b: &SharedString) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl Deref for SharedString {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for SharedString {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for SharedString {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SharedString {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&str> for SharedString {
+    fn from(value: &str) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+impl From<&String> for SharedString {
+    fn from(value: &String) -> Self {
+        Self::new(value.clone())
+    }
+}
+
+impl From<SharedString> for String {
+    fn from(value: SharedString) -> Self {
+        value.into_string()
+    }
+}
+
+impl PartialEq<str> for SharedString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for SharedString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for SharedString {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<SharedString> for &str {
+    fn eq(&self, other: &SharedString) -> bool {
+        *self == other.as_str()
+    }
+}
+
+impl PartialEq<SharedString> for String {
+    fn eq(&self, other: &SharedString) -> bool {
+        self == other.as_str()
+    }
+}
+
+impl Display for SharedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+/// Writes the string like [String] does
+impl Debug for SharedString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Reference counted, copy-on-write bytes
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SharedBytes(Arc<Vec<u8>>);
+
+impl SharedBytes {
+    /// Creates [SharedBytes] without copying `value`
+    pub fn new(value: Vec<u8>) -> Self {
+        Self(Arc::new(value))
+    }
+
+    /// Returns the byte slice
+    pub fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns a mutable reference to the bytes. They are copied before, if
+    /// they are shared with other values
+    pub fn make_mut(&mut self) -> &mut Vec<u8> {
+        Arc::make_mut(&mut self.0)
+    }
+
+    /// Returns the [Vec] of bytes. It is only copied, if it is shared with
+    /// other values
+    pub fn into_vec(self) -> Vec<u8> {
+        Arc::unwrap_or_clone(self.0)
+    }
+
+    /// Returns true, if both share the same storage
+    pub fn ptr_eq(a: &SharedBytes, b: &SharedBytes) -> bool {
+        Arc::ptr_eq(&a.0, &b.0)
+    }
+}
+
+impl Deref for SharedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Borrow<[u8]> for SharedBytes {
+    fn borrow(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for SharedBytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&[u8]> for SharedBytes {
+    fn from(value: &[u8]) -> Self {
+        Self::new(value.to_vec())
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for SharedBytes {
+    fn from(value: [u8; N]) -> Self {
+        Self::new(value.to_vec())
+    }
+}
+
+impl From<SharedBytes> for Vec<u8> {
+    fn from(value: SharedBytes) -> Self {
+        value.into_vec()
+    }
+}
+
+impl PartialEq<[u8]> for SharedBytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<Vec<u8>> for SharedBytes {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+/// Writes the bytes like a [Vec] does
+impl Debug for SharedBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SharedBytes, SharedString};
+
+    #[test]
+    fn test_shared_string() {
+        let text = SharedString::from("abc");
+        let mut copy = text.clone();
+        assert!(SharedString::ptr_eq(&text, &copy));
+        assert_eq!(text, copy);
+        assert_eq!("abc", text);
+        assert_eq!(text, "abc".to_string());
+        assert_eq!(3, text.len());
+        assert_eq!("\"abc\"", format!("{:?}", text));
+
+        // Copy on write
+        copy.make_mut().push('d');
+        assert!(!SharedString::ptr_eq(&text, &copy));
+        assert_eq!("abc", text);
+        assert_eq!("abcd", copy.as_str());
+
+        // Not shared, so no copy is made
+        let mut single = SharedString::new("x".to_string());
+        let before = single.as_ptr();
+        single.make_mut().push('y');
+        assert_eq!(before, single.as_ptr());
+        assert_eq!("xy".to_string(), single.into_string());
+    }
+
+    #[test]
+    fn test_shared_bytes() {
+        let bytes = SharedBytes::from(vec![1, 2, 3]);
+        let mut copy = bytes.clone();
+        assert!(SharedBytes::ptr_eq(&bytes, &copy));
+        assert_eq!(bytes, vec![1, 2, 3]);
+        assert_eq!(&[1, 2], &bytes[..2]);
+        assert_eq!("[1, 2, 3]", format!("{:?}", bytes));
+
+        copy.make_mut().push(4);
+        assert_eq!(bytes, vec![1, 2, 3]);
+        assert_eq!(copy, vec![1, 2, 3, 4]);
+        assert_eq!(vec![1, 2, 3], Vec::from(bytes));
+        assert_eq!(SharedBytes::from([1, 2, 3, 4]), copy);
+    }
+}
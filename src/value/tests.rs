@@ -1,4 +1,4 @@
-use chrono::{Local, NaiveDate, Utc};
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, Utc};
 use rust_decimal::Decimal;
 
 use crate::{field::add_field, record::Record};
@@ -157,7 +157,7 @@ fn test_print_decimal() {
 #[test]
 fn test_print_string() {
     let exp_str = "This are not the droids you're looking for";
-    let v = Value::String(exp_str.to_string());
+    let v = Value::String(exp_str.into());
     let s = format!("{v}");
 
     assert_eq!(exp_str, s);
@@ -166,7 +166,7 @@ fn test_print_string() {
 #[test]
 fn test_print_blob() {
     let exp_blob: &[u8] = b"This are not the droids you're looking for";
-    let v = Value::Blob(exp_blob.to_vec());
+    let v = Value::Blob(exp_blob.into());
     let s = format!("{v}");
 
     assert_eq!(
@@ -206,7 +206,7 @@ fn test_print_time() {
 fn test_print_collection() {
     let exp: NaiveDate = Local::now().date_naive();
     let v1 = Value::Date(exp);
-    let v2 = Value::String("This is a string!".to_string());
+    let v2 = Value::String("This is a string!".into());
     let v3 = Value::I32(42);
 
     let v = Value::Collection(vec![v1, v2, v3]);
@@ -220,7 +220,7 @@ fn test_print_record() {
     let mut record = Record::new();
     let date: NaiveDate = Local::now().date_naive();
     let v1 = Value::Date(date);
-    let v2 = Value::String("This is a string!".to_string());
+    let v2 = Value::String("This is a string!".into());
     let v3 = Value::I32(42);
 
     add_field(record.fields_as_mut(), "date", v1);
@@ -246,3 +246,21 @@ fn test_print_none() {
 
     assert_eq!("<None>", s);
 }
+
+#[test]
+fn test_print_new_variants() {
+    let date_time = DateTime::parse_from_rfc3339("2024-05-01T10:00:00.5+02:00").unwrap();
+    assert_eq!(
+        "2024-05-01T10:00:00.500+02:00",
+        Value::DateTimeTz(date_time).to_string()
+    );
+    assert_eq!("PT90S", Value::Duration(TimeDelta::seconds(90)).to_string());
+    assert_eq!(
+        "00000000-0000-0000-0000-000000000000",
+        Value::Uuid(uuid::Uuid::nil()).to_string()
+    );
+    assert_eq!(
+        r#"{"a":[1,"b"]}"#,
+        Value::Json(serde_json::json!({"a": [1, "b"]})).to_string()
+    );
+}
@@ -0,0 +1,478 @@
+//! Conversions out of a [Value]
+//!
+//! Every conversion is lossless: integer variants convert into every integer
+//! type that can hold the value (e.g. [Value::U16] into [i64]), floats accept
+//! only the integer variants they can represent exactly and [Value::F32] widens
+//! into [f64]. All other conversions require the matching variant.
+//!
+//! # Example
+//! ```
+//! use model::value::Value;
+//!
+//! let value = Value::U16(4711);
+//! let number: i64 = i64::try_from(&value).unwrap();
+//! assert_eq!(4711, number);
+//! assert_eq!(4711, value.as_i64().unwrap());
+//!
+//! let error = u8::try_from(&value).unwrap_err();
+//! assert_eq!("Cannot convert U16 to u8: value out of range", error.to_string());
+//! ```
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::{Decimal, prelude::FromPrimitive};
+use std::fmt::Display;
+use uuid::Uuid;
+
+use super::{
+    Value,
+    shared::{SharedBytes, SharedString},
+};
+use crate::record::Record;
+
+/// The error returned, when a [Value] cannot be converted into a Rust type
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    from: &'static str,
+    to: &'static str,
+    out_of_range: bool,
+}
+
+impl ConversionError {
+    /// Creates an error for a `value` with a variant, that cannot be converted
+    /// into the type `to`
+    fn new(value: &Value, to: &'static str) -> Self {
+        Self {
+            from: value.type_name(),
+            to,
+            out_of_range: false,
+        }
+    }
+
+    /// Creates an error for a `value` with a matching variant, whose value
+    /// does not fit into the type `to`
+    fn out_of_range(value: &Value, to: &'static str) -> Self {
+        Self {
+            from: value.type_name(),
+            to,
+            out_of_range: true,
+        }
+    }
+
+    /// Returns the name of the [Value] variant that could not be converted
+    pub fn from(&self) -> &'static str {
+        self.from
+    }
+
+    /// Returns the name of the target type
+    pub fn to(&self) -> &'static str {
+        self.to
+    }
+
+    /// Returns true, if the variant was convertible, but the value did not fit
+    /// into the target type
+    pub fn is_out_of_range(&self) -> bool {
+        self.out_of_range
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Cannot convert {} to {}", self.from, self.to)?;
+        if self.out_of_range {
+            write!(f, ": value out of range")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+/// Implements [TryFrom] for [Value] and &[Value] for integer types. The
+/// conversion succeeds for every integer variant, whose value fits into the
+/// target type
+macro_rules! try_from_integer {
+    ($($target:ty),*) => {$(
+        impl TryFrom<&Value> for $target {
+            type Error = ConversionError;
+
+            #[allow(clippy::useless_conversion)]
+            fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                let result = match value {
+                    Value::I8(v) => <$target>::try_from(*v).ok(),
+                    Value::I16(v) => <$target>::try_from(*v).ok(),
+                    Value::I32(v) => <$target>::try_from(*v).ok(),
+                    Value::I64(v) => <$target>::try_from(*v).ok(),
+                    Value::I128(v) => <$target>::try_from(*v).ok(),
+                    Value::ISize(v) => <$target>::try_from(*v).ok(),
+                    Value::U8(v) => <$target>::try_from(*v).ok(),
+                    Value::U16(v) => <$target>::try_from(*v).ok(),
+                    Value::U32(v) => <$target>::try_from(*v).ok(),
+                    Value::U64(v) => <$target>::try_from(*v).ok(),
+                    Value::U128(v) => <$target>::try_from(*v).ok(),
+                    Value::USize(v) => <$target>::try_from(*v).ok(),
+                    _ => return Err(ConversionError::new(value, stringify!($target))),
+                };
+                result.ok_or_else(|| ConversionError::out_of_range(value, stringify!($target)))
+            }
+        }
+
+        impl TryFrom<Value> for $target {
+            type Error = ConversionError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                <$target>::try_from(&value)
+            }
+        }
+    )*};
+}
+
+try_from_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// Implements [TryFrom] for [Value] and &[Value] for types, that need a
+/// specific variant. `$variant` is copied out of the value
+macro_rules! try_from_variant {
+    ($($target:ty => $variant:ident),*) => {$(
+        impl TryFrom<&Value> for $target {
+            type Error = ConversionError;
+
+            fn try_from(value: &Value) -> Result<Self, Self::Error> {
+                match value {
+                    Value::$variant(v) => Ok(*v),
+                    _ => Err(ConversionError::new(value, stringify!($target))),
+                }
+            }
+        }
+
+        impl TryFrom<Value> for $target {
+            type Error = ConversionError;
+
+            fn try_from(value: Value) -> Result<Self, Self::Error> {
+                <$target>::try_from(&value)
+            }
+        }
+    )*};
+}
+
+try_from_variant!(
+    bool => Bool,
+    char => Char,
+    NaiveDate => Date,
+    NaiveDateTime => DateTime,
+    NaiveTime => Time,
+    DateTime<FixedOffset> => DateTimeTz,
+    TimeDelta => Duration,
+    Uuid => Uuid
+);
+
+impl TryFrom<&Value> for f32 {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::F32(v) => Ok(*v),
+            Value::I8(v) => Ok(f32::from(*v)),
+            Value::I16(v) => Ok(f32::from(*v)),
+            Value::U8(v) => Ok(f32::from(*v)),
+            Value::U16(v) => Ok(f32::from(*v)),
+            _ => Err(ConversionError::new(value, "f32")),
+        }
+    }
+}
+
+impl TryFrom<Value> for f32 {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        f32::try_from(&value)
+    }
+}
+
+impl TryFrom<&Value> for f64 {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::F64(v) => Ok(*v),
+            Value::F32(v) => Ok(f64::from(*v)),
+            Value::I8(v) => Ok(f64::from(*v)),
+            Value::I16(v) => Ok(f64::from(*v)),
+            Value::I32(v) => Ok(f64::from(*v)),
+            Value::U8(v) => Ok(f64::from(*v)),
+            Value::U16(v) => Ok(f64::from(*v)),
+            Value::U32(v) => Ok(f64::from(*v)),
+            _ => Err(ConversionError::new(value, "f64")),
+        }
+    }
+}
+
+impl TryFrom<Value> for f64 {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        f64::try_from(&value)
+    }
+}
+
+impl TryFrom<&Value> for Decimal {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Decimal(v) => Ok(*v),
+            Value::I8(v) => Ok(Decimal::from(*v)),
+            Value::I16(v) => Ok(Decimal::from(*v)),
+            Value::I32(v) => Ok(Decimal::from(*v)),
+            Value::I64(v) => Ok(Decimal::from(*v)),
+            Value::ISize(v) => Ok(Decimal::from(*v)),
+            Value::U8(v) => Ok(Decimal::from(*v)),
+            Value::U16(v) => Ok(Decimal::from(*v)),
+            Value::U32(v) => Ok(Decimal::from(*v)),
+            Value::U64(v) => Ok(Decimal::from(*v)),
+            Value::USize(v) => Ok(Decimal::from(*v)),
+            Value::I128(v) => Decimal::from_i128(*v)
+                .ok_or_else(|| ConversionError::out_of_range(value, "Decimal")),
+            Value::U128(v) => Decimal::from_u128(*v)
+                .ok_or_else(|| ConversionError::out_of_range(value, "Decimal")),
+            _ => Err(ConversionError::new(value, "Decimal")),
+        }
+    }
+}
+
+impl TryFrom<Value> for Decimal {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        Decimal::try_from(&value)
+    }
+}
+
+impl TryFrom<&Value> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(v) => Ok(v.to_string()),
+            Value::Char(v) => Ok(v.to_string()),
+            _ => Err(ConversionError::new(value, "String")),
+        }
+    }
+}
+
+impl TryFrom<Value> for String {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(v) => Ok(v.into_string()),
+            _ => String::try_from(&value),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Vec<u8> {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Blob(v) => Ok(v.to_vec()),
+            _ => Err(ConversionError::new(value, "Vec<u8>")),
+        }
+    }
+}
+
+impl TryFrom<Value> for Vec<u8> {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Blob(v) => Ok(v.into_vec()),
+            _ => Err(ConversionError::new(&value, "Vec<u8>")),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Vec<Value> {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Collection(v) => Ok(v.clone()),
+            _ => Err(ConversionError::new(value, "Vec<Value>")),
+        }
+    }
+}
+
+impl TryFrom<Value> for Vec<Value> {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Collection(v) => Ok(v),
+            _ => Err(ConversionError::new(&value, "Vec<Value>")),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Record {
+    type Error = ConversionError;
+
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Record(v) => Ok(v.clone()),
+            _ => Err(ConversionError::new(value, "Record")),
+        }
+    }
+}
+
+impl TryFrom<Value> for Record {
+    type Error = ConversionError;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Record(v) => Ok(v),
+            _ => Err(ConversionError::new(&value, "Record")),
+        }
+    }
+}
+
+/// Accessors, that follow the conversion rules of the [TryFrom]
+/// implementations
+impl Value {
+    /// Returns the value as [bool]
+    pub fn as_bool(&self) -> Result<bool, ConversionError> {
+        bool::try_from(self)
+    }
+
+    /// Returns the value as [char]
+    pub fn as_char(&self) -> Result<char, ConversionError> {
+        char::try_from(self)
+    }
+
+    /// Returns the value as [i64], if it is an integer that fits
+    pub fn as_i64(&self) -> Result<i64, ConversionError> {
+        i64::try_from(self)
+    }
+
+    /// Returns the value as [i128], if it is an integer that fits
+    pub fn as_i128(&self) -> Result<i128, ConversionError> {
+        i128::try_from(self)
+    }
+
+    /// Returns the value as [u64], if it is an integer that fits
+    pub fn as_u64(&self) -> Result<u64, ConversionError> {
+        u64::try_from(self)
+    }
+
+    /// Returns the value as [u128], if it is an integer that fits
+    pub fn as_u128(&self) -> Result<u128, ConversionError> {
+        u128::try_from(self)
+    }
+
+    /// Returns the value as [f64], if it can be represented exactly
+    pub fn as_f64(&self) -> Result<f64, ConversionError> {
+        f64::try_from(self)
+    }
+
+    /// Returns the value as [Decimal], if it is a decimal or an integer that
+    /// fits
+    pub fn as_decimal(&self) -> Result<Decimal, ConversionError> {
+        Decimal::try_from(self)
+    }
+
+    /// Returns a reference to the string of a [Value::String]
+    pub fn as_str(&self) -> Result<&str, ConversionError> {
+        match self {
+            Value::String(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "&str")),
+        }
+    }
+
+    /// Returns a reference to the bytes of a [Value::Blob]
+    pub fn as_bytes(&self) -> Result<&[u8], ConversionError> {
+        match self {
+            Value::Blob(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "&[u8]")),
+        }
+    }
+
+    /// Returns the shared string of a [Value::String]. Cloning it does not
+    /// copy the string
+    pub fn as_shared_str(&self) -> Result<&SharedString, ConversionError> {
+        match self {
+            Value::String(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "SharedString")),
+        }
+    }
+
+    /// Returns the shared bytes of a [Value::Blob]. Cloning them does not
+    /// copy the bytes
+    pub fn as_shared_bytes(&self) -> Result<&SharedBytes, ConversionError> {
+        match self {
+            Value::Blob(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "SharedBytes")),
+        }
+    }
+
+    /// Returns the value as [NaiveDate]
+    pub fn as_date(&self) -> Result<NaiveDate, ConversionError> {
+        NaiveDate::try_from(self)
+    }
+
+    /// Returns the value as [NaiveDateTime]
+    pub fn as_date_time(&self) -> Result<NaiveDateTime, ConversionError> {
+        NaiveDateTime::try_from(self)
+    }
+
+    /// Returns the value as [NaiveTime]
+    pub fn as_time(&self) -> Result<NaiveTime, ConversionError> {
+        NaiveTime::try_from(self)
+    }
+
+    /// Returns the value as [DateTime] with its offset
+    pub fn as_date_time_tz(&self) -> Result<DateTime<FixedOffset>, ConversionError> {
+        DateTime::<FixedOffset>::try_from(self)
+    }
+
+    /// Returns the value as [TimeDelta]
+    pub fn as_duration(&self) -> Result<TimeDelta, ConversionError> {
+        TimeDelta::try_from(self)
+    }
+
+    /// Returns the value as [Uuid]
+    pub fn as_uuid(&self) -> Result<Uuid, ConversionError> {
+        Uuid::try_from(self)
+    }
+
+    /// Returns a reference to the document of a [Value::Json]
+    pub fn as_json(&self) -> Result<&serde_json::Value, ConversionError> {
+        match self {
+            Value::Json(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "&serde_json::Value")),
+        }
+    }
+
+    /// Returns a reference to the values of a [Value::Collection]
+    pub fn as_collection(&self) -> Result<&[Value], ConversionError> {
+        match self {
+            Value::Collection(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "&[Value]")),
+        }
+    }
+
+    /// Returns a reference to the record of a [Value::Record]
+    pub fn as_record(&self) -> Result<&Record, ConversionError> {
+        match self {
+            Value::Record(v) => Ok(v),
+            _ => Err(ConversionError::new(self, "&Record")),
+        }
+    }
+
+    /// Returns true, if the value is [Value::None]
+    pub fn is_none(&self) -> bool {
+        matches!(self, Value::None)
+    }
+}
+
+#[cfg(test)]
+mod tests;
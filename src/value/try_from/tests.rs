@@ -0,0 +1,221 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+use rust_decimal::Decimal;
+
+use crate::{field::add_field, record::Record, value::Value};
+
+use super::ConversionError;
+
+#[test]
+fn test_integer_widening() {
+    assert_eq!(Ok(42i64), i64::try_from(&Value::U16(42)));
+    assert_eq!(Ok(-42i64), i64::try_from(&Value::I8(-42)));
+    assert_eq!(Ok(42i128), i128::try_from(Value::U64(42)));
+    assert_eq!(Ok(42u64), u64::try_from(Value::USize(42)));
+    assert_eq!(Ok(42i16), i16::try_from(&Value::I16(42)));
+}
+
+#[test]
+fn test_integer_narrowing() {
+    assert_eq!(Ok(200u8), u8::try_from(&Value::I64(200)));
+    assert_eq!(Ok(7i8), i8::try_from(&Value::U128(7)));
+
+    let error = u8::try_from(&Value::I64(-1)).unwrap_err();
+    assert_eq!("I64", error.from());
+    assert_eq!("u8", error.to());
+    assert!(error.is_out_of_range());
+    assert_eq!(
+        "Cannot convert I64 to u8: value out of range",
+        error.to_string()
+    );
+
+    let error = i64::try_from(&Value::U128(u128::MAX)).unwrap_err();
+    assert!(error.is_out_of_range());
+}
+
+#[test]
+fn test_integer_wrong_variant() {
+    let error = i32::try_from(&Value::String("42".into())).unwrap_err();
+    assert_eq!("String", error.from());
+    assert_eq!("i32", error.to());
+    assert!(!error.is_out_of_range());
+    assert_eq!("Cannot convert String to i32", error.to_string());
+
+    assert!(i32::try_from(&Value::F64(42.0)).is_err());
+    assert!(u64::try_from(Value::None).is_err());
+}
+
+#[test]
+fn test_floats() {
+    assert_eq!(Ok(1.5f64), f64::try_from(&Value::F32(1.5)));
+    assert_eq!(Ok(73.0f64), f64::try_from(&Value::U32(73)));
+    assert_eq!(Ok(-73.0f64), f64::try_from(Value::I32(-73)));
+    assert_eq!(Ok(2.25f32), f32::try_from(&Value::F32(2.25)));
+    assert_eq!(Ok(7.0f32), f32::try_from(Value::I16(7)));
+
+    // Not exactly representable
+    assert!(f64::try_from(&Value::I64(1)).is_err());
+    assert!(f32::try_from(&Value::I32(1)).is_err());
+    assert!(f32::try_from(&Value::F64(1.0)).is_err());
+}
+
+#[test]
+fn test_decimal() {
+    let decimal = Decimal::new(12345, 2);
+    assert_eq!(Ok(decimal), Decimal::try_from(&Value::Decimal(decimal)));
+    assert_eq!(Ok(Decimal::from(42)), Decimal::try_from(Value::U8(42)));
+    assert_eq!(Ok(Decimal::from(-42)), Decimal::try_from(&Value::I128(-42)));
+    assert_eq!(Ok(Decimal::from(42)), Decimal::try_from(&Value::U128(42)));
+
+    let error = Decimal::try_from(&Value::I128(i128::MAX)).unwrap_err();
+    assert!(error.is_out_of_range());
+    let error = Decimal::try_from(&Value::U128(u128::MAX)).unwrap_err();
+    assert!(error.is_out_of_range());
+    assert!(Decimal::try_from(&Value::F64(1.0)).is_err());
+}
+
+#[test]
+fn test_variants() {
+    assert_eq!(Ok(true), bool::try_from(&Value::Bool(true)));
+    assert_eq!(Ok('x'), char::try_from(Value::Char('x')));
+    assert!(bool::try_from(&Value::U8(1)).is_err());
+
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 0).unwrap();
+    let date_time = NaiveDateTime::new(date, time);
+    assert_eq!(Ok(date), NaiveDate::try_from(&Value::Date(date)));
+    assert_eq!(Ok(time), NaiveTime::try_from(Value::Time(time)));
+    assert_eq!(
+        Ok(date_time),
+        NaiveDateTime::try_from(&Value::DateTime(date_time))
+    );
+    assert_eq!(
+        Err(ConversionError::new(&Value::Date(date), "NaiveDateTime")),
+        NaiveDateTime::try_from(&Value::Date(date))
+    );
+}
+
+#[test]
+fn test_owned_types() {
+    assert_eq!(Ok("abc".to_string()), String::try_from(Value::from("abc")));
+    assert_eq!(Ok("abc".to_string()), String::try_from(&Value::from("abc")));
+    assert_eq!(Ok("x".to_string()), String::try_from(Value::Char('x')));
+    assert!(String::try_from(Value::I32(1)).is_err());
+
+    assert_eq!(
+        Ok(vec![1u8, 2]),
+        Vec::<u8>::try_from(Value::Blob(vec![1, 2].into()))
+    );
+    assert_eq!(
+        Ok(vec![1u8, 2]),
+        Vec::<u8>::try_from(&Value::Blob(vec![1, 2].into()))
+    );
+    assert!(Vec::<u8>::try_from(Value::None).is_err());
+    assert!(Vec::<u8>::try_from(&Value::None).is_err());
+
+    let collection = vec![Value::I32(1), Value::None];
+    assert_eq!(
+        Ok(collection.clone()),
+        Vec::<Value>::try_from(Value::Collection(collection.clone()))
+    );
+    assert_eq!(
+        Ok(collection.clone()),
+        Vec::<Value>::try_from(&Value::Collection(collection))
+    );
+    assert!(Vec::<Value>::try_from(Value::None).is_err());
+    assert!(Vec::<Value>::try_from(&Value::None).is_err());
+
+    let mut record = Record::new();
+    add_field(record.fields_as_mut(), "name", "value".into());
+    assert_eq!(
+        Ok(record.clone()),
+        Record::try_from(&Value::Record(record.clone()))
+    );
+    assert_eq!(Ok(record.clone()), Record::try_from(Value::Record(record)));
+    assert!(Record::try_from(Value::None).is_err());
+    assert!(Record::try_from(&Value::None).is_err());
+}
+
+#[test]
+fn test_accessors() {
+    assert_eq!(Ok(true), Value::Bool(true).as_bool());
+    assert_eq!(Ok('c'), Value::Char('c').as_char());
+    assert_eq!(Ok(65535), Value::U16(u16::MAX).as_i64());
+    assert_eq!(Ok(-1), Value::ISize(-1).as_i128());
+    assert_eq!(Ok(1), Value::I8(1).as_u64());
+    assert_eq!(Ok(u128::MAX), Value::U128(u128::MAX).as_u128());
+    assert_eq!(Ok(0.5), Value::F32(0.5).as_f64());
+    assert_eq!(Ok(Decimal::from(5)), Value::U8(5).as_decimal());
+    assert_eq!(Ok("text"), Value::from("text").as_str());
+    assert_eq!(Ok(&[1u8, 2][..]), Value::Blob(vec![1, 2].into()).as_bytes());
+    assert_eq!(
+        Ok(&[Value::None][..]),
+        Value::Collection(vec![Value::None]).as_collection()
+    );
+    assert_eq!(Ok(&Record::new()), Value::Record(Record::new()).as_record());
+
+    let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let time = NaiveTime::from_hms_opt(10, 42, 0).unwrap();
+    assert_eq!(Ok(date), Value::Date(date).as_date());
+    assert_eq!(Ok(time), Value::Time(time).as_time());
+    assert_eq!(
+        Ok(date.and_time(time)),
+        Value::DateTime(date.and_time(time)).as_date_time()
+    );
+
+    let error = Value::Char('c').as_str().unwrap_err();
+    assert_eq!("Cannot convert Char to &str", error.to_string());
+    assert!(Value::None.as_bytes().is_err());
+    assert!(Value::None.as_collection().is_err());
+    assert!(Value::None.as_record().is_err());
+    assert!(Value::I64(-1).as_u64().is_err());
+
+    assert!(Value::None.is_none());
+    assert!(!Value::I32(0).is_none());
+}
+
+#[test]
+fn test_error_in_boxed_error() {
+    fn total(value: &Value) -> Result<i64, crate::BoxedError> {
+        Ok(value.as_i64()? + 1)
+    }
+
+    assert_eq!(43, total(&Value::U8(42)).unwrap());
+    assert_eq!(
+        "Cannot convert Bool to i64",
+        total(&Value::Bool(false)).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_type_name() {
+    assert_eq!("Bool", Value::Bool(true).type_name());
+    assert_eq!("Collection", Value::Collection(vec![]).type_name());
+    assert_eq!("Record", Value::Record(Record::new()).type_name());
+    assert_eq!("None", Value::None.type_name());
+}
+
+#[test]
+fn test_new_variants() {
+    let date_time = DateTime::parse_from_rfc3339("2024-05-01T10:00:00+02:00").unwrap();
+    let value = Value::DateTimeTz(date_time);
+    assert_eq!(Ok(date_time), DateTime::<FixedOffset>::try_from(&value));
+    assert_eq!(Ok(date_time), value.as_date_time_tz());
+    assert!(value.as_date_time().is_err());
+
+    assert_eq!(
+        Ok(TimeDelta::seconds(2)),
+        Value::Duration(TimeDelta::seconds(2)).as_duration()
+    );
+    assert_eq!(
+        Ok(uuid::Uuid::nil()),
+        Value::Uuid(uuid::Uuid::nil()).as_uuid()
+    );
+    assert_eq!(
+        "Cannot convert String to Uuid",
+        Value::from("x").as_uuid().unwrap_err().to_string()
+    );
+
+    let json = serde_json::json!([1]);
+    assert_eq!(Ok(&json), Value::Json(json.clone()).as_json());
+    assert!(Value::Collection(vec![]).as_json().is_err());
+}
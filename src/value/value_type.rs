@@ -0,0 +1,343 @@
+//! A type descriptor for the variants of a [Value]
+//!
+use std::{fmt::Display, str::FromStr};
+
+use super::Value;
+
+/// The type of a [Value]. There is one type for every variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    Bool,
+    Char,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    ISize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    USize,
+    F32,
+    F64,
+    Decimal,
+    String,
+    Blob,
+    Date,
+    DateTime,
+    Time,
+    DateTimeTz,
+    Duration,
+    Uuid,
+    Json,
+    Collection,
+    Record,
+    None,
+}
+
+impl ValueType {
+    /// All value types in the order of the [Value] variants
+    pub const ALL: [ValueType; 29] = [
+        ValueType::Bool,
+        ValueType::Char,
+        ValueType::I8,
+        ValueType::I16,
+        ValueType::I32,
+        ValueType::I64,
+        ValueType::I128,
+        ValueType::ISize,
+        ValueType::U8,
+        ValueType::U16,
+        ValueType::U32,
+        ValueType::U64,
+        ValueType::U128,
+        ValueType::USize,
+        ValueType::F32,
+        ValueType::F64,
+        ValueType::Decimal,
+        ValueType::String,
+        ValueType::Blob,
+        ValueType::Date,
+        ValueType::DateTime,
+        ValueType::Time,
+        ValueType::DateTimeTz,
+        ValueType::Duration,
+        ValueType::Uuid,
+        ValueType::Json,
+        ValueType::Collection,
+        ValueType::Record,
+        ValueType::None,
+    ];
+
+    /// Returns the name of the [Value] variant
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueType::Bool => "Bool",
+            ValueType::Char => "Char",
+            ValueType::I8 => "I8",
+            ValueType::I16 => "I16",
+            ValueType::I32 => "I32",
+            ValueType::I64 => "I64",
+            ValueType::I128 => "I128",
+            ValueType::ISize => "ISize",
+            ValueType::U8 => "U8",
+            ValueType::U16 => "U16",
+            ValueType::U32 => "U32",
+            ValueType::U64 => "U64",
+            ValueType::U128 => "U128",
+            ValueType::USize => "USize",
+            ValueType::F32 => "F32",
+            ValueType::F64 => "F64",
+            ValueType::Decimal => "Decimal",
+            ValueType::String => "String",
+            ValueType::Blob => "Blob",
+            ValueType::Date => "Date",
+            ValueType::DateTime => "DateTime",
+            ValueType::Time => "Time",
+            ValueType::DateTimeTz => "DateTimeTz",
+            ValueType::Duration => "Duration",
+            ValueType::Uuid => "Uuid",
+            ValueType::Json => "Json",
+            ValueType::Collection => "Collection",
+            ValueType::Record => "Record",
+            ValueType::None => "None",
+        }
+    }
+
+    /// Returns true for all integer types
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            ValueType::I8
+                | ValueType::I16
+                | ValueType::I32
+                | ValueType::I64
+                | ValueType::I128
+                | ValueType::ISize
+                | ValueType::U8
+                | ValueType::U16
+                | ValueType::U32
+                | ValueType::U64
+                | ValueType::U128
+                | ValueType::USize
+        )
+    }
+
+    /// Returns true for the signed integer types
+    pub fn is_signed_integer(&self) -> bool {
+        matches!(
+            self,
+            ValueType::I8
+                | ValueType::I16
+                | ValueType::I32
+                | ValueType::I64
+                | ValueType::I128
+                | ValueType::ISize
+        )
+    }
+
+    /// Returns the integer type, that can hold all values of both integer
+    /// types, or [None] if one of them is not an integer type
+    ///
+    /// Two signed or two unsigned types give the wider type. A signed and an
+    /// unsigned type give a signed type, that can hold both, e.g. `I8` and
+    /// `U8` give `I16`, `I64` and `U64` give `I128`. `ISize` and `USize` are
+    /// kept only, if both types are the same
+    ///
+    /// # Example
+    /// ```
+    /// use model::value::value_type::ValueType;
+    /// assert_eq!(Some(ValueType::I32), ValueType::U16.common_integer(ValueType::I8));
+    /// assert_eq!(None, ValueType::U16.common_integer(ValueType::F32));
+    /// ```
+    pub fn common_integer(self, other: ValueType) -> Option<ValueType> {
+        if !self.is_integer() || !other.is_integer() {
+            return None;
+        }
+        if self == other {
+            return Some(self);
+        }
+        let (a, b) = (self.without_size(), other.without_size());
+        Some(match (a.is_signed_integer(), b.is_signed_integer()) {
+            (true, true) | (false, false) => {
+                if a.integer_bits() >= b.integer_bits() {
+                    a
+                } else {
+                    b
+                }
+            }
+            (true, false) if a.integer_bits() > b.integer_bits() => a,
+            (true, false) => ValueType::signed_with(b.integer_bits() + 1),
+            (false, true) if b.integer_bits() > a.integer_bits() => b,
+            (false, true) => ValueType::signed_with(a.integer_bits() + 1),
+        })
+    }
+
+    /// Returns true, if every value of this type can be represented exactly
+    /// by the type `target`
+    ///
+    /// Integers widen to wider integers, see [ValueType::common_integer],
+    /// integers up to 64 bits to [ValueType::Decimal], up to 32 bits to
+    /// [ValueType::F64] and up to 16 bits to [ValueType::F32].
+    /// [ValueType::F32] widens to [ValueType::F64] and [ValueType::Char] to
+    /// [ValueType::String]
+    ///
+    /// # Example
+    /// ```
+    /// use model::value::value_type::ValueType;
+    /// assert!(ValueType::U8.widens_to(ValueType::I32));
+    /// assert!(ValueType::I32.widens_to(ValueType::F64));
+    /// assert!(!ValueType::I64.widens_to(ValueType::F64));
+    /// assert!(!ValueType::I32.widens_to(ValueType::U64));
+    /// ```
+    pub fn widens_to(self, target: ValueType) -> bool {
+        if self == target {
+            return true;
+        }
+        if self.is_integer() {
+            let bits = self.without_size().integer_bits();
+            return match target {
+                ValueType::Decimal => bits <= 64,
+                ValueType::F64 => bits <= 32,
+                ValueType::F32 => bits <= 16,
+                _ => self.common_integer(target) == Some(target),
+            };
+        }
+        matches!(
+            (self, target),
+            (ValueType::F32, ValueType::F64) | (ValueType::Char, ValueType::String)
+        )
+    }
+
+    /// Returns the number of bits of an integer type
+    fn integer_bits(self) -> u32 {
+        match self {
+            ValueType::I8 | ValueType::U8 => 8,
+            ValueType::I16 | ValueType::U16 => 16,
+            ValueType::I32 | ValueType::U32 => 32,
+            ValueType::I64 | ValueType::U64 => 64,
+            ValueType::ISize | ValueType::USize => usize::BITS,
+            _ => 128,
+        }
+    }
+
+    /// Returns the signed type with at least `bits` bits
+    fn signed_with(bits: u32) -> ValueType {
+        match bits {
+            0..=8 => ValueType::I8,
+            9..=16 => ValueType::I16,
+            17..=32 => ValueType::I32,
+            33..=64 => ValueType::I64,
+            _ => ValueType::I128,
+        }
+    }
+
+    /// Returns the fixed size type for `ISize` and `USize`
+    fn without_size(self) -> ValueType {
+        match (self, usize::BITS) {
+            (ValueType::ISize, 32) => ValueType::I32,
+            (ValueType::ISize, _) => ValueType::I64,
+            (ValueType::USize, 32) => ValueType::U32,
+            (ValueType::USize, _) => ValueType::U64,
+            _ => self,
+        }
+    }
+
+    /// Returns true for all integer, float and decimal types
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || matches!(self, ValueType::F32 | ValueType::F64 | ValueType::Decimal)
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Parses the name of a [ValueType] case-insensitively, e.g. `i32` or
+/// `DateTime`
+impl FromStr for ValueType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ValueType::ALL
+            .into_iter()
+            .find(|value_type| value_type.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Unknown value type '{}'", s))
+    }
+}
+
+impl Value {
+    /// Returns the [ValueType] of this value
+    ///
+    /// # Example
+    /// ```
+    /// use model::value::{Value, value_type::ValueType};
+    /// assert_eq!(ValueType::U16, Value::U16(42).value_type());
+    /// ```
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Value::Bool(_) => ValueType::Bool,
+            Value::Char(_) => ValueType::Char,
+            Value::I8(_) => ValueType::I8,
+            Value::I16(_) => ValueType::I16,
+            Value::I32(_) => ValueType::I32,
+            Value::I64(_) => ValueType::I64,
+            Value::I128(_) => ValueType::I128,
+            Value::ISize(_) => ValueType::ISize,
+            Value::U8(_) => ValueType::U8,
+            Value::U16(_) => ValueType::U16,
+            Value::U32(_) => ValueType::U32,
+            Value::U64(_) => ValueType::U64,
+            Value::U128(_) => ValueType::U128,
+            Value::USize(_) => ValueType::USize,
+            Value::F32(_) => ValueType::F32,
+            Value::F64(_) => ValueType::F64,
+            Value::Decimal(_) => ValueType::Decimal,
+            Value::String(_) => ValueType::String,
+            Value::Blob(_) => ValueType::Blob,
+            Value::Date(_) => ValueType::Date,
+            Value::DateTime(_) => ValueType::DateTime,
+            Value::Time(_) => ValueType::Time,
+            Value::DateTimeTz(_) => ValueType::DateTimeTz,
+            Value::Duration(_) => ValueType::Duration,
+            Value::Uuid(_) => ValueType::Uuid,
+            Value::Json(_) => ValueType::Json,
+            Value::Collection(_) => ValueType::Collection,
+            Value::Record(_) => ValueType::Record,
+            Value::None => ValueType::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::ValueType;
+    use crate::value::Value;
+
+    #[test]
+    fn test_name_round_trip() {
+        for value_type in ValueType::ALL {
+            assert_eq!(Ok(value_type), ValueType::from_str(value_type.name()));
+        }
+        assert_eq!(Ok(ValueType::DateTime), ValueType::from_str("datetime"));
+        assert_eq!(Ok(ValueType::I32), "i32".parse());
+        assert!(ValueType::from_str("int").is_err());
+    }
+
+    #[test]
+    fn test_value_type() {
+        assert_eq!(ValueType::None, Value::None.value_type());
+        assert_eq!(ValueType::Decimal, Value::Decimal(1.into()).value_type());
+        assert_eq!("Blob", Value::Blob(vec![].into()).value_type().to_string());
+        assert!(ValueType::USize.is_integer());
+        assert!(ValueType::F32.is_numeric());
+        assert!(!ValueType::Char.is_numeric());
+    }
+}
@@ -4,6 +4,8 @@ use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 
+use crate::{field::metadata::keys, record::Record};
+
 /// A structure to store TCP/IP connection information to a database
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename = "connection")]
@@ -43,10 +45,27 @@ impl Table {
         }
         HashSet::new()
     }
+
+    /// Sets the [keys::KEY] metadata of all fields of the `record`, that are
+    /// listed in the unique fields
+    pub fn mark_unique_fields(&self, record: &mut Record) {
+        let unique_fields = self.get_unique_fields_as_set();
+        for field in record.fields_as_mut() {
+            if unique_fields.contains(field.name()) {
+                field.metadata_mut().set(&keys::KEY, true);
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        field::{add_field, metadata::keys},
+        record::Record,
+        value::Value,
+    };
+
     use super::Table;
     #[test]
     fn test_unique_fields_ser() {
@@ -113,4 +132,21 @@ mod tests {
         assert_eq!(*v.get("c").unwrap(), "c");
     }
 
+    #[test]
+    fn test_mark_unique_fields() {
+        let xml = r#"<table name="Name" uniqueFields="a, c"/>"#;
+        let table: Table = serde_xml_rs::from_str(xml).unwrap();
+
+        let mut record = Record::new();
+        for name in ["a", "b", "c"] {
+            add_field(record.fields_as_mut(), name, Value::I32(1));
+        }
+        table.mark_unique_fields(&mut record);
+        let keys: Vec<Option<bool>> = record
+            .fields()
+            .iter()
+            .map(|field| field.metadata().get(&keys::KEY))
+            .collect();
+        assert_eq!(vec![Some(true), None, Some(true)], keys);
+    }
 }
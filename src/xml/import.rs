@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 /// * `name` - the (optional) name of the importer from the plugin 
 ///         (if there a more than one available)
 /// * `configuration` - a [Configuration] element for this importer
+/// * `incremental` - an optional [Incremental] element, if the importer
+///   should only read the records changed since the last run
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Importer {
     #[serde(rename = "@plugin")]
@@ -17,4 +19,33 @@ pub struct Importer {
     #[serde(rename = "@name")]
     pub name: Option<String>,
     pub configuration: Option<Configuration>,
+    pub incremental: Option<Incremental>,
+}
+
+/// An incremental import, e.g. `<incremental field="updated_at"/>`
+///
+/// The maximum value of `field` of a run is the high-water mark. The next run
+/// gets it in the configuration of the importer, see
+/// [crate::incremental].
+///
+/// # Members
+/// * `field` - the name of the field, that tracks the changes
+/// * `key` - the (optional) configuration key for the high-water mark.
+///   Defaults to [Incremental::DEFAULT_KEY]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Incremental {
+    #[serde(rename = "@field")]
+    pub field: String,
+    #[serde(rename = "@key")]
+    pub key: Option<String>,
+}
+
+impl Incremental {
+    /// The default configuration key for the high-water mark
+    pub const DEFAULT_KEY: &str = "high_water_mark";
+
+    /// Returns the configuration key for the high-water mark
+    pub fn key(&self) -> &str {
+        self.key.as_deref().unwrap_or(Self::DEFAULT_KEY)
+    }
 }
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::xml::file::create_rite;
+use crate::xml::{file::create_rite, import::Importer, process::Process};
 
 static EXAMPLE_XML: &str = "../data/test/test-example.xml";
 static EXAMPLE_2_XML: &str = "../data/test/test-example-2.xml";
@@ -98,3 +98,27 @@ fn test_example_2_xml() -> Result<(), Box<dyn std::error::Error>> {
     println!("Parsed Rite XML: {:?}", rite);
     Ok(())
 }
+
+#[test]
+fn test_incremental_importer() -> Result<(), Box<dyn std::error::Error>> {
+    let process: Process = serde_xml_rs::from_str(
+        r#"
+        <process id="delta">
+            <importer plugin="db">
+                <incremental field="updated_at" key="since" />
+            </importer>
+            <exporters>
+                <exporter plugin="console" />
+            </exporters>
+        </process>"#,
+    )?;
+    let incremental = process.importer.incremental.unwrap();
+    assert_eq!("updated_at", incremental.field);
+    assert_eq!("since", incremental.key());
+
+    let importer: Importer = serde_xml_rs::from_str(
+        r#"<importer plugin="db"><incremental field="updated_at" /></importer>"#,
+    )?;
+    assert_eq!("high_water_mark", importer.incremental.unwrap().key());
+    Ok(())
+}